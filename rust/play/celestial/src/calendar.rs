@@ -0,0 +1,518 @@
+//! Calendars, converted through the Julian day number: the proleptic Julian
+//! and Gregorian calendars and the switch between them, the Hebrew calendar,
+//! the tabular Islamic calendar and ISO week dates, with the date of Easter.
+//!
+//! The Julian and Gregorian arithmetic and the computus follow Meeus,
+//! *Astronomical Algorithms*, chapters 7 and 8; the Hebrew calendar follows
+//! Reingold and Dershowitz, *Calendrical Calculations*.  Years are
+//! astronomical, so 1 BC is year 0 and 2 BC year -1.
+
+use std::fmt;
+
+use crate::{
+    error::{Error, Result},
+    Day, JulianDay,
+};
+
+/// Julian day number of 1582 October 15, the first day of the Gregorian
+/// calendar.
+pub const GREGORIAN_REFORM: i64 = 2_299_161;
+
+/// Julian day number of 1 Tishri AM 1, the Hebrew epoch.
+const HEBREW_EPOCH: i64 = 347_998;
+
+/// Julian day number of 1 Muharram AH 1, 622 July 16 of the Julian
+/// calendar.
+const ISLAMIC_EPOCH: i64 = 1_948_440;
+
+/// Years either side of zero beyond which no date is worth computing.
+const MAX_YEAR: i64 = 1_000_000_000;
+
+const WEEK: [Day; 7] = [
+    Day::Sunday,
+    Day::Monday,
+    Day::Tuesday,
+    Day::Wednesday,
+    Day::Thursday,
+    Day::Friday,
+    Day::Saturday,
+];
+
+/// A calendar that dates days by their Julian day number, the number of the
+/// noon that falls within the day.
+pub trait Calendar {
+    type Date;
+
+    /// The Julian day number of a date, or an error for a date the calendar
+    /// does not have.
+    fn day_number(&self, date: &Self::Date) -> Result<i64>;
+
+    /// The date of a Julian day number.
+    fn date(&self, day_number: i64) -> Self::Date;
+
+    /// The Julian day of the midnight that begins a date.
+    fn julian_day(&self, date: &Self::Date) -> Result<JulianDay> {
+        Ok(JulianDay::new(self.day_number(date)?, -0.5))
+    }
+
+    /// The date on which a Julian day falls, each day running from
+    /// midnight.
+    fn date_of(&self, jd: JulianDay) -> Self::Date {
+        self.date(civil_day_number(jd))
+    }
+
+    /// The same day in another calendar.
+    fn convert<C: Calendar>(&self, date: &Self::Date, to: &C) -> Result<C::Date> {
+        Ok(to.date(self.day_number(date)?))
+    }
+}
+
+/// A date as a year, month and day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CalendarDate {
+    pub year: i64,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl CalendarDate {
+    pub fn new(year: i64, month: u8, day: u8) -> Self {
+        CalendarDate { year, month, day }
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// The Julian calendar, extended back before its introduction in 45 BC.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Julian;
+
+impl Calendar for Julian {
+    type Date = CalendarDate;
+
+    fn day_number(&self, date: &CalendarDate) -> Result<i64> {
+        check(date, "Julian", 12, |year, month| {
+            days_in_month(month, year.rem_euclid(4) == 0)
+        })?;
+        Ok(julian_day_number(date.year, date.month, date.day))
+    }
+
+    fn date(&self, day_number: i64) -> CalendarDate {
+        julian_date(day_number)
+    }
+}
+
+/// The Gregorian calendar, taking over from the Julian calendar on the day
+/// of a reform.  By default that is 1582 October 15, the day after Julian
+/// October 4, and the dates between are missing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gregorian {
+    reform: Option<i64>,
+}
+
+impl Gregorian {
+    /// The Gregorian calendar extended back to all dates, as chrono's is.
+    pub fn proleptic() -> Self {
+        Gregorian { reform: None }
+    }
+
+    /// Julian dates before the given Julian day number, the first day of
+    /// the Gregorian calendar.  Great Britain and its colonies, for
+    /// example, changed on 1752 September 14, day 2 361 222.
+    pub fn with_reform(first_day: i64) -> Self {
+        Gregorian {
+            reform: Some(first_day),
+        }
+    }
+
+    /// The first day of the Gregorian calendar, if it ever began.
+    pub fn reform(&self) -> Option<i64> {
+        self.reform
+    }
+
+    fn is_julian(&self, day_number: i64) -> bool {
+        self.reform.is_some_and(|reform| day_number < reform)
+    }
+}
+
+impl Default for Gregorian {
+    fn default() -> Self {
+        Gregorian::with_reform(GREGORIAN_REFORM)
+    }
+}
+
+impl Calendar for Gregorian {
+    type Date = CalendarDate;
+
+    fn day_number(&self, date: &CalendarDate) -> Result<i64> {
+        let gregorian = check(date, "Gregorian", 12, |year, month| {
+            days_in_month(month, is_gregorian_leap_year(year))
+        })
+        .map(|_| gregorian_day_number(date.year, date.month, date.day));
+        match (gregorian, Julian.day_number(date)) {
+            (Ok(day_number), _) if !self.is_julian(day_number) => Ok(day_number),
+            (_, Ok(day_number)) if self.is_julian(day_number) => Ok(day_number),
+            (Ok(_), Ok(_)) => Err(Error::InvalidDate(format!(
+                "{} was dropped by the Gregorian reform",
+                date
+            ))),
+            (Err(error), _) | (_, Err(error)) => Err(error),
+        }
+    }
+
+    fn date(&self, day_number: i64) -> CalendarDate {
+        match self.is_julian(day_number) {
+            true => julian_date(day_number),
+            false => gregorian_date(day_number),
+        }
+    }
+}
+
+/// The Hebrew calendar, its months numbered from Nisan, so that the year
+/// begins with Tishri, month 7, and Adar is month 12, or Adar I in a leap
+/// year followed by Adar II as month 13.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Hebrew;
+
+impl Hebrew {
+    pub fn is_leap_year(year: i64) -> bool {
+        (7 * year + 1).rem_euclid(19) < 7
+    }
+
+    /// The number of months in a year, 12 or 13.
+    pub fn months(year: i64) -> u8 {
+        if Hebrew::is_leap_year(year) {
+            13
+        } else {
+            12
+        }
+    }
+
+    /// The number of days in a year, from 353 to 385.
+    pub fn days(year: i64) -> i64 {
+        new_year(year + 1) - new_year(year)
+    }
+
+    pub fn days_in_month(year: i64, month: u8) -> u8 {
+        match month {
+            2 | 4 | 6 | 10 | 13 => 29,
+            12 if !Hebrew::is_leap_year(year) => 29,
+            // Heshvan is long in years of 355 or 385 days and Kislev short
+            // in years of 353 or 383
+            8 if Hebrew::days(year) % 10 != 5 => 29,
+            9 if Hebrew::days(year) % 10 == 3 => 29,
+            _ => 30,
+        }
+    }
+
+    fn first_of_month(year: i64, month: u8) -> i64 {
+        let months = |range: std::ops::Range<u8>| {
+            range
+                .map(|month| Hebrew::days_in_month(year, month) as i64)
+                .sum::<i64>()
+        };
+        let days = match month < 7 {
+            true => months(7..Hebrew::months(year) + 1) + months(1..month),
+            false => months(7..month),
+        };
+        new_year(year) + days
+    }
+}
+
+impl Calendar for Hebrew {
+    type Date = CalendarDate;
+
+    fn day_number(&self, date: &CalendarDate) -> Result<i64> {
+        let year = date.year;
+        check(date, "Hebrew", Hebrew::months(year), Hebrew::days_in_month)?;
+        Ok(Hebrew::first_of_month(year, date.month) + date.day as i64 - 1)
+    }
+
+    fn date(&self, day_number: i64) -> CalendarDate {
+        // From the mean length of a year, which can be a year either way
+        let approximate =
+            ((day_number - HEBREW_EPOCH) as i128 * 98_496).div_euclid(35_975_351) as i64 + 1;
+        let year = (approximate - 1..=approximate + 1)
+            .rev()
+            .find(|&year| new_year(year) <= day_number)
+            .unwrap_or(approximate - 1);
+        let start = match day_number < Hebrew::first_of_month(year, 1) {
+            true => 7,
+            false => 1,
+        };
+        let month = (start..Hebrew::months(year))
+            .find(|&month| {
+                day_number
+                    < Hebrew::first_of_month(year, month)
+                        + Hebrew::days_in_month(year, month) as i64
+            })
+            .unwrap_or_else(|| Hebrew::months(year));
+        let day = day_number - Hebrew::first_of_month(year, month) + 1;
+
+        CalendarDate::new(year, month, day as u8)
+    }
+}
+
+/// Days from the epoch to the molad of Tishri, put off a day when it would
+/// fall on a Sunday, Wednesday or Friday.
+fn elapsed_days(year: i64) -> i64 {
+    let months = (235 * year - 234).div_euclid(19);
+    let parts = 12_084 + 13_753 * months;
+    let day = 29 * months + parts.div_euclid(25_920);
+    match (3 * (day + 1)).rem_euclid(7) < 3 {
+        true => day + 1,
+        false => day,
+    }
+}
+
+/// Julian day number of 1 Tishri, put off further to keep the years to
+/// their allowed lengths.
+fn new_year(year: i64) -> i64 {
+    let (previous, this, next) = (
+        elapsed_days(year - 1),
+        elapsed_days(year),
+        elapsed_days(year + 1),
+    );
+    let delay = if next - this == 356 {
+        2
+    } else if this - previous == 382 {
+        1
+    } else {
+        0
+    };
+    HEBREW_EPOCH + this + delay
+}
+
+/// The tabular Islamic calendar, of 30-year cycles of which years 2, 5, 7,
+/// 10, 13, 16, 18, 21, 24, 26 and 29 have a thirtieth day in the last month,
+/// counted from the civil epoch.  Observed months begin with the sighting
+/// of the crescent and can fall a day or two either side.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Islamic;
+
+impl Islamic {
+    pub fn is_leap_year(year: i64) -> bool {
+        (14 + 11 * year).rem_euclid(30) < 11
+    }
+}
+
+impl Calendar for Islamic {
+    type Date = CalendarDate;
+
+    fn day_number(&self, date: &CalendarDate) -> Result<i64> {
+        check(date, "Islamic", 12, |year, month| match month {
+            12 if Islamic::is_leap_year(year) => 30,
+            month if month % 2 == 1 => 30,
+            _ => 29,
+        })?;
+        let (year, month) = (date.year, date.month as i64);
+        Ok(date.day as i64
+            + (59 * (month - 1) + 1) / 2
+            + 354 * (year - 1)
+            + (3 + 11 * year).div_euclid(30)
+            + ISLAMIC_EPOCH
+            - 1)
+    }
+
+    fn date(&self, day_number: i64) -> CalendarDate {
+        let first = |year: i64, month: u8| {
+            Islamic
+                .day_number(&CalendarDate::new(year, month, 1))
+                .unwrap_or(day_number)
+        };
+        let year = (30 * (day_number - ISLAMIC_EPOCH) + 10_646).div_euclid(10_631);
+        let month = (1..12)
+            .rev()
+            .find(|&month| first(year, month + 1) <= day_number)
+            .map_or(1, |month| month + 1);
+        let day = day_number - first(year, month) + 1;
+
+        CalendarDate::new(year, month, day as u8)
+    }
+}
+
+/// An ISO 8601 week date: weeks run from Monday, and week 1 of a year is
+/// the one with its first Thursday.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IsoWeekDate {
+    pub year: i64,
+    pub week: u8,
+    pub weekday: Day,
+}
+
+impl fmt::Display for IsoWeekDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-W{:02}-{}",
+            self.year,
+            self.week,
+            iso_weekday(self.weekday)
+        )
+    }
+}
+
+/// ISO week dates, always on the proleptic Gregorian calendar.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct IsoWeek;
+
+impl IsoWeek {
+    /// The number of weeks in a year, 52 or 53.
+    pub fn weeks(year: i64) -> u8 {
+        ((week_one(year + 1) - week_one(year)) / 7) as u8
+    }
+}
+
+impl Calendar for IsoWeek {
+    type Date = IsoWeekDate;
+
+    fn day_number(&self, date: &IsoWeekDate) -> Result<i64> {
+        if date.year.abs() > MAX_YEAR || !(1..=IsoWeek::weeks(date.year)).contains(&date.week) {
+            return Err(Error::InvalidDate(format!("{} as an ISO week date", date)));
+        }
+        Ok(week_one(date.year) + 7 * (date.week as i64 - 1) + iso_weekday(date.weekday) - 1)
+    }
+
+    fn date(&self, day_number: i64) -> IsoWeekDate {
+        let thursday = day_number - day_number.rem_euclid(7) + 3;
+        let year = gregorian_date(thursday).year;
+
+        IsoWeekDate {
+            year,
+            week: ((thursday - week_one(year)) / 7 + 1) as u8,
+            weekday: day_of_week(JulianDay::new(day_number, 0.)),
+        }
+    }
+}
+
+/// Julian day number of the Monday that begins week 1.
+fn week_one(year: i64) -> i64 {
+    let january_4 = gregorian_day_number(year, 1, 4);
+    january_4 - january_4.rem_euclid(7)
+}
+
+/// Monday as 1 through to Sunday as 7.
+fn iso_weekday(day: Day) -> i64 {
+    match day {
+        Day::Sunday => 7,
+        day => day as i64,
+    }
+}
+
+/// The day of the week on which a Julian day falls.
+pub fn day_of_week(jd: JulianDay) -> Day {
+    WEEK[(civil_day_number(jd) + 1).rem_euclid(7) as usize]
+}
+
+/// Easter Sunday in the Gregorian calendar, by the anonymous algorithm of
+/// 1876 that Meeus gives.  It holds for 1583 onwards.
+pub fn easter(year: i64) -> CalendarDate {
+    let a = year.rem_euclid(19);
+    let (b, c) = (year.div_euclid(100), year.rem_euclid(100));
+    let (d, e) = (b.div_euclid(4), b.rem_euclid(4));
+    let f = (b + 8).div_euclid(25);
+    let g = (b - f + 1).div_euclid(3);
+    let h = (19 * a + b - d - g + 15).rem_euclid(30);
+    let (i, k) = (c.div_euclid(4), c.rem_euclid(4));
+    let l = (32 + 2 * e + 2 * i - h - k).rem_euclid(7);
+    let m = (a + 11 * h + 22 * l).div_euclid(451);
+    let n = h + l - 7 * m + 114;
+
+    CalendarDate::new(year, (n / 31) as u8, (n % 31 + 1) as u8)
+}
+
+/// Easter Sunday in the Julian calendar, as the Orthodox churches still
+/// reckon it.
+pub fn julian_easter(year: i64) -> CalendarDate {
+    let (a, b, c) = (year.rem_euclid(4), year.rem_euclid(7), year.rem_euclid(19));
+    let d = (19 * c + 15) % 30;
+    let e = (2 * a + 4 * b - d + 34).rem_euclid(7);
+    let n = d + e + 114;
+
+    CalendarDate::new(year, (n / 31) as u8, (n % 31 + 1) as u8)
+}
+
+/// Julian day number of a date in the Julian calendar, which need not be
+/// valid.
+pub(crate) fn julian_day_number(year: i64, month: u8, day: u8) -> i64 {
+    let (y, m) = march_year(year, month);
+    day as i64 + (153 * m + 2) / 5 + 365 * y + y.div_euclid(4) - 32_083
+}
+
+/// Julian day number of a date in the proleptic Gregorian calendar, which
+/// need not be valid.
+pub(crate) fn gregorian_day_number(year: i64, month: u8, day: u8) -> i64 {
+    let (y, m) = march_year(year, month);
+    day as i64 + (153 * m + 2) / 5 + 365 * y + y.div_euclid(4) - y.div_euclid(100)
+        + y.div_euclid(400)
+        - 32_045
+}
+
+/// Years from March of 4801 BC, and months from March.
+fn march_year(year: i64, month: u8) -> (i64, i64) {
+    let a = (14 - month as i64) / 12;
+    (year + 4_800 - a, month as i64 + 12 * a - 3)
+}
+
+fn julian_date(day_number: i64) -> CalendarDate {
+    from_march_year(0, day_number + 32_082)
+}
+
+fn gregorian_date(day_number: i64) -> CalendarDate {
+    let a = day_number + 32_044;
+    let b = (4 * a + 3).div_euclid(146_097);
+    from_march_year(100 * b, a - (146_097 * b).div_euclid(4))
+}
+
+/// The date from whole centuries since March of 4801 BC and the days since.
+fn from_march_year(centuries: i64, c: i64) -> CalendarDate {
+    let d = (4 * c + 3).div_euclid(1_461);
+    let e = c - (1_461 * d).div_euclid(4);
+    let m = (5 * e + 2) / 153;
+
+    CalendarDate::new(
+        centuries + d - 4_800 + m / 10,
+        (m + 3 - 12 * (m / 10)) as u8,
+        (e - (153 * m + 2) / 5 + 1) as u8,
+    )
+}
+
+fn is_gregorian_leap_year(year: i64) -> bool {
+    (year.rem_euclid(4) == 0 && year.rem_euclid(100) != 0) || year.rem_euclid(400) == 0
+}
+
+fn days_in_month(month: u8, leap: bool) -> u8 {
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// An error unless the month and day of a date are in range.
+fn check<F>(date: &CalendarDate, calendar: &str, months: u8, days: F) -> Result<()>
+where
+    F: Fn(i64, u8) -> u8,
+{
+    let valid = date.year.abs() <= MAX_YEAR
+        && (1..=months).contains(&date.month)
+        && (1..=days(date.year, date.month)).contains(&date.day);
+    match valid {
+        true => Ok(()),
+        false => Err(Error::InvalidDate(format!(
+            "{} in the {} calendar",
+            date, calendar
+        ))),
+    }
+}
+
+/// The number of the day, running from midnight, on which a Julian day
+/// falls.
+fn civil_day_number(jd: JulianDay) -> i64 {
+    (jd + 0.5).day()
+}
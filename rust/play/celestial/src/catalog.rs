@@ -0,0 +1,295 @@
+//! Star catalogs read from local files: the Yale Bright Star Catalogue, 5th
+//! edition (BSC5), in its fixed-width form, or a CSV subset of Hipparcos.
+//!
+//! Places are mean places on the equator and equinox of J2000.0, at the
+//! epoch the catalog gives them for: J2000.0 for BSC5 and J1991.25 for
+//! Hipparcos.  Proper motion carries a star to any other epoch, after which
+//! [`crate::corrections::Reduction`] takes it to where it is seen.
+
+use std::{fs, ops::RangeInclusive, path::Path, str::FromStr};
+
+use chrono::{DateTime, TimeZone};
+
+use crate::{
+    coords::{Equatorial, Horizon},
+    corrections::Reduction,
+    degrees,
+    error::{Error, Result},
+    hours,
+    observer::Observer,
+    Angle, JulianDay,
+};
+
+/// A star from a catalog.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Star {
+    /// Number in the Bright Star Catalogue.
+    pub hr: Option<u32>,
+    /// Number in the Hipparcos catalog.
+    pub hip: Option<u32>,
+    /// Bayer or Flamsteed designation such as `3Alp Lyr` from BSC5, or
+    /// whatever name the CSV gives.
+    pub name: Option<String>,
+    /// Mean place at `epoch`.
+    pub position: Equatorial,
+    pub epoch: JulianDay,
+    /// Proper motion in right ascension in arcseconds a year, measured on
+    /// the sky (μα cos δ).
+    pub pm_ra: f64,
+    /// Proper motion in declination in arcseconds a year.
+    pub pm_dec: f64,
+    /// Visual magnitude.
+    pub magnitude: f64,
+    /// MK spectral class, such as `A0Va`.
+    pub spectral_class: String,
+}
+
+/// A list of stars, with queries over them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Catalog {
+    stars: Vec<Star>,
+}
+
+impl Star {
+    /// Mean place at another epoch, moving the star in a straight line at
+    /// its proper motion (Meeus, chapter 21).  Good for centuries, except
+    /// for fast stars close to a pole.
+    pub fn position_at(&self, jd: JulianDay) -> Equatorial {
+        let years = (jd - self.epoch) / 365.25;
+        let ra = self.position.ra_hours()
+            + self.pm_ra * years / 3600. / 15. / self.position.dec.to_radians().value.cos();
+        let dec = self.position.dec_degrees() + self.pm_dec * years / 3600.;
+
+        Equatorial::new(hours(ra.rem_euclid(24.)), degrees(dec))
+    }
+
+    /// Whether a name picks out this star, ignoring case and spaces and
+    /// with or without a Flamsteed number, so that `alp lyr` finds Vega.
+    fn is_called(&self, name: &str) -> bool {
+        let squash = |s: &str| -> String {
+            s.chars()
+                .filter(|c| !c.is_whitespace())
+                .flat_map(char::to_lowercase)
+                .collect()
+        };
+        let wanted = squash(name);
+
+        self.name.as_deref().is_some_and(|own| {
+            let own = squash(own);
+            own == wanted || own.trim_start_matches(|c: char| c.is_ascii_digit()) == wanted
+        })
+    }
+}
+
+impl Catalog {
+    pub fn new(stars: Vec<Star>) -> Self {
+        Catalog { stars }
+    }
+
+    /// Read a catalog file in either format.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Parse the fixed-width BSC5 `catalog` file.  Its handful of entries
+    /// without a place or magnitude, novae and other objects that were
+    /// dropped, are skipped.
+    pub fn from_bsc5(s: &str) -> Result<Self> {
+        let mut stars = Vec::new();
+
+        for (n, line) in s.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            let field = |columns: RangeInclusive<usize>| {
+                let end = (*columns.end()).min(line.len());
+                line.get(columns.start() - 1..end).unwrap_or("").trim()
+            };
+            let number = |columns: RangeInclusive<usize>, what: &str| {
+                parse_field::<f64>(field(columns), what, n + 1)
+            };
+
+            let hr = parse_field::<u32>(field(1..=4), "HR number", n + 1)?;
+            let (ra, dec, magnitude) = match (
+                number(76..=77, "RA")?,
+                number(85..=86, "declination")?,
+                number(103..=107, "magnitude")?,
+            ) {
+                (Some(h), Some(d), Some(magnitude)) => (h, d, magnitude),
+                _ => continue,
+            };
+            let ra = Angle::from_hms_tuple((
+                ra,
+                number(78..=79, "RA")?.unwrap_or(0.),
+                number(80..=83, "RA")?.unwrap_or(0.),
+            ));
+            let dec = dec
+                + number(87..=88, "declination")?.unwrap_or(0.) / 60.
+                + number(89..=90, "declination")?.unwrap_or(0.) / 3600.;
+            let sign = if field(84..=84) == "-" { -1. } else { 1. };
+
+            stars.push(Star {
+                hr,
+                hip: None,
+                name: Some(field(5..=14))
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string),
+                position: Equatorial::new(ra, degrees(sign * dec)),
+                epoch: JulianDay::J2000,
+                pm_ra: number(149..=154, "proper motion")?.unwrap_or(0.),
+                pm_dec: number(155..=160, "proper motion")?.unwrap_or(0.),
+                magnitude,
+                spectral_class: field(128..=147).to_string(),
+            });
+        }
+
+        Ok(Catalog::new(stars))
+    }
+
+    /// Parse a CSV extract of Hipparcos with a header line naming its
+    /// columns as the catalog does: `HIP`, `RAdeg`, `DEdeg`, `pmRA` and
+    /// `pmDE` in milliarcseconds a year, `Vmag` and `SpType`, along with
+    /// `HR` and `Name` if there are any.  Only the places and magnitude are
+    /// needed, and rows without them are skipped.
+    pub fn from_hipparcos_csv(s: &str) -> Result<Self> {
+        let mut lines = s.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+        let header = match lines.next() {
+            Some((_, header)) => split_csv(header),
+            None => return Ok(Catalog::default()),
+        };
+        let column = |name: &str| header.iter().position(|h| h.eq_ignore_ascii_case(name));
+        let required =
+            |name: &str| column(name).ok_or_else(|| Error::Parse(format!("no {} column", name)));
+        let (ra, dec, vmag) = (required("RAdeg")?, required("DEdeg")?, required("Vmag")?);
+        let (hip, hr, name) = (column("HIP"), column("HR"), column("Name"));
+        let (pm_ra, pm_dec, sp_type) = (column("pmRA"), column("pmDE"), column("SpType"));
+
+        let mut stars = Vec::new();
+        for (n, line) in lines {
+            let fields = split_csv(line);
+            let field = |i: Option<usize>| i.and_then(|i| fields.get(i)).copied().unwrap_or("");
+            let number = |i: usize, what: &str| parse_field::<f64>(field(Some(i)), what, n + 1);
+            let mas = |i: Option<usize>| -> Result<f64> {
+                Ok(parse_field::<f64>(field(i), "proper motion", n + 1)?.unwrap_or(0.) / 1000.)
+            };
+
+            let (ra, dec, magnitude) = match (
+                number(ra, "RA")?,
+                number(dec, "declination")?,
+                number(vmag, "magnitude")?,
+            ) {
+                (Some(ra), Some(dec), Some(magnitude)) => (ra, dec, magnitude),
+                _ => continue,
+            };
+
+            stars.push(Star {
+                hr: parse_field(field(hr), "HR number", n + 1)?,
+                hip: parse_field(field(hip), "HIP number", n + 1)?,
+                name: Some(field(name))
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string),
+                position: Equatorial::new(degrees(ra).to_hours(), degrees(dec)),
+                epoch: hipparcos_epoch(),
+                pm_ra: mas(pm_ra)?,
+                pm_dec: mas(pm_dec)?,
+                magnitude,
+                spectral_class: field(sp_type).to_string(),
+            });
+        }
+
+        Ok(Catalog::new(stars))
+    }
+
+    pub fn stars(&self) -> &[Star] {
+        &self.stars
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<&Star> {
+        self.stars.iter().find(|star| star.is_called(name))
+    }
+
+    pub fn by_hr(&self, hr: u32) -> Option<&Star> {
+        self.stars.iter().find(|star| star.hr == Some(hr))
+    }
+
+    pub fn by_hip(&self, hip: u32) -> Option<&Star> {
+        self.stars.iter().find(|star| star.hip == Some(hip))
+    }
+
+    /// Stars within a radius of a place, nearest first, with their proper
+    /// motion carried to a date.
+    pub fn cone(&self, centre: Equatorial, radius: Angle, jd: JulianDay) -> Vec<&Star> {
+        let mut found: Vec<_> = self
+            .stars
+            .iter()
+            .map(|star| (star, star.position_at(jd).separation(&centre)))
+            .filter(|(_, separation)| *separation <= radius)
+            .collect();
+        found.sort_by(|a, b| a.1.norm().total_cmp(&b.1.norm()));
+
+        found.into_iter().map(|(star, _)| star).collect()
+    }
+
+    /// The brightest stars an observer sees above an altitude, brightest
+    /// first, with where they are seen through the air at the
+    /// site.
+    pub fn brightest_visible<Tz: TimeZone>(
+        &self,
+        count: usize,
+        dt: DateTime<Tz>,
+        observer: &Observer,
+        altitude: Angle,
+    ) -> Vec<(&Star, Horizon)> {
+        let jd = JulianDay::from(dt.clone());
+        let reduction = Reduction::default();
+        let mut stars: Vec<_> = self.stars.iter().collect();
+        stars.sort_by(|a, b| a.magnitude.total_cmp(&b.magnitude));
+
+        stars
+            .into_iter()
+            .map(|star| {
+                let horizon = reduction.observed(star.position_at(jd), dt.clone(), observer);
+                (star, horizon)
+            })
+            .filter(|(_, horizon)| horizon.altitude >= altitude)
+            .take(count)
+            .collect()
+    }
+}
+
+impl FromStr for Catalog {
+    type Err = Error;
+
+    /// Parse either format, taking a first line with commas in it for the
+    /// header of a CSV file.
+    fn from_str(s: &str) -> Result<Self> {
+        let first = s.lines().find(|line| !line.trim().is_empty());
+        let catalog = match first {
+            Some(line) if line.contains(',') => Catalog::from_hipparcos_csv(s)?,
+            _ => Catalog::from_bsc5(s)?,
+        };
+
+        if catalog.stars.is_empty() {
+            return Err(Error::Parse("no stars found".to_string()));
+        }
+        Ok(catalog)
+    }
+}
+
+/// The Hipparcos catalog epoch, J1991.25 TT.
+fn hipparcos_epoch() -> JulianDay {
+    JulianDay::new(2_448_349, 0.0625)
+}
+
+/// A field that may be blank, or an error naming the line it is on.
+fn parse_field<T: FromStr>(text: &str, what: &str, line: usize) -> Result<Option<T>> {
+    if text.is_empty() {
+        return Ok(None);
+    }
+    text.parse()
+        .map(Some)
+        .map_err(|_| Error::Parse(format!("bad {} on line {}: {:?}", what, line, text)))
+}
+
+fn split_csv(line: &str) -> Vec<&str> {
+    line.split(',')
+        .map(|field| field.trim().trim_matches('"').trim())
+        .collect()
+}
@@ -0,0 +1,522 @@
+//! Sky charts drawn as SVG: the whole sky above an observer at a moment,
+//! seen looking up, with north at the top and east on the left as when a
+//! chart is held overhead.
+//!
+//! Stars come from a [`Catalog`], and the figures of the constellations
+//! from a local file of lines between Hipparcos stars, in the format of
+//! Stellarium's `constellationship.fab`.  Places are apparent places without
+//! refraction, so the edge of the chart is the geometric horizon.  Every
+//! coordinate is rounded to a tenth of a pixel and everything is drawn in a
+//! fixed order, so the same inputs always give the same file.
+
+use std::{
+    collections::HashMap,
+    f64::consts::FRAC_PI_2,
+    fmt::{self, Write},
+    fs,
+    path::Path,
+    str::FromStr,
+};
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::{
+    catalog::Catalog,
+    coords::{Equatorial, Horizon},
+    corrections::Reduction,
+    degrees,
+    error::{Error, Result},
+    hours,
+    moon::{self, LunarPosition},
+    observer::Observer,
+    planets::{Planet, Theory},
+    sun::SolarPosition,
+    timescale::{JulianDate, Tt},
+    Angle, JulianDay,
+};
+
+/// Room around the horizon for the cardinal points, in pixels.
+const MARGIN: f64 = 30.;
+
+/// Step along the lines of the grid, in degrees.
+const GRID_STEP: f64 = 2.;
+
+const STYLE: &str = "
+.background { fill: #ffffff; }
+.sky { fill: #0b1a33; }
+.horizon { fill: none; stroke: #000000; stroke-width: 2; }
+.grid { fill: none; stroke: #3a5a8c; stroke-width: 0.5; }
+.figure { stroke: #7f9fcf; stroke-width: 1; }
+.star { fill: #ffffff; }
+.planet { fill: #ffd070; }
+.moon-dark { fill: #3c3c3c; }
+.moon-lit { fill: #f4f4e8; }
+text { font-family: sans-serif; font-size: 12px; }
+.label { fill: #ffd070; }
+.cardinal { fill: #000000; font-size: 16px; font-weight: bold; text-anchor: middle; dominant-baseline: middle; }
+.caption { fill: #000000; }
+";
+
+/// How the hemisphere of the sky is flattened onto the page.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Projection {
+    /// Keeps the shapes of constellations, at the cost of stretching them
+    /// towards the horizon.
+    Stereographic,
+    /// The sky as a globe seen from far away, crowding the horizon.
+    Orthographic,
+}
+
+/// The figure of one constellation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Figure {
+    /// Abbreviation, such as `Ori`.
+    pub abbreviation: String,
+    /// Lines between stars by their Hipparcos numbers.
+    pub lines: Vec<(u32, u32)>,
+}
+
+/// The figures of the constellations.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Constellations {
+    figures: Vec<Figure>,
+}
+
+/// What to draw, and how big.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chart {
+    pub projection: Projection,
+    /// Width and height in pixels.
+    pub size: f64,
+    /// Stars fainter than this are left out.
+    pub magnitude_limit: f64,
+    /// Spacing of the lines of right ascension and declination, if there is
+    /// to be a grid.
+    pub grid: Option<(Angle, Angle)>,
+}
+
+/// Where things are drawn on the page.
+struct Frame {
+    projection: Projection,
+    centre: f64,
+    radius: f64,
+}
+
+impl Constellations {
+    pub fn new(figures: Vec<Figure>) -> Self {
+        Constellations { figures }
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    pub fn figures(&self) -> &[Figure] {
+        &self.figures
+    }
+}
+
+impl FromStr for Constellations {
+    type Err = Error;
+
+    /// Parse lines of an abbreviation, the number of lines in the figure and
+    /// then the Hipparcos numbers of the two ends of each, all separated by
+    /// spaces.  Blank lines and lines starting with `#` are skipped.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut figures = Vec::new();
+
+        for (n, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || Error::Parse(format!("bad constellation on line {}", n + 1));
+            let mut words = line.split_whitespace();
+            let abbreviation = words.next().ok_or_else(error)?.to_string();
+            let count: usize = words
+                .next()
+                .and_then(|w| w.parse().ok())
+                .ok_or_else(error)?;
+            let numbers = words
+                .map(|word| word.parse::<u32>().map_err(|_| error()))
+                .collect::<Result<Vec<_>>>()?;
+            if numbers.len() != 2 * count {
+                return Err(error());
+            }
+
+            figures.push(Figure {
+                abbreviation,
+                lines: numbers.chunks(2).map(|pair| (pair[0], pair[1])).collect(),
+            });
+        }
+
+        Ok(Constellations::new(figures))
+    }
+}
+
+impl Chart {
+    /// Draw the sky for an observer at a moment, or an error for a grid
+    /// whose lines aren't a positive step apart.
+    pub fn render<Tz: TimeZone>(
+        &self,
+        catalog: &Catalog,
+        constellations: &Constellations,
+        dt: DateTime<Tz>,
+        observer: &Observer,
+    ) -> Result<String> {
+        if let Some((ra_step, dec_step)) = self.grid {
+            if !(ra_step > degrees(0.) && dec_step > degrees(0.)) {
+                return Err(Error::Parse(format!(
+                    "grid steps of {}° and {}° are not positive",
+                    ra_step.to_degrees(),
+                    dec_step.to_degrees()
+                )));
+            }
+        }
+
+        let mut svg = String::new();
+        self.draw(&mut svg, catalog, constellations, dt, observer)
+            .expect("writing to a string");
+        Ok(svg)
+    }
+
+    fn draw<Tz: TimeZone>(
+        &self,
+        svg: &mut String,
+        catalog: &Catalog,
+        constellations: &Constellations,
+        dt: DateTime<Tz>,
+        observer: &Observer,
+    ) -> fmt::Result {
+        let jd = JulianDay::from(dt.clone());
+        let frame = Frame {
+            projection: self.projection,
+            centre: self.size / 2.,
+            radius: self.size / 2. - MARGIN,
+        };
+        let (size, centre, radius) = (self.size, frame.centre, frame.radius);
+
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size:.0}" height="{size:.0}" viewBox="0 0 {size:.0} {size:.0}">"#,
+            size = size
+        )?;
+        writeln!(svg, "<style>{}</style>", STYLE)?;
+        writeln!(
+            svg,
+            r#"<rect class="background" width="{:.0}" height="{:.0}"/>"#,
+            size, size
+        )?;
+        writeln!(
+            svg,
+            r#"<circle class="sky" cx="{:.1}" cy="{:.1}" r="{:.1}"/>"#,
+            centre, centre, radius
+        )?;
+
+        if let Some((ra_step, dec_step)) = self.grid {
+            self.draw_grid(svg, &frame, ra_step, dec_step, observer, jd)?;
+        }
+
+        let reduction = Reduction {
+            refraction: false,
+            ..Reduction::default()
+        };
+        let seen = |position: Equatorial| reduction.observed(position, dt.clone(), observer);
+        let places: Vec<_> = catalog
+            .stars()
+            .iter()
+            .map(|star| (star, frame.project(seen(star.position_at(jd)))))
+            .collect();
+
+        // By Hipparcos number, the first in the catalog winning
+        let by_hip: HashMap<u32, Option<(f64, f64)>> = places
+            .iter()
+            .rev()
+            .filter_map(|(star, place)| star.hip.map(|hip| (hip, *place)))
+            .collect();
+        for figure in constellations.figures() {
+            for &(from, to) in &figure.lines {
+                let end = |hip: u32| by_hip.get(&hip).copied().flatten();
+                if let (Some((x1, y1)), Some((x2, y2))) = (end(from), end(to)) {
+                    writeln!(
+                        svg,
+                        r#"<line class="figure" x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}"/>"#,
+                        x1, y1, x2, y2
+                    )?;
+                }
+            }
+        }
+
+        let mut stars: Vec<_> = places
+            .iter()
+            .filter(|(star, _)| star.magnitude <= self.magnitude_limit)
+            .filter_map(|(star, place)| place.map(|place| (star.magnitude, place)))
+            .collect();
+        // Faintest first, so that the bright ones are drawn on top
+        stars.sort_by(|a, b| b.0.total_cmp(&a.0));
+        for (magnitude, (x, y)) in stars {
+            writeln!(
+                svg,
+                r#"<circle class="star" cx="{:.1}" cy="{:.1}" r="{:.1}"/>"#,
+                x,
+                y,
+                self.disc(magnitude)
+            )?;
+        }
+
+        let tt = JulianDate::<Tt>::from_datetime(dt.clone()).jd;
+        for planet in Planet::ALL.iter() {
+            let position = planet.geocentric(tt, Theory::Vsop87);
+            if let Some((x, y)) = frame.project(observer.horizon(position.equatorial, jd)) {
+                writeln!(
+                    svg,
+                    r#"<circle class="planet" cx="{:.1}" cy="{:.1}" r="{:.1}"/>"#,
+                    x,
+                    y,
+                    self.disc(position.magnitude)
+                )?;
+                label(svg, x, y, planet.name())?;
+            }
+        }
+
+        self.draw_moon(svg, &frame, dt.clone(), observer)?;
+
+        writeln!(
+            svg,
+            r#"<circle class="horizon" cx="{:.1}" cy="{:.1}" r="{:.1}"/>"#,
+            centre, centre, radius
+        )?;
+
+        for (letter, azimuth) in &[("N", 0.), ("E", 90.), ("S", 180.), ("W", 270.)] {
+            let (x, y) = frame.place(degrees(*azimuth), radius + MARGIN / 2.);
+            writeln!(
+                svg,
+                r#"<text class="cardinal" x="{:.1}" y="{:.1}">{}</text>"#,
+                x, y, letter
+            )?;
+        }
+
+        writeln!(
+            svg,
+            r#"<text class="caption" x="4" y="{:.1}">{:.2}°{} {:.2}°{} {}</text>"#,
+            size - 4.,
+            observer.latitude.abs(),
+            if observer.latitude < 0. { 'S' } else { 'N' },
+            observer.longitude.abs(),
+            if observer.longitude < 0. { 'W' } else { 'E' },
+            dt.with_timezone(&Utc).format("%Y-%m-%d %H:%M UTC")
+        )?;
+        writeln!(svg, "</svg>")
+    }
+
+    /// Lines of right ascension from pole to pole and of declination all the
+    /// way round, as far as they are above the horizon.
+    fn draw_grid(
+        &self,
+        svg: &mut String,
+        frame: &Frame,
+        ra_step: Angle,
+        dec_step: Angle,
+        observer: &Observer,
+        jd: JulianDay,
+    ) -> fmt::Result {
+        let (ra_step, dec_step) = (ra_step.to_degrees().value, dec_step.to_degrees().value);
+        let sky = |ra: f64, dec: f64| {
+            observer.horizon(Equatorial::new(degrees(ra).to_hours(), degrees(dec)), jd)
+        };
+
+        let mut ra = 0.;
+        while ra < 360. {
+            frame.trace(svg, -90., 90., |dec| sky(ra, dec))?;
+            ra += ra_step;
+        }
+        let mut dec = dec_step - 90.;
+        while dec < 90. {
+            frame.trace(svg, 0., 360., |ra| sky(ra, dec))?;
+            dec += dec_step;
+        }
+
+        Ok(())
+    }
+
+    /// The Moon with its lit part turned to the Sun.
+    fn draw_moon<Tz: TimeZone>(
+        &self,
+        svg: &mut String,
+        frame: &Frame,
+        dt: DateTime<Tz>,
+        observer: &Observer,
+    ) -> fmt::Result {
+        let jd = JulianDay::from(dt.clone());
+        let tt = JulianDate::<Tt>::from_datetime(dt.clone()).jd;
+        let moon = LunarPosition::from_julian_day(tt);
+        let place = observer.horizon(observer.topocentric(moon.equatorial, moon.parallax, jd), jd);
+        let (x, y) = match frame.project(place) {
+            Some(point) => point,
+            None => return Ok(()),
+        };
+
+        // A point a degree from the Moon towards the Sun shows which way the
+        // bright limb faces on the page
+        let sun = observer.horizon(SolarPosition::from_julian_day(tt).equatorial, jd);
+        let towards = frame.at(step_towards(place, sun, 1.));
+        let angle = (towards.1 - y).atan2(towards.0 - x).to_degrees();
+
+        let r = 8. * self.size / 800.;
+        let lit = moon::illuminated_fraction(dt);
+        writeln!(
+            svg,
+            r#"<g transform="translate({:.1} {:.1}) rotate({:.1})">"#,
+            x, y, angle
+        )?;
+        writeln!(svg, r#"<circle class="moon-dark" r="{:.1}"/>"#, r)?;
+        writeln!(
+            svg,
+            r#"<path class="moon-lit" d="M 0,{:.1} A {:.1},{:.1} 0 0 1 0,{:.1} A {:.1},{:.1} 0 0 {} 0,{:.1} Z"/>"#,
+            -r,
+            r,
+            r,
+            r,
+            r * (1. - 2. * lit).abs(),
+            r,
+            if lit > 0.5 { 1 } else { 0 },
+            -r
+        )?;
+        writeln!(svg, "</g>")?;
+        label(svg, x + r - 6., y, "Moon")
+    }
+
+    /// Radius of a star or planet of a magnitude, in pixels.
+    fn disc(&self, magnitude: f64) -> f64 {
+        (0.8 + 0.55 * (self.magnitude_limit - magnitude)).max(0.8) * self.size / 800.
+    }
+}
+
+impl Default for Chart {
+    /// An 800 pixel stereographic chart of stars to magnitude 5.5, with a
+    /// grid every two hours and thirty degrees.
+    fn default() -> Self {
+        Chart {
+            projection: Projection::Stereographic,
+            size: 800.,
+            magnitude_limit: 5.5,
+            grid: Some((hours(2.), degrees(30.))),
+        }
+    }
+}
+
+impl Frame {
+    /// Where a place above the horizon is drawn.
+    fn project(&self, horizon: Horizon) -> Option<(f64, f64)> {
+        match horizon.altitude >= degrees(0.) {
+            true => Some(self.at(horizon)),
+            false => None,
+        }
+    }
+
+    /// Where a place is drawn, or would be if it were above the horizon.
+    fn at(&self, horizon: Horizon) -> (f64, f64) {
+        let zenith = FRAC_PI_2 - horizon.altitude.to_radians().value;
+        let distance = match self.projection {
+            Projection::Stereographic => (zenith / 2.).tan(),
+            Projection::Orthographic => zenith.sin(),
+        };
+        self.place(horizon.azimuth, self.radius * distance)
+    }
+
+    /// A point some distance from the centre towards an azimuth.
+    fn place(&self, azimuth: Angle, distance: f64) -> (f64, f64) {
+        let azimuth = azimuth.to_radians().value;
+        (
+            self.centre - distance * azimuth.sin(),
+            self.centre - distance * azimuth.cos(),
+        )
+    }
+
+    /// A line through the places along a path in the sky from one value of
+    /// its parameter to another, broken where it goes below the horizon and
+    /// ended on the horizon where it crosses.
+    fn trace<F: Fn(f64) -> Horizon>(
+        &self,
+        svg: &mut String,
+        from: f64,
+        to: f64,
+        path: F,
+    ) -> fmt::Result {
+        let altitude = |horizon: &Horizon| horizon.altitude.to_degrees().value;
+        let mut segments: Vec<Vec<(f64, f64)>> = vec![Vec::new()];
+        let steps = ((to - from) / GRID_STEP).round() as usize;
+        let mut previous: Option<(f64, Horizon)> = None;
+
+        for i in 0..=steps {
+            let parameter = from + (to - from) * i as f64 / steps as f64;
+            let here = path(parameter);
+
+            if let Some((last, before)) = previous {
+                if (altitude(&before) >= 0.) != (altitude(&here) >= 0.) {
+                    let fraction = altitude(&before) / (altitude(&before) - altitude(&here));
+                    let crossing = path(last + fraction * (parameter - last));
+                    segments.last_mut().unwrap().push(self.at(Horizon {
+                        altitude: degrees(0.),
+                        ..crossing
+                    }));
+                    if altitude(&here) < 0. {
+                        segments.push(Vec::new());
+                    }
+                }
+            }
+            if altitude(&here) >= 0. {
+                segments.last_mut().unwrap().push(self.at(here));
+            }
+            previous = Some((parameter, here));
+        }
+
+        for points in segments.iter().filter(|points| points.len() > 1) {
+            let mut d = String::new();
+            for (i, (x, y)) in points.iter().enumerate() {
+                write!(d, "{}{:.1},{:.1}", if i == 0 { "M " } else { " L " }, x, y)?;
+            }
+            writeln!(svg, r#"<path class="grid" d="{}"/>"#, d)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn label(svg: &mut String, x: f64, y: f64, text: &str) -> fmt::Result {
+    writeln!(
+        svg,
+        r#"<text class="label" x="{:.1}" y="{:.1}">{}</text>"#,
+        x + 6.,
+        y - 6.,
+        text
+    )
+}
+
+/// The place some degrees along the great circle from one place to another.
+fn step_towards(from: Horizon, to: Horizon, step: f64) -> Horizon {
+    let vector = |horizon: Horizon| {
+        let (azimuth, altitude) = (
+            horizon.azimuth.to_radians().value,
+            horizon.altitude.to_radians().value,
+        );
+        [
+            altitude.cos() * azimuth.cos(),
+            altitude.cos() * azimuth.sin(),
+            altitude.sin(),
+        ]
+    };
+    let (a, b) = (vector(from), vector(to));
+    let along = a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+    let across: Vec<f64> = (0..3).map(|i| b[i] - along * a[i]).collect();
+    let length = across.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let step = step.to_radians();
+    let p: Vec<f64> = (0..3)
+        .map(|i| a[i] * step.cos() + across[i] / length * step.sin())
+        .collect();
+
+    Horizon {
+        azimuth: degrees(p[1].atan2(p[0]).to_degrees().rem_euclid(360.)),
+        altitude: degrees(p[2].asin().to_degrees()),
+    }
+}
@@ -0,0 +1,796 @@
+//! The `celestial` command: Julian days, sidereal time, where the Sun, Moon
+//! and planets are, when they rise and set, a plan of the night ahead and a
+//! chart of the sky.
+//!
+//! Moments are ISO 8601, as `2021-03-20T22:00:00+01:00` or
+//! `2021-03-20T21:00Z`.  One without an offset is on the clocks of `--tz`,
+//! else of the site, else UTC, and a bare date is its local midnight.  Every
+//! command but the chart, which is SVG, can print JSON instead of text.
+
+use std::{fmt, path::PathBuf, str::FromStr};
+
+use chrono::{
+    Date, DateTime, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, SecondsFormat,
+    TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use serde::{Serialize, Serializer};
+use structopt::StructOpt;
+
+use crate::{
+    catalog::Catalog,
+    chart::{Chart, Constellations, Projection},
+    degrees,
+    error::{Error, Result},
+    hours,
+    moon::{self, LunarPosition},
+    observer::{parse_longitude, Observer},
+    planets::{Planet, Theory},
+    rise_set::{self, rise_transit_set, RiseTransitSet},
+    sidereal::SiderealTime,
+    sun::SolarPosition,
+    timescale::{self, JulianDate, Tt},
+    JulianDay,
+};
+
+/// Planets lower than this at their best, in degrees, are left out of the
+/// night's plan.
+const MIN_ALTITUDE: f64 = 5.;
+
+/// How often the night is sampled for the planets' best moments.
+const STEP_MINUTES: i64 = 10;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "celestial",
+    about = "Times and places of the Sun, Moon and planets"
+)]
+pub struct Opt {
+    /// Print JSON instead of text
+    #[structopt(long, global = true)]
+    pub json: bool,
+    /// Where to observe from, as "51.4769N, 0.0005W, 46m"
+    #[structopt(
+        long,
+        env = "CELESTIAL_SITE",
+        global = true,
+        allow_hyphen_values = true
+    )]
+    pub site: Option<Observer>,
+    /// Time zone of input and output, as "Europe/London", if not the site's
+    #[structopt(long, env = "CELESTIAL_TZ", global = true)]
+    pub tz: Option<Tz>,
+    #[structopt(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    /// Julian day, modified Julian day and Terrestrial Time of a moment
+    Jd {
+        /// Default now
+        at: Option<When>,
+    },
+    /// Greenwich and local sidereal time
+    Lst {
+        /// Longitude, signed positive east or ending in E or W; default the
+        /// site's
+        #[structopt(long, allow_hyphen_values = true, parse(try_from_str = parse_longitude))]
+        lon: Option<f64>,
+        #[structopt(long)]
+        at: Option<When>,
+    },
+    /// Where the Sun is
+    Sun {
+        #[structopt(long)]
+        at: Option<When>,
+    },
+    /// Where the Moon is, and how much of it is lit
+    Moon {
+        #[structopt(long)]
+        at: Option<When>,
+    },
+    /// Where a planet is, and how bright
+    Planet {
+        /// Mercury, Venus, Mars, Jupiter, Saturn, Uranus or Neptune
+        name: Planet,
+        #[structopt(long)]
+        at: Option<When>,
+    },
+    /// Rising, transit and setting of the Sun, Moon or a planet on a date
+    RiseSet {
+        /// Sun, Moon or a planet
+        body: Target,
+        #[structopt(long)]
+        date: Option<When>,
+    },
+    /// Dusk, dawn, the Moon and the planets for the night after a date
+    Tonight {
+        #[structopt(long)]
+        date: Option<When>,
+    },
+    /// An SVG chart of the sky above the site
+    Chart {
+        /// Star catalog, BSC5 or a Hipparcos CSV
+        #[structopt(long, parse(from_os_str))]
+        catalog: PathBuf,
+        /// Constellation figures, as Stellarium's constellationship.fab
+        #[structopt(long, parse(from_os_str))]
+        lines: Option<PathBuf>,
+        #[structopt(long)]
+        at: Option<When>,
+        /// Flatten the sky orthographically rather than stereographically
+        #[structopt(long)]
+        orthographic: bool,
+        /// Faintest stars drawn
+        #[structopt(long, default_value = "5.5")]
+        magnitude: f64,
+        /// Leave out the grid of right ascension and declination
+        #[structopt(long)]
+        no_grid: bool,
+    },
+}
+
+/// A moment or a date on the command line.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum When {
+    Now,
+    /// A moment with its offset from UTC.
+    Instant(DateTime<FixedOffset>),
+    /// A moment on local clocks.
+    Local(NaiveDateTime),
+    /// A local date.
+    Date(NaiveDate),
+}
+
+/// Something with rising and setting times.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Target {
+    Sun,
+    Moon,
+    Planet(Planet),
+}
+
+impl When {
+    /// The moment, on the clocks of a time zone.  A local time that is
+    /// skipped when the clocks go forward is an error, and one that comes
+    /// twice is taken the first time.
+    pub fn resolve(&self, tz: &Tz) -> Result<DateTime<Tz>> {
+        match self {
+            When::Now => Ok(Utc::now().with_timezone(tz)),
+            When::Instant(dt) => Ok(dt.with_timezone(tz)),
+            When::Local(naive) => local(naive, tz),
+            When::Date(date) => local(&date.and_hms(0, 0, 0), tz),
+        }
+    }
+
+    /// The local date in a time zone.
+    pub fn date(&self, tz: &Tz) -> Result<Date<Tz>> {
+        let naive = match self {
+            When::Date(date) => *date,
+            When::Local(naive) => naive.date(),
+            _ => self.resolve(tz)?.naive_local().date(),
+        };
+        tz.from_local_date(&naive)
+            .earliest()
+            .ok_or_else(|| Error::InvalidDate(format!("{} in {}", naive, tz.name())))
+    }
+}
+
+impl FromStr for When {
+    type Err = Error;
+
+    /// `now`, or a moment or date in ISO 8601, with a `T` or a space between
+    /// the date and the time.
+    fn from_str(s: &str) -> Result<Self> {
+        if s.eq_ignore_ascii_case("now") {
+            return Ok(When::Now);
+        }
+        let s = s.trim().replacen(' ', "T", 1);
+
+        if let Ok(dt) = DateTime::parse_from_rfc3339(&s) {
+            return Ok(When::Instant(dt));
+        }
+        if let Ok(dt) = DateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M%#z") {
+            return Ok(When::Instant(dt));
+        }
+        if let Some(dt) = s
+            .strip_suffix(|c| c == 'Z' || c == 'z')
+            .and_then(|naive| naive_datetime(naive).ok())
+        {
+            return Ok(When::Instant(Utc.from_utc_datetime(&dt).into()));
+        }
+        if let Ok(naive) = naive_datetime(&s) {
+            return Ok(When::Local(naive));
+        }
+        NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+            .map(When::Date)
+            .map_err(|_| Error::Parse(format!("expected an ISO 8601 date or time, got {:?}", s)))
+    }
+}
+
+impl Target {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Target::Sun => "Sun",
+            Target::Moon => "Moon",
+            Target::Planet(planet) => planet.name(),
+        }
+    }
+}
+
+impl FromStr for Target {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "sun" => Ok(Target::Sun),
+            "moon" => Ok(Target::Moon),
+            _ => s.parse().map(Target::Planet),
+        }
+    }
+}
+
+fn naive_datetime(s: &str) -> Result<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M"))
+        .map_err(|error| Error::Parse(error.to_string()))
+}
+
+fn local(naive: &NaiveDateTime, tz: &Tz) -> Result<DateTime<Tz>> {
+    match tz.from_local_datetime(naive) {
+        LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => Ok(dt),
+        LocalResult::None => Err(Error::InvalidDate(format!(
+            "{} is skipped in {}",
+            naive,
+            tz.name()
+        ))),
+    }
+}
+
+/// The Julian day of a moment on several scales.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct JulianDays {
+    pub utc: DateTime<Utc>,
+    pub jd: f64,
+    pub mjd: f64,
+    /// Julian day on Terrestrial Time.
+    pub jd_tt: f64,
+}
+
+/// Sidereal times of a moment, in hours.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SiderealTimes {
+    pub utc: DateTime<Utc>,
+    pub longitude: f64,
+    pub gmst: f64,
+    pub gast: f64,
+    /// Local apparent sidereal time.
+    pub lst: f64,
+}
+
+/// Where a body is at a moment.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Position {
+    pub body: &'static str,
+    #[serde(serialize_with = "rfc3339")]
+    pub time: DateTime<Tz>,
+    /// Right ascension in hours, topocentric for the Moon from a site.
+    pub ra: f64,
+    pub dec: f64,
+    pub distance: f64,
+    pub distance_unit: &'static str,
+    /// Refracted altitude, from a site.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub altitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub azimuth: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub magnitude: Option<f64>,
+    /// Fraction of the disc that is lit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub illuminated: Option<f64>,
+    /// Angular distance from the Sun in degrees.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elongation: Option<f64>,
+}
+
+/// Rising, transit and setting on a local date.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Events {
+    pub body: &'static str,
+    pub date: NaiveDate,
+    /// `"crosses"`, `"circumpolar"` or `"never rises"`.
+    pub horizon: &'static str,
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub rise: Option<DateTime<Tz>>,
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub transit: Option<DateTime<Tz>>,
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub set: Option<DateTime<Tz>>,
+}
+
+/// The night after a local date.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NightPlan {
+    pub date: NaiveDate,
+    pub site: Observer,
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub sunset: Option<DateTime<Tz>>,
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub civil_dusk: Option<DateTime<Tz>>,
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub nautical_dusk: Option<DateTime<Tz>>,
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub astronomical_dusk: Option<DateTime<Tz>>,
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub astronomical_dawn: Option<DateTime<Tz>>,
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub nautical_dawn: Option<DateTime<Tz>>,
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub civil_dawn: Option<DateTime<Tz>>,
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub sunrise: Option<DateTime<Tz>>,
+    pub moon: MoonTonight,
+    /// Planets that get well above the horizon after dusk, in order from
+    /// the Sun.
+    pub planets: Vec<PlanetTonight>,
+}
+
+/// The Moon between one noon and the next.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MoonTonight {
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub rise: Option<DateTime<Tz>>,
+    #[serde(serialize_with = "rfc3339_or_null")]
+    pub set: Option<DateTime<Tz>>,
+    /// Fraction of the disc lit at midnight.
+    pub illuminated: f64,
+    pub waxing: bool,
+}
+
+/// A planet at its highest between dusk and dawn.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PlanetTonight {
+    pub name: &'static str,
+    #[serde(serialize_with = "rfc3339")]
+    pub best: DateTime<Tz>,
+    pub altitude: f64,
+    pub azimuth: f64,
+    pub magnitude: f64,
+}
+
+/// Run a command, returning what it prints.
+pub fn run(opt: &Opt) -> Result<String> {
+    let tz = opt
+        .tz
+        .or_else(|| opt.site.map(|site| site.tz))
+        .unwrap_or(Tz::UTC);
+    let site = opt.site.map(|site| site.with_timezone(tz));
+    let now = When::Now;
+    let site_or_error = || {
+        site.ok_or_else(|| Error::Parse("no site: give --site or set CELESTIAL_SITE".to_string()))
+    };
+
+    match &opt.command {
+        Command::Jd { at } => render(&julian_days(at.unwrap_or(now).resolve(&tz)?), opt.json),
+        Command::Lst { lon, at } => {
+            let longitude = match lon {
+                Some(longitude) => *longitude,
+                None => site_or_error()?.longitude,
+            };
+            let dt = at.unwrap_or(now).resolve(&tz)?;
+            render(&sidereal_times(dt, longitude), opt.json)
+        }
+        Command::Sun { at } => render(
+            &position(Target::Sun, at.unwrap_or(now).resolve(&tz)?, site.as_ref()),
+            opt.json,
+        ),
+        Command::Moon { at } => render(
+            &position(Target::Moon, at.unwrap_or(now).resolve(&tz)?, site.as_ref()),
+            opt.json,
+        ),
+        Command::Planet { name, at } => render(
+            &position(
+                Target::Planet(*name),
+                at.unwrap_or(now).resolve(&tz)?,
+                site.as_ref(),
+            ),
+            opt.json,
+        ),
+        Command::RiseSet { body, date } => render(
+            &events(*body, &site_or_error()?, date.unwrap_or(now).date(&tz)?),
+            opt.json,
+        ),
+        Command::Tonight { date } => render(
+            &night_plan(&site_or_error()?, date.unwrap_or(now).date(&tz)?)?,
+            opt.json,
+        ),
+        Command::Chart {
+            catalog,
+            lines,
+            at,
+            orthographic,
+            magnitude,
+            no_grid,
+        } => {
+            let constellations = match lines {
+                Some(path) => Constellations::from_file(path)?,
+                None => Constellations::default(),
+            };
+            let chart = Chart {
+                projection: match orthographic {
+                    true => Projection::Orthographic,
+                    false => Projection::Stereographic,
+                },
+                magnitude_limit: *magnitude,
+                grid: Chart::default().grid.filter(|_| !no_grid),
+                ..Chart::default()
+            };
+            chart.render(
+                &Catalog::from_file(catalog)?,
+                &constellations,
+                at.unwrap_or(now).resolve(&tz)?,
+                &site_or_error()?,
+            )
+        }
+    }
+}
+
+fn render<T: Serialize + fmt::Display>(report: &T, json: bool) -> Result<String> {
+    match json {
+        true => {
+            serde_json::to_string_pretty(report).map_err(|error| Error::Parse(error.to_string()))
+        }
+        false => Ok(report.to_string()),
+    }
+}
+
+pub fn julian_days<Tz: TimeZone>(dt: DateTime<Tz>) -> JulianDays {
+    let utc = JulianDate::<timescale::Utc>::from(dt.clone());
+    JulianDays {
+        utc: dt.with_timezone(&Utc),
+        jd: utc.jd.to_f64(),
+        mjd: utc.mjd(),
+        jd_tt: utc.to::<Tt>().jd.to_f64(),
+    }
+}
+
+pub fn sidereal_times<Tz: TimeZone>(dt: DateTime<Tz>, longitude: f64) -> SiderealTimes {
+    let jd = JulianDay::from(dt.clone());
+    let gast = SiderealTime::apparent(jd);
+    SiderealTimes {
+        utc: dt.with_timezone(&Utc),
+        longitude,
+        gmst: SiderealTime::mean(jd).hours(),
+        gast: gast.hours(),
+        lst: gast.local(degrees(longitude)).hours(),
+    }
+}
+
+/// Where a body is, and from a site how high, with the Moon's parallax
+/// allowed for.
+pub fn position(target: Target, time: DateTime<Tz>, site: Option<&Observer>) -> Position {
+    let jd = JulianDay::from(time);
+    let tt = JulianDate::<Tt>::from_datetime(time).jd;
+    let mut report = Position {
+        body: target.name(),
+        time,
+        ra: 0.,
+        dec: 0.,
+        distance: 0.,
+        distance_unit: "AU",
+        altitude: None,
+        azimuth: None,
+        magnitude: None,
+        illuminated: None,
+        elongation: None,
+    };
+
+    let equatorial = match target {
+        Target::Sun => {
+            let sun = SolarPosition::from_julian_day(tt);
+            report.distance = sun.distance;
+            sun.equatorial
+        }
+        Target::Moon => {
+            let moon = LunarPosition::from_julian_day(tt);
+            report.distance = moon.distance;
+            report.distance_unit = "km";
+            report.illuminated = Some(moon::illuminated_fraction(time));
+            match site {
+                Some(site) => site.topocentric(moon.equatorial, moon.parallax, jd),
+                None => moon.equatorial,
+            }
+        }
+        Target::Planet(planet) => {
+            let planet = planet.geocentric(tt, Theory::Vsop87);
+            report.distance = planet.distance;
+            report.magnitude = Some(planet.magnitude);
+            report.elongation = Some(planet.elongation);
+            planet.equatorial
+        }
+    };
+    report.ra = equatorial.ra_hours();
+    report.dec = equatorial.dec_degrees();
+
+    if let Some(site) = site {
+        let horizon = site.atmosphere().refract(site.horizon(equatorial, jd));
+        report.altitude = Some(horizon.altitude.to_degrees().value);
+        report.azimuth = Some(horizon.azimuth.to_degrees().value);
+    }
+    report
+}
+
+pub fn events(target: Target, site: &Observer, date: Date<Tz>) -> Events {
+    let events = match target {
+        Target::Sun => rise_transit_set(&rise_set::Sun::Disc, site, date),
+        Target::Moon => rise_transit_set(&rise_set::Moon, site, date),
+        Target::Planet(planet) => rise_transit_set(&planet, site, date),
+    };
+    Events {
+        body: target.name(),
+        date: date.naive_local(),
+        horizon: match events {
+            RiseTransitSet::Crosses { .. } => "crosses",
+            RiseTransitSet::Circumpolar { .. } => "circumpolar",
+            RiseTransitSet::NeverRises { .. } => "never rises",
+        },
+        rise: events.rise().cloned(),
+        transit: events.transit().cloned(),
+        set: events.set().cloned(),
+    }
+}
+
+/// Dusk and dawn, the Moon, and the planets worth looking for in the night
+/// after a local date.  Clocks that skip midnight are no trouble, but ones
+/// that skip noon, or 6 pm or 6 am in a night without dusk or dawn, are an
+/// error.
+pub fn night_plan(site: &Observer, date: Date<Tz>) -> Result<NightPlan> {
+    let tz = date.timezone();
+    let at = |date: Date<Tz>, hour| local(&date.naive_local().and_hms(hour, 0, 0), &tz);
+    let next = date.succ();
+    let dusk = |sun| rise_transit_set(&sun, site, date).set().cloned();
+    let dawn = |sun| rise_transit_set(&sun, site, next).rise().cloned();
+    let (sunset, civil_dusk) = (
+        dusk(rise_set::Sun::Disc),
+        dusk(rise_set::Sun::CivilTwilight),
+    );
+    let (sunrise, civil_dawn) = (
+        dawn(rise_set::Sun::Disc),
+        dawn(rise_set::Sun::CivilTwilight),
+    );
+
+    let noon = at(date, 12)?;
+    let next_noon = at(next, 12)?;
+    let midnight = rise_set::midnight(&next);
+
+    // The Moon's first rising and setting from noon to noon
+    let moon_events = [date, next]
+        .iter()
+        .map(|day| rise_transit_set(&rise_set::Moon, site, *day))
+        .collect::<Vec<_>>();
+    let first = |event: fn(&RiseTransitSet<Tz>) -> Option<&DateTime<Tz>>| {
+        moon_events
+            .iter()
+            .filter_map(event)
+            .find(|dt| noon <= **dt && **dt < next_noon)
+            .cloned()
+    };
+    let tt = JulianDate::<Tt>::from_datetime(midnight).jd;
+    let elongation = LunarPosition::from_julian_day(tt).apparent_longitude
+        - SolarPosition::from_julian_day(tt).apparent_longitude;
+    let moon = MoonTonight {
+        rise: first(RiseTransitSet::rise),
+        set: first(RiseTransitSet::set),
+        illuminated: moon::illuminated_fraction(midnight),
+        waxing: elongation.rem_euclid(360.) < 180.,
+    };
+
+    // Between civil dusk and dawn, or through a night without them
+    let start = match civil_dusk.or(sunset) {
+        Some(dt) => dt,
+        None => at(date, 18)?,
+    };
+    let end = match civil_dawn.or(sunrise) {
+        Some(dt) => dt,
+        None => at(next, 6)?,
+    };
+    let planets = Planet::ALL
+        .iter()
+        .filter_map(|planet| best_moment(*planet, site, start, end))
+        .filter(|planet| planet.altitude >= MIN_ALTITUDE)
+        .collect();
+
+    Ok(NightPlan {
+        date: date.naive_local(),
+        site: *site,
+        sunset,
+        civil_dusk,
+        nautical_dusk: dusk(rise_set::Sun::NauticalTwilight),
+        astronomical_dusk: dusk(rise_set::Sun::AstronomicalTwilight),
+        astronomical_dawn: dawn(rise_set::Sun::AstronomicalTwilight),
+        nautical_dawn: dawn(rise_set::Sun::NauticalTwilight),
+        civil_dawn,
+        sunrise,
+        moon,
+        planets,
+    })
+}
+
+/// When a planet stands highest between two moments.
+fn best_moment(
+    planet: Planet,
+    site: &Observer,
+    start: DateTime<Tz>,
+    end: DateTime<Tz>,
+) -> Option<PlanetTonight> {
+    let steps = (end - start).num_minutes() / STEP_MINUTES;
+    (0..=steps)
+        .map(|step| {
+            let time = start + Duration::minutes(step * STEP_MINUTES);
+            let jd = JulianDay::from(time);
+            let position =
+                planet.geocentric(JulianDate::<Tt>::from_datetime(time).jd, Theory::Vsop87);
+            let horizon = site
+                .atmosphere()
+                .refract(site.horizon(position.equatorial, jd));
+            PlanetTonight {
+                name: planet.name(),
+                best: time,
+                altitude: horizon.altitude.to_degrees().value,
+                azimuth: horizon.azimuth.to_degrees().value,
+                magnitude: position.magnitude,
+            }
+        })
+        .max_by(|a, b| a.altitude.total_cmp(&b.altitude))
+}
+
+impl fmt::Display for JulianDays {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "UTC     {}", self.utc.format("%Y-%m-%d %H:%M:%S%.3f"))?;
+        writeln!(f, "JD      {:.6}", self.jd)?;
+        writeln!(f, "MJD     {:.6}", self.mjd)?;
+        write!(f, "JD (TT) {:.6}", self.jd_tt)
+    }
+}
+
+impl fmt::Display for SiderealTimes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hms = SiderealTime::from_hours;
+        writeln!(f, "UTC  {}", self.utc.format("%Y-%m-%d %H:%M:%S"))?;
+        writeln!(f, "GMST {:.3}", hms(self.gmst))?;
+        writeln!(f, "GAST {:.3}", hms(self.gast))?;
+        write!(f, "LST  {:.3} at {}°", hms(self.lst), self.longitude)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} at {}",
+            self.body,
+            self.time.format("%Y-%m-%d %H:%M:%S %Z")
+        )?;
+        writeln!(f, "RA        {:.1}", hours(self.ra).display_hms())?;
+        write!(f, "Dec       {}", degrees(self.dec).display_dms())?;
+        match self.distance_unit {
+            "km" => write!(f, "\nDistance  {:.0} km", self.distance)?,
+            unit => write!(f, "\nDistance  {:.4} {}", self.distance, unit)?,
+        }
+        if let (Some(altitude), Some(azimuth)) = (self.altitude, self.azimuth) {
+            write!(f, "\nAltitude  {:.1}°", altitude)?;
+            write!(f, "\nAzimuth   {:.1}°", azimuth)?;
+        }
+        if let Some(magnitude) = self.magnitude {
+            write!(f, "\nMagnitude {:.1}", magnitude)?;
+        }
+        if let Some(elongation) = self.elongation {
+            write!(f, "\nFrom Sun  {:.1}°", elongation)?;
+        }
+        if let Some(illuminated) = self.illuminated {
+            write!(f, "\nLit       {:.0}%", illuminated * 100.)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Events {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} on {}", self.body, self.date)?;
+        match self.horizon {
+            "crosses" => {}
+            horizon => write!(f, ", {}", horizon)?,
+        }
+        for (name, time) in &[
+            ("Rise", &self.rise),
+            ("Transit", &self.transit),
+            ("Set", &self.set),
+        ] {
+            write!(f, "\n{:<8}{}", name, Clock(time, "%H:%M:%S %Z"))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for NightPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Night of {} at {:.4}, {:.4}",
+            self.date, self.site.latitude, self.site.longitude
+        )?;
+        for (name, time) in &[
+            ("Sunset", &self.sunset),
+            ("Civil dusk", &self.civil_dusk),
+            ("Nautical dusk", &self.nautical_dusk),
+            ("Astronomical dusk", &self.astronomical_dusk),
+            ("Astronomical dawn", &self.astronomical_dawn),
+            ("Nautical dawn", &self.nautical_dawn),
+            ("Civil dawn", &self.civil_dawn),
+            ("Sunrise", &self.sunrise),
+        ] {
+            writeln!(f, "{:<18}{}", name, Clock(time, "%H:%M %Z"))?;
+        }
+
+        writeln!(
+            f,
+            "Moon              rises {}, sets {}, {:.0}% lit, {}",
+            Clock(&self.moon.rise, "%H:%M"),
+            Clock(&self.moon.set, "%H:%M"),
+            self.moon.illuminated * 100.,
+            if self.moon.waxing { "waxing" } else { "waning" }
+        )?;
+
+        match self.planets.is_empty() {
+            true => write!(f, "No planets above {}°", MIN_ALTITUDE),
+            false => {
+                write!(f, "Planets")?;
+                for planet in &self.planets {
+                    write!(
+                        f,
+                        "\n  {:<8} best {} at {:>4.1}° altitude, azimuth {:>5.1}°, magnitude {:>4.1}",
+                        planet.name,
+                        planet.best.format("%H:%M"),
+                        planet.altitude,
+                        planet.azimuth,
+                        planet.magnitude
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Local times with their offsets, which chrono-tz would write with the
+/// zone's abbreviation.
+fn rfc3339<S: Serializer>(
+    dt: &DateTime<Tz>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&dt.to_rfc3339_opts(SecondsFormat::Secs, false))
+}
+
+fn rfc3339_or_null<S: Serializer>(
+    dt: &Option<DateTime<Tz>>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    match dt {
+        Some(dt) => rfc3339(dt, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// A time of day that may not happen.
+struct Clock<'a>(&'a Option<DateTime<Tz>>, &'static str);
+
+impl fmt::Display for Clock<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(dt) => write!(f, "{}", dt.format(self.1)),
+            None => write!(f, "-"),
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! Celestial coordinate systems and the conversions between them.
+//!
+//! Coordinates, and the sidereal times, latitudes and obliquities they are
+//! converted with, are typed [`Angle`]s: right ascension, hour angle and
+//! sidereal time in hours, the rest in degrees.  An observer's latitude and
+//! longitude are in decimal degrees.  Azimuth is measured from north through
+//! east.  Longitudes are positive east of Greenwich.
+
+use chrono::{DateTime, TimeZone};
+
+use crate::{degrees, hours, observer::Observer, sidereal::SiderealTime, Angle, JulianDay};
+
+/// Right ascension and declination.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Equatorial {
+    pub ra: Angle,
+    pub dec: Angle,
+}
+
+/// Hour angle and declination, for an observer at a particular longitude.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HourAngleCoords {
+    pub hour_angle: Angle,
+    pub dec: Angle,
+}
+
+/// Azimuth and altitude, for an observer at a particular place.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Horizon {
+    pub azimuth: Angle,
+    pub altitude: Angle,
+}
+
+/// Ecliptic longitude and latitude.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ecliptic {
+    pub lon: Angle,
+    pub lat: Angle,
+}
+
+/// Galactic longitude and latitude (J2000 galactic pole).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Galactic {
+    pub l: Angle,
+    pub b: Angle,
+}
+
+/// Right ascension of the north galactic pole, in degrees.
+const GALACTIC_POLE_RA: f64 = 192.859_48;
+/// Declination of the north galactic pole, in degrees.
+const GALACTIC_POLE_DEC: f64 = 27.128_25;
+/// Galactic longitude of the north celestial pole, in degrees.
+const GALACTIC_NCP_LON: f64 = 122.931_92;
+
+/// Mean obliquity of the ecliptic (IAU 1980).
+pub fn obliquity(jd: JulianDay) -> Angle {
+    let t = (jd - JulianDay::J2000) / 36_525.;
+    let seconds = 21.448 - 46.815 * t - 0.000_59 * t.powi(2) + 0.001_813 * t.powi(3);
+
+    degrees(23. + 26. / 60. + seconds / 3600.)
+}
+
+/// Nutation in longitude and in obliquity, from the four largest terms of
+/// the IAU 1980 series.  Good to about half an arcsecond.
+pub fn nutation(jd: JulianDay) -> (Angle, Angle) {
+    let t = (jd - JulianDay::J2000) / 36_525.;
+    let omega = (125.044_52 - 1_934.136_261 * t).to_radians();
+    let sun = (280.4665 + 36_000.769_8 * t).to_radians();
+    let moon = (218.3165 + 481_267.881_3 * t).to_radians();
+
+    let longitude = -17.20 * omega.sin() - 1.32 * (2. * sun).sin() - 0.23 * (2. * moon).sin()
+        + 0.21 * (2. * omega).sin();
+    let obliquity = 9.20 * omega.cos() + 0.57 * (2. * sun).cos() + 0.10 * (2. * moon).cos()
+        - 0.09 * (2. * omega).cos();
+
+    (degrees(longitude / 3600.), degrees(obliquity / 3600.))
+}
+
+/// Local apparent sidereal time for an observer, in hours, taking UTC as
+/// UT1, as [`Observer::horizon`] does.
+pub fn local_sidereal_time<Tz: TimeZone>(dt: DateTime<Tz>, observer: &Observer) -> Angle {
+    SiderealTime::apparent(JulianDay::from(dt))
+        .local(degrees(observer.longitude))
+        .angle()
+}
+
+impl Equatorial {
+    pub fn new(ra: Angle, dec: Angle) -> Self {
+        Equatorial { ra, dec }
+    }
+
+    /// Right ascension in decimal hours, the unit of sidereal time.
+    pub fn ra_hours(&self) -> f64 {
+        self.ra.to_hours().value
+    }
+
+    pub fn dec_degrees(&self) -> f64 {
+        self.dec.to_degrees().value
+    }
+
+    /// Hour angle for a local sidereal time.
+    pub fn to_hour_angle(&self, lst: Angle) -> HourAngleCoords {
+        HourAngleCoords {
+            hour_angle: hours((lst.to_hours().value - self.ra_hours()).rem_euclid(24.)),
+            dec: self.dec,
+        }
+    }
+
+    /// Position in the sky for an observer at a time and place.
+    pub fn to_horizon<Tz: TimeZone>(&self, dt: DateTime<Tz>, observer: &Observer) -> Horizon {
+        let lst = local_sidereal_time(dt, observer);
+        self.to_hour_angle(lst)
+            .to_horizon(degrees(observer.latitude))
+    }
+
+    /// Ecliptic coordinates for an obliquity of the ecliptic.
+    pub fn to_ecliptic(&self, obliquity: Angle) -> Ecliptic {
+        let (ra, dec, eps) = (
+            self.ra.to_radians().value,
+            self.dec.to_radians().value,
+            obliquity.to_radians().value,
+        );
+        let lon = (ra.sin() * eps.cos() + dec.tan() * eps.sin()).atan2(ra.cos());
+        let lat = (dec.sin() * eps.cos() - dec.cos() * eps.sin() * ra.sin()).asin();
+
+        Ecliptic {
+            lon: degrees(lon.to_degrees().rem_euclid(360.)),
+            lat: degrees(lat.to_degrees()),
+        }
+    }
+
+    pub fn to_galactic(&self) -> Galactic {
+        let (ra, dec) = (self.ra.to_radians().value, self.dec.to_radians().value);
+        let (pole_ra, pole_dec) = (
+            GALACTIC_POLE_RA.to_radians(),
+            GALACTIC_POLE_DEC.to_radians(),
+        );
+        let b =
+            (dec.sin() * pole_dec.sin() + dec.cos() * pole_dec.cos() * (ra - pole_ra).cos()).asin();
+        let x = dec.cos() * (ra - pole_ra).sin();
+        let y = dec.sin() * pole_dec.cos() - dec.cos() * pole_dec.sin() * (ra - pole_ra).cos();
+
+        Galactic {
+            l: degrees((GALACTIC_NCP_LON - x.atan2(y).to_degrees()).rem_euclid(360.)),
+            b: degrees(b.to_degrees()),
+        }
+    }
+
+    /// Angular separation from another place, well conditioned at any
+    /// distance (Vincenty's formula).
+    pub fn separation(&self, other: &Equatorial) -> Angle {
+        let (dec1, dec2) = (self.dec.to_radians().value, other.dec.to_radians().value);
+        let d_ra = (other.ra - self.ra).to_radians().value;
+        let x = dec1.sin() * dec2.sin() + dec1.cos() * dec2.cos() * d_ra.cos();
+        let y = ((dec2.cos() * d_ra.sin()).powi(2)
+            + (dec1.cos() * dec2.sin() - dec1.sin() * dec2.cos() * d_ra.cos()).powi(2))
+        .sqrt();
+
+        degrees(y.atan2(x).to_degrees())
+    }
+}
+
+impl HourAngleCoords {
+    /// Equatorial coordinates for a local sidereal time.
+    pub fn to_equatorial(&self, lst: Angle) -> Equatorial {
+        Equatorial {
+            ra: hours((lst - self.hour_angle).to_hours().value.rem_euclid(24.)),
+            dec: self.dec,
+        }
+    }
+
+    pub fn to_horizon(&self, latitude: Angle) -> Horizon {
+        let (ha, dec, lat) = (
+            self.hour_angle.to_radians().value,
+            self.dec.to_radians().value,
+            latitude.to_radians().value,
+        );
+        let altitude = (dec.sin() * lat.sin() + dec.cos() * lat.cos() * ha.cos()).asin();
+        let azimuth =
+            (-dec.cos() * ha.sin()).atan2(dec.sin() * lat.cos() - dec.cos() * lat.sin() * ha.cos());
+
+        Horizon {
+            azimuth: degrees(azimuth.to_degrees().rem_euclid(360.)),
+            altitude: degrees(altitude.to_degrees()),
+        }
+    }
+}
+
+impl Horizon {
+    pub fn to_hour_angle(&self, latitude: Angle) -> HourAngleCoords {
+        let (az, alt, lat) = (
+            self.azimuth.to_radians().value,
+            self.altitude.to_radians().value,
+            latitude.to_radians().value,
+        );
+        let dec = (alt.sin() * lat.sin() + alt.cos() * lat.cos() * az.cos()).asin();
+        let ha =
+            (-az.sin() * alt.cos()).atan2(alt.sin() * lat.cos() - alt.cos() * lat.sin() * az.cos());
+
+        HourAngleCoords {
+            hour_angle: hours((ha.to_degrees() / 15.).rem_euclid(24.)),
+            dec: degrees(dec.to_degrees()),
+        }
+    }
+
+    /// Equatorial coordinates of a point in the sky seen at a time and place.
+    pub fn to_equatorial<Tz: TimeZone>(&self, dt: DateTime<Tz>, observer: &Observer) -> Equatorial {
+        let lst = local_sidereal_time(dt, observer);
+        self.to_hour_angle(degrees(observer.latitude))
+            .to_equatorial(lst)
+    }
+}
+
+impl Ecliptic {
+    pub fn new(lon: Angle, lat: Angle) -> Self {
+        Ecliptic { lon, lat }
+    }
+
+    pub fn lon_degrees(&self) -> f64 {
+        self.lon.to_degrees().value
+    }
+
+    pub fn lat_degrees(&self) -> f64 {
+        self.lat.to_degrees().value
+    }
+
+    /// Equatorial coordinates for an obliquity of the ecliptic.
+    pub fn to_equatorial(&self, obliquity: Angle) -> Equatorial {
+        let (lon, lat, eps) = (
+            self.lon.to_radians().value,
+            self.lat.to_radians().value,
+            obliquity.to_radians().value,
+        );
+        let ra = (lon.sin() * eps.cos() - lat.tan() * eps.sin()).atan2(lon.cos());
+        let dec = (lat.sin() * eps.cos() + lat.cos() * eps.sin() * lon.sin()).asin();
+
+        Equatorial {
+            ra: hours((ra.to_degrees() / 15.).rem_euclid(24.)),
+            dec: degrees(dec.to_degrees()),
+        }
+    }
+}
+
+impl Galactic {
+    pub fn new(l: Angle, b: Angle) -> Self {
+        Galactic { l, b }
+    }
+
+    pub fn to_equatorial(&self) -> Equatorial {
+        let (l, b) = (self.l.to_radians().value, self.b.to_radians().value);
+        let (pole_dec, ncp_lon) = (
+            GALACTIC_POLE_DEC.to_radians(),
+            GALACTIC_NCP_LON.to_radians(),
+        );
+        let dec =
+            (b.sin() * pole_dec.sin() + b.cos() * pole_dec.cos() * (ncp_lon - l).cos()).asin();
+        let x = b.cos() * (ncp_lon - l).sin();
+        let y = b.sin() * pole_dec.cos() - b.cos() * pole_dec.sin() * (ncp_lon - l).cos();
+
+        Equatorial {
+            ra: hours(((GALACTIC_POLE_RA + x.atan2(y).to_degrees()) / 15.).rem_euclid(24.)),
+            dec: degrees(dec.to_degrees()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use float_eq::assert_float_eq;
+
+    #[test]
+    fn round_trips() {
+        let star = Equatorial::new(hours(5.919_529), degrees(7.407_064));
+
+        let ecliptic = star.to_ecliptic(degrees(23.44));
+        let back = ecliptic.to_equatorial(degrees(23.44));
+        assert_float_eq!(
+            (back.ra_hours(), back.dec_degrees()),
+            (star.ra_hours(), star.dec_degrees()),
+            abs <= (1e-9, 1e-9)
+        );
+
+        let galactic = star.to_galactic();
+        let back = galactic.to_equatorial();
+        assert_float_eq!(
+            (back.ra_hours(), back.dec_degrees()),
+            (star.ra_hours(), star.dec_degrees()),
+            abs <= (1e-9, 1e-9)
+        );
+
+        let horizon = star.to_hour_angle(hours(3.)).to_horizon(degrees(-33.9));
+        let back = horizon
+            .to_hour_angle(degrees(-33.9))
+            .to_equatorial(hours(3.));
+        assert_float_eq!(
+            (back.ra_hours(), back.dec_degrees()),
+            (star.ra_hours(), star.dec_degrees()),
+            abs <= (1e-9, 1e-9)
+        );
+    }
+}
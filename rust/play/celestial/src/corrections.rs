@@ -0,0 +1,220 @@
+//! Corrections from a catalog mean place to the place a star is seen, after
+//! Meeus, *Astronomical Algorithms*, chapters 16, 21 and 23.
+//!
+//! The Julian days given to `precess`, `nutate`, `aberrate` and
+//! `Reduction::apparent` are on TT.  `Reduction::observed` takes a civil
+//! time and uses it as UT, for the sidereal time and for the corrections
+//! alike, which change too slowly for the minute between UT and TT to show.
+//!
+//! The chain is precession from the catalog epoch to the date, nutation,
+//! annual aberration and then atmospheric refraction.  Proper motion, annual
+//! parallax and the small diurnal effects are left out, which matters only
+//! for the nearest stars and for the Moon.
+
+use chrono::{DateTime, TimeZone};
+
+use crate::{
+    coords::{nutation, obliquity, Equatorial, Horizon},
+    degrees, hours,
+    observer::Observer,
+    sun::SolarPosition,
+    JulianDay,
+};
+
+/// The standard epoch J2000.0, 2000 January 1.5 TD.
+pub const J2000: JulianDay = JulianDay::J2000;
+
+/// Constant of aberration in degrees.
+const ABERRATION: f64 = 20.495_52 / 3600.;
+
+/// Temperature and pressure at the observer, which scale the refraction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Atmosphere {
+    /// Air temperature in degrees Celsius.
+    pub temperature: f64,
+    /// Air pressure in millibars.
+    pub pressure: f64,
+}
+
+/// The steps from a catalog mean place to an observed place.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Reduction {
+    /// Epoch of the catalog's equator and equinox.
+    pub epoch: JulianDay,
+    /// Whether to refract through the observer's air, or give geometric
+    /// altitudes.
+    pub refraction: bool,
+}
+
+/// Precess a mean place between two epochs, with the rigorous IAU 1976
+/// method.
+pub fn precess(position: Equatorial, from: JulianDay, to: JulianDay) -> Equatorial {
+    let big_t = (from - J2000) / 36_525.;
+    let t = (to - from) / 36_525.;
+
+    let rate = 2_306.218_1 + 1.396_56 * big_t - 0.000_139 * big_t.powi(2);
+    let zeta = rate * t + (0.301_88 - 0.000_344 * big_t) * t.powi(2) + 0.017_998 * t.powi(3);
+    let z = rate * t + (1.094_68 + 0.000_066 * big_t) * t.powi(2) + 0.018_203 * t.powi(3);
+    let theta = (2_004.310_9 - 0.853_30 * big_t - 0.000_217 * big_t.powi(2)) * t
+        - (0.426_65 + 0.000_217 * big_t) * t.powi(2)
+        - 0.041_833 * t.powi(3);
+    let (zeta, z, theta) = (
+        (zeta / 3600.).to_radians(),
+        (z / 3600.).to_radians(),
+        (theta / 3600.).to_radians(),
+    );
+
+    let (ra, dec) = (
+        position.ra.to_radians().value,
+        position.dec.to_radians().value,
+    );
+    let a = dec.cos() * (ra + zeta).sin();
+    let b = theta.cos() * dec.cos() * (ra + zeta).cos() - theta.sin() * dec.sin();
+    let c = theta.sin() * dec.cos() * (ra + zeta).cos() + theta.cos() * dec.sin();
+
+    Equatorial {
+        ra: hours(((a.atan2(b) + z).to_degrees() / 15.).rem_euclid(24.)),
+        dec: degrees(c.asin().to_degrees()),
+    }
+}
+
+/// Move a mean place of the date to the true equator and equinox.
+pub fn nutate(position: Equatorial, jd: JulianDay) -> Equatorial {
+    let (lon, obl) = nutation(jd);
+    let eps = (obliquity(jd) + obl).to_radians().value;
+    let (nutation_lon, nutation_obl) = (lon.to_degrees().value, obl.to_degrees().value);
+    let (ra, dec) = (
+        position.ra.to_radians().value,
+        position.dec.to_radians().value,
+    );
+
+    let d_ra = (eps.cos() + eps.sin() * ra.sin() * dec.tan()) * nutation_lon
+        - ra.cos() * dec.tan() * nutation_obl;
+    let d_dec = eps.sin() * ra.cos() * nutation_lon + ra.sin() * nutation_obl;
+
+    Equatorial {
+        ra: hours((position.ra_hours() + d_ra / 15.).rem_euclid(24.)),
+        dec: position.dec + degrees(d_dec),
+    }
+}
+
+/// Annual aberration, the shift from the Earth's motion around the Sun.
+/// Good to a small fraction of an arcsecond away from the poles.
+pub fn aberrate(position: Equatorial, jd: JulianDay) -> Equatorial {
+    let t = (jd - J2000) / 36_525.;
+    let e = 0.016_708_634 - 0.000_042_037 * t - 0.000_000_126_7 * t.powi(2);
+    let perihelion = (102.937_35 + 1.719_46 * t + 0.000_46 * t.powi(2)).to_radians();
+    let sun = SolarPosition::from_julian_day(jd)
+        .true_longitude
+        .to_radians();
+    let eps = obliquity(jd).to_radians().value;
+    let (ra, dec) = (
+        position.ra.to_radians().value,
+        position.dec.to_radians().value,
+    );
+
+    let d_ra = (-(ra.cos() * sun.cos() * eps.cos() + ra.sin() * sun.sin())
+        + e * (ra.cos() * perihelion.cos() * eps.cos() + ra.sin() * perihelion.sin()))
+        * ABERRATION
+        / dec.cos();
+    let d_dec = (-(sun.cos() * eps.cos() * (eps.tan() * dec.cos() - ra.sin() * dec.sin())
+        + ra.cos() * dec.sin() * sun.sin())
+        + e * (perihelion.cos() * eps.cos() * (eps.tan() * dec.cos() - ra.sin() * dec.sin())
+            + ra.cos() * dec.sin() * perihelion.sin()))
+        * ABERRATION;
+
+    Equatorial {
+        ra: hours((position.ra_hours() + d_ra / 15.).rem_euclid(24.)),
+        dec: position.dec + degrees(d_dec),
+    }
+}
+
+impl Atmosphere {
+    pub fn new(temperature: f64, pressure: f64) -> Self {
+        Atmosphere {
+            temperature,
+            pressure,
+        }
+    }
+
+    /// Refraction in degrees for an apparent (observed) altitude, by
+    /// Bennett's formula.  Subtract it to get the true altitude.
+    pub fn refraction_from_apparent(&self, altitude: f64) -> f64 {
+        let h = altitude.max(-1.);
+        let minutes = 1. / (h + 7.31 / (h + 4.4)).to_radians().tan() + 0.001_351_5;
+
+        minutes / 60. * self.scale()
+    }
+
+    /// Refraction in degrees for a true (geometric) altitude, by
+    /// Saemundsson's formula.  Add it to get the apparent altitude.
+    pub fn refraction_from_true(&self, altitude: f64) -> f64 {
+        let h = altitude.max(-1.);
+        let minutes = 1.02 / (h + 10.3 / (h + 5.11)).to_radians().tan() + 0.001_927_9;
+
+        minutes / 60. * self.scale()
+    }
+
+    /// Where a geometric place in the sky is seen, raised by refraction.
+    pub fn refract(&self, horizon: Horizon) -> Horizon {
+        let altitude = horizon.altitude.to_degrees().value;
+        Horizon {
+            altitude: degrees(altitude + self.refraction_from_true(altitude)),
+            ..horizon
+        }
+    }
+
+    /// Both formulae are for 10 °C and 1010 mb.
+    fn scale(&self) -> f64 {
+        self.pressure / 1010. * 283. / (273. + self.temperature)
+    }
+}
+
+impl Default for Atmosphere {
+    /// 10 °C and 1010 mb, the conditions the formulae assume.
+    fn default() -> Self {
+        Atmosphere::new(10., 1010.)
+    }
+}
+
+impl Reduction {
+    /// Reduce places from a catalog for an epoch, with refraction.
+    pub fn new(epoch: JulianDay) -> Self {
+        Reduction {
+            epoch,
+            refraction: true,
+        }
+    }
+
+    /// Apparent geocentric place on a date: precessed, nutated and
+    /// aberrated.
+    pub fn apparent(&self, mean: Equatorial, jd: JulianDay) -> Equatorial {
+        let position = precess(mean, self.epoch, jd);
+        aberrate(nutate(position, jd), jd)
+    }
+
+    /// Where an observer sees a catalog place, refracted by the air at the
+    /// site if refraction is on.
+    pub fn observed<Tz: TimeZone>(
+        &self,
+        mean: Equatorial,
+        dt: DateTime<Tz>,
+        observer: &Observer,
+    ) -> Horizon {
+        let jd = JulianDay::from(dt);
+        let apparent = self.apparent(mean, jd);
+
+        let horizon = observer.horizon(apparent, jd);
+        match self.refraction {
+            true => observer.atmosphere().refract(horizon),
+            false => horizon,
+        }
+    }
+}
+
+impl Default for Reduction {
+    /// Places for J2000.0, with refraction.
+    fn default() -> Self {
+        Reduction::new(J2000)
+    }
+}
@@ -0,0 +1,541 @@
+//! Solar and lunar eclipses, found from the positions of the Sun and Moon
+//! at each new and full moon, in the manner of Meeus, *Astronomical
+//! Algorithms*, chapter 54, and the Explanatory Supplement.
+//!
+//! A solar eclipse is classified from the Moon's shadow cones on the plane
+//! through the centre of the Earth square to the shadow axis, and a lunar
+//! eclipse from the Earth's shadow at the distance of the Moon, enlarged by
+//! Danjon's rule for the atmosphere.  Positions are computed on TT, a
+//! minute or so ahead of the UTC the times are given in, which is taken as
+//! UT1.  The positions here are good to some seconds of arc, which puts
+//! times within a minute or so of the published canons.
+
+use std::{convert::TryFrom, f64::consts::PI, ops::RangeBounds};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    coords::{Equatorial, Horizon},
+    degrees, hours,
+    moon::{lunar_phases, LunarPosition, Phase},
+    observer::{Observer, WGS84_FLATTENING, WGS84_RADIUS},
+    sidereal::SiderealTime,
+    sun::SolarPosition,
+    timescale::{JulianDate, Tt, Ut1},
+    JulianDay,
+};
+
+/// Equatorial radius of the Earth in kilometres.
+const EARTH_RADIUS: f64 = WGS84_RADIUS;
+
+/// Squared eccentricity of the Earth's meridian.
+const EARTH_E2: f64 = WGS84_FLATTENING * (2. - WGS84_FLATTENING);
+
+/// Radius of the Sun in kilometres.
+const SUN_RADIUS: f64 = 696_000.;
+
+/// The Moon's radius in Earth radii, for its limb in the penumbra and for
+/// the mean radius of its valleys in the umbra.
+const MOON_K_PENUMBRA: f64 = 0.272_507_6;
+const MOON_K_UMBRA: f64 = 0.272_281;
+
+/// Kilometres in an astronomical unit.
+const AU: f64 = 149_597_870.7;
+
+/// Half the length of the search either side of the new or full moon, in
+/// days: longer than the longest eclipse.
+const WINDOW: f64 = 0.3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SolarEclipseKind {
+    Partial,
+    Annular,
+    Total,
+    /// Annular at the ends of the path and total in the middle.
+    Hybrid,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LunarEclipseKind {
+    Penumbral,
+    Partial,
+    Total,
+}
+
+/// A solar eclipse as seen from the Earth as a whole.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolarEclipse {
+    pub kind: SolarEclipseKind,
+    /// Whether the shadow axis meets the Earth.  Total and annular eclipses
+    /// that are not central only graze the polar regions.
+    pub central: bool,
+    /// When the shadow axis passes closest to the centre of the Earth.
+    pub maximum: DateTime<Utc>,
+    /// Least distance of the shadow axis from the centre of the Earth, in
+    /// equatorial radii, positive north.
+    pub gamma: f64,
+    /// Fraction of the Sun's diameter covered at greatest eclipse, or the
+    /// ratio of the apparent diameters of the Moon and Sun when central.
+    pub magnitude: f64,
+}
+
+/// A solar eclipse as seen from one place.  The contacts and maximum may
+/// fall while the Sun is below the horizon.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalSolarEclipse {
+    pub first_contact: DateTime<Utc>,
+    /// The start of totality or annularity.
+    pub second_contact: Option<DateTime<Utc>>,
+    pub third_contact: Option<DateTime<Utc>>,
+    pub fourth_contact: DateTime<Utc>,
+    pub maximum: DateTime<Utc>,
+    /// Fraction of the Sun's diameter covered, or the ratio of the apparent
+    /// diameters when the Moon is wholly within the Sun's disc or covers
+    /// it.
+    pub magnitude: f64,
+    /// Fraction of the area of the Sun's disc covered.
+    pub obscuration: f64,
+    /// Where the Sun is at maximum.
+    pub horizon: Horizon,
+}
+
+/// A lunar eclipse, the same for every place that has the Moon above the
+/// horizon.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LunarEclipse {
+    pub kind: LunarEclipseKind,
+    /// When the Moon passes closest to the axis of the Earth's shadow.
+    pub maximum: DateTime<Utc>,
+    /// Least distance of the Moon's centre from the shadow axis, in
+    /// equatorial radii of the Earth, positive north.
+    pub gamma: f64,
+    /// Fraction of the Moon's diameter in the penumbra at maximum.
+    pub penumbral_magnitude: f64,
+    /// Fraction of the Moon's diameter in the umbra at maximum, negative
+    /// for a penumbral eclipse.
+    pub umbral_magnitude: f64,
+    /// Entering and leaving the penumbra.
+    pub penumbral: (DateTime<Utc>, DateTime<Utc>),
+    /// Entering and leaving the umbra.
+    pub partial: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    /// Wholly within the umbra.
+    pub total: Option<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+/// Geocentric positions of the Sun and Moon in kilometres, along the
+/// equator and equinox of date.
+struct Bodies {
+    sun: SolarPosition,
+    moon: LunarPosition,
+    sun_vector: [f64; 3],
+    moon_vector: [f64; 3],
+}
+
+/// The Moon's shadow on the fundamental plane through the Earth's centre,
+/// in equatorial radii.
+struct Shadow {
+    x: f64,
+    y: f64,
+    /// Declination of the shadow axis.
+    dec: f64,
+    /// The Moon's distance along the shadow axis, negative on the Sun's
+    /// side of the plane.
+    z_moon: f64,
+    tan_f1: f64,
+    tan_f2: f64,
+}
+
+/// The solar eclipses at the new moons in a time range, in order.  An
+/// unbounded start begins from now.
+pub fn solar_eclipses<R: RangeBounds<DateTime<Utc>>>(
+    range: R,
+) -> impl Iterator<Item = SolarEclipse> {
+    lunar_phases(range)
+        .filter(|(phase, _)| *phase == Phase::NewMoon)
+        .filter_map(|(_, time)| SolarEclipse::near(JulianDay::from(time)))
+}
+
+/// The lunar eclipses at the full moons in a time range, in order.  An
+/// unbounded start begins from now.
+pub fn lunar_eclipses<R: RangeBounds<DateTime<Utc>>>(
+    range: R,
+) -> impl Iterator<Item = LunarEclipse> {
+    lunar_phases(range)
+        .filter(|(phase, _)| *phase == Phase::FullMoon)
+        .filter_map(|(_, time)| LunarEclipse::near(JulianDay::from(time)))
+}
+
+impl SolarEclipse {
+    /// The eclipse at a new moon, if there is one.
+    fn near(new_moon: JulianDay) -> Option<Self> {
+        let distance = |jd: JulianDay| Shadow::at(jd).distance();
+        let jd = minimise(distance, new_moon + -WINDOW, new_moon + WINDOW);
+        let shadow = Shadow::at(jd);
+        let (gamma, gamma1) = (
+            shadow.distance().copysign(shadow.y),
+            shadow.scaled_distance(),
+        );
+        let (l1, l2) = (shadow.penumbra(0.), shadow.umbra(0.));
+
+        let central = gamma1 < 1.;
+        let (kind, magnitude) = if central {
+            // On the surface below the Moon, and where the axis leaves the
+            // Earth at sunrise and sunset
+            let surface = -(1. - gamma1 * gamma1).sqrt();
+            let (l1, l2s) = (shadow.penumbra(surface), shadow.umbra(surface));
+            let kind = match (l2s > 0., l2 > 0.) {
+                (true, true) => SolarEclipseKind::Total,
+                (true, false) => SolarEclipseKind::Hybrid,
+                _ => SolarEclipseKind::Annular,
+            };
+            (kind, (l1 + l2s) / (l1 - l2s))
+        } else {
+            let magnitude = (l1 - (gamma1 - 1.)) / (l1 - l2);
+            let kind = match gamma1 < 1. + l2.abs() {
+                true if l2 > 0. => SolarEclipseKind::Total,
+                true => SolarEclipseKind::Annular,
+                false if gamma1 < 1. + l1 => SolarEclipseKind::Partial,
+                false => return None,
+            };
+            (kind, magnitude)
+        };
+
+        Some(SolarEclipse {
+            kind,
+            central,
+            maximum: DateTime::try_from(jd).ok()?,
+            gamma,
+            magnitude,
+        })
+    }
+
+    /// The eclipse as seen from a place, if it is seen there at all.
+    pub fn local(&self, observer: &Observer) -> Option<LocalSolarEclipse> {
+        let greatest = JulianDay::from(self.maximum);
+        let discs = |jd: JulianDay| {
+            let bodies = Bodies::at(jd);
+            let site = observer.position(SiderealTime::apparent(jd));
+            let sun = subtract(bodies.sun_vector, site);
+            let moon = subtract(bodies.moon_vector, site);
+            let radius = |vector: [f64; 3], radius: f64| (radius / norm(vector)).asin();
+            (
+                angle_between(sun, moon),
+                radius(sun, SUN_RADIUS),
+                radius(moon, MOON_K_PENUMBRA * EARTH_RADIUS),
+            )
+        };
+        let separation = |jd: JulianDay| discs(jd).0;
+
+        let (start, end) = (greatest + -WINDOW, greatest + WINDOW);
+        let jd = minimise(separation, start, end);
+        let (least, sun, moon) = discs(jd);
+        if least >= sun + moon {
+            return None;
+        }
+        let contact = |radius: fn(f64, f64) -> f64, from: JulianDay, to: JulianDay| {
+            crossing(
+                |jd| {
+                    let (separation, sun, moon) = discs(jd);
+                    separation - radius(sun, moon)
+                },
+                from,
+                to,
+            )
+            .and_then(|jd| DateTime::try_from(jd).ok())
+        };
+        let outer: fn(f64, f64) -> f64 = |sun, moon| sun + moon;
+        let inner: fn(f64, f64) -> f64 = |sun, moon| (sun - moon).abs();
+        let (second_contact, third_contact) = match least < (sun - moon).abs() {
+            true => (contact(inner, start, jd), contact(inner, jd, end)),
+            false => (None, None),
+        };
+
+        let magnitude = match least <= (sun - moon).abs() {
+            true => moon / sun,
+            false => (sun + moon - least) / (2. * sun),
+        };
+        let bodies = Bodies::at(jd);
+        let sun_vector = subtract(
+            bodies.sun_vector,
+            observer.position(SiderealTime::apparent(jd)),
+        );
+
+        Some(LocalSolarEclipse {
+            first_contact: contact(outer, start, jd)?,
+            second_contact,
+            third_contact,
+            fourth_contact: contact(outer, jd, end)?,
+            maximum: DateTime::try_from(jd).ok()?,
+            magnitude,
+            obscuration: overlap(sun, moon, least) / (PI * sun * sun),
+            horizon: horizon(sun_vector, jd, observer),
+        })
+    }
+}
+
+impl LunarEclipse {
+    /// The eclipse at a full moon, if there is one.
+    fn near(full_moon: JulianDay) -> Option<Self> {
+        let (start, end) = (full_moon + -WINDOW, full_moon + WINDOW);
+        let jd = minimise(|jd| Umbra::at(jd).separation, start, end);
+        let umbra = Umbra::at(jd);
+        let penumbral_magnitude = umbra.magnitude(umbra.penumbra);
+        let umbral_magnitude = umbra.magnitude(umbra.umbra);
+        if penumbral_magnitude <= 0. {
+            return None;
+        }
+
+        // Times when the Moon's limb is at a distance from the axis
+        let contacts = |radius: fn(&Umbra) -> f64| -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+            let inside = |jd: JulianDay| {
+                let umbra = Umbra::at(jd);
+                umbra.separation - radius(&umbra)
+            };
+            let (first, last) = (crossing(inside, start, jd)?, crossing(inside, jd, end)?);
+            Some((
+                DateTime::try_from(first).ok()?,
+                DateTime::try_from(last).ok()?,
+            ))
+        };
+        let penumbral = contacts(|u| u.penumbra + u.moon)?;
+        let partial = contacts(|u| u.umbra + u.moon);
+        let total = contacts(|u| u.umbra - u.moon);
+        let kind = match (&partial, &total) {
+            (_, Some(_)) => LunarEclipseKind::Total,
+            (Some(_), None) => LunarEclipseKind::Partial,
+            _ => LunarEclipseKind::Penumbral,
+        };
+
+        Some(LunarEclipse {
+            kind,
+            maximum: DateTime::try_from(jd).ok()?,
+            gamma: umbra.gamma,
+            penumbral_magnitude,
+            umbral_magnitude,
+            penumbral,
+            partial,
+            total,
+        })
+    }
+
+    /// Where an observer sees the Moon at maximum.
+    pub fn horizon(&self, observer: &Observer) -> Horizon {
+        let jd = JulianDay::from(self.maximum);
+        let moon = subtract(
+            Bodies::at(jd).moon_vector,
+            observer.position(SiderealTime::apparent(jd)),
+        );
+        horizon(moon, jd, observer)
+    }
+}
+
+/// The Moon against the Earth's shadow, in degrees.
+struct Umbra {
+    /// Distance of the Moon's centre from the shadow axis.
+    separation: f64,
+    penumbra: f64,
+    umbra: f64,
+    /// Semi-diameter of the Moon.
+    moon: f64,
+    gamma: f64,
+}
+
+impl Umbra {
+    fn at(jd: JulianDay) -> Self {
+        let Bodies { sun, moon, .. } = Bodies::at(jd);
+        let axis = Equatorial::new(
+            hours((sun.equatorial.ra_hours() + 12.).rem_euclid(24.)),
+            -sun.equatorial.dec,
+        );
+        let separation = moon.equatorial.separation(&axis).to_degrees().value;
+        let sun_parallax = 8.794_143 / 3600. / sun.distance;
+        let sun_radius = 959.63 / 3600. / sun.distance;
+
+        // Danjon's rule, the Earth's radius enlarged by 1/85 for its
+        // atmosphere
+        let earth = 1.01 * moon.parallax + sun_parallax;
+        let gamma = separation.to_radians().sin() * moon.distance / EARTH_RADIUS;
+
+        Umbra {
+            separation,
+            penumbra: earth + sun_radius,
+            umbra: earth - sun_radius,
+            moon: (MOON_K_PENUMBRA * moon.parallax.to_radians().sin())
+                .asin()
+                .to_degrees(),
+            gamma: gamma.copysign(moon.equatorial.dec_degrees() - axis.dec_degrees()),
+        }
+    }
+
+    /// Fraction of the Moon's diameter within a shadow of a radius.
+    fn magnitude(&self, radius: f64) -> f64 {
+        (radius + self.moon - self.separation) / (2. * self.moon)
+    }
+}
+
+impl Bodies {
+    /// The Sun and Moon at a Julian day on UT.
+    fn at(jd: JulianDay) -> Self {
+        let tt = JulianDate::<Ut1>::new(jd).to::<Tt>().jd;
+        let sun = SolarPosition::from_julian_day(tt);
+        let moon = LunarPosition::from_julian_day(tt);
+
+        Bodies {
+            sun,
+            moon,
+            sun_vector: vector(sun.equatorial, sun.distance * AU),
+            moon_vector: vector(moon.equatorial, moon.distance),
+        }
+    }
+}
+
+impl Shadow {
+    fn at(jd: JulianDay) -> Self {
+        let Bodies {
+            sun_vector,
+            moon_vector,
+            ..
+        } = Bodies::at(jd);
+        let axis = subtract(moon_vector, sun_vector);
+        let length = norm(axis);
+        let z = axis.map(|x| x / length);
+
+        // y towards the north pole, and x towards the east
+        let pole = [-z[2] * z[0], -z[2] * z[1], 1. - z[2] * z[2]];
+        let y = pole.map(|x| x / norm(pole));
+        let x = [
+            y[1] * z[2] - y[2] * z[1],
+            y[2] * z[0] - y[0] * z[2],
+            y[0] * z[1] - y[1] * z[0],
+        ];
+        let along = |axis: [f64; 3]| dot(moon_vector, axis) / EARTH_RADIUS;
+        let cone = |radius: f64| {
+            let sin = radius / length;
+            sin / (1. - sin * sin).sqrt()
+        };
+
+        Shadow {
+            x: along(x),
+            y: along(y),
+            dec: z[2].asin(),
+            z_moon: along(z),
+            tan_f1: cone(SUN_RADIUS + MOON_K_PENUMBRA * EARTH_RADIUS),
+            tan_f2: cone(SUN_RADIUS - MOON_K_UMBRA * EARTH_RADIUS),
+        }
+    }
+
+    fn distance(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    /// Distance of the axis from the centre with the Earth stretched along
+    /// its polar axis into a sphere.
+    fn scaled_distance(&self) -> f64 {
+        let rho = (1. - EARTH_E2 * self.dec.cos().powi(2)).sqrt();
+        self.x.hypot(self.y / rho)
+    }
+
+    /// Radius of the penumbra at a distance along the axis.
+    fn penumbra(&self, z: f64) -> f64 {
+        MOON_K_PENUMBRA * (1. + self.tan_f1 * self.tan_f1).sqrt() + (z - self.z_moon) * self.tan_f1
+    }
+
+    /// Radius of the umbra at a distance along the axis, negative beyond
+    /// its vertex where the Moon is seen within the Sun.
+    fn umbra(&self, z: f64) -> f64 {
+        MOON_K_UMBRA * (1. + self.tan_f2 * self.tan_f2).sqrt() - (z - self.z_moon) * self.tan_f2
+    }
+}
+
+/// Where a topocentric vector points in an observer's sky.
+fn horizon(topocentric: [f64; 3], jd: JulianDay, observer: &Observer) -> Horizon {
+    let [x, y, z] = topocentric;
+    let position = Equatorial::new(
+        hours(y.atan2(x).to_degrees().rem_euclid(360.) / 15.),
+        degrees(z.atan2(x.hypot(y)).to_degrees()),
+    );
+    observer.horizon(position, jd)
+}
+
+fn vector(position: Equatorial, distance: f64) -> [f64; 3] {
+    let (ra, dec) = (
+        position.ra.to_radians().value,
+        position.dec.to_radians().value,
+    );
+    [
+        distance * dec.cos() * ra.cos(),
+        distance * dec.cos() * ra.sin(),
+        distance * dec.sin(),
+    ]
+}
+
+fn subtract(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// The angle between two vectors in radians, accurate when small.
+fn angle_between(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let cross = [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ];
+    norm(cross).atan2(dot(a, b))
+}
+
+/// Area common to two discs of radii `a` and `b` with centres `d` apart.
+fn overlap(a: f64, b: f64, d: f64) -> f64 {
+    if d >= a + b {
+        return 0.;
+    }
+    if d <= (a - b).abs() {
+        return PI * a.min(b).powi(2);
+    }
+    let lens = |r: f64, other: f64| {
+        let angle = ((d * d + r * r - other * other) / (2. * d * r)).acos();
+        r * r * (angle - angle.sin() * angle.cos())
+    };
+    lens(a, b) + lens(b, a)
+}
+
+/// The time between two others at which a function is least, by golden
+/// section search, to a hundredth of a second.
+fn minimise(f: impl Fn(JulianDay) -> f64, from: JulianDay, to: JulianDay) -> JulianDay {
+    let ratio = (5_f64.sqrt() - 1.) / 2.;
+    let (mut a, mut b) = (from, to);
+    while b - a > 1e-7 {
+        let step = (b - a) * (1. - ratio);
+        let (c, d) = (a + step, b + -step);
+        match f(c) < f(d) {
+            true => b = d,
+            false => a = c,
+        }
+    }
+    a + (b - a) / 2.
+}
+
+/// The time between two others at which a function changes sign, by
+/// bisection, or none if it has the same sign at both.
+fn crossing(f: impl Fn(JulianDay) -> f64, from: JulianDay, to: JulianDay) -> Option<JulianDay> {
+    let (mut a, mut b) = (from, to);
+    let rising = f(a) < 0.;
+    if rising == (f(b) < 0.) {
+        return None;
+    }
+    while b - a > 1e-7 {
+        let middle = a + (b - a) / 2.;
+        match (f(middle) < 0.) == rising {
+            true => a = middle,
+            false => b = middle,
+        }
+    }
+    Some(a + (b - a) / 2.)
+}
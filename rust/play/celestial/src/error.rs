@@ -0,0 +1,30 @@
+use std::fmt;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidDate(String),
+    Parse(String),
+    Propagation(String),
+    StdIo(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidDate(message) => write!(f, "invalid date: {}", message),
+            Error::Parse(message) => write!(f, "parse error: {}", message),
+            Error::Propagation(message) => write!(f, "propagation error: {}", message),
+            Error::StdIo(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Error::StdIo(error)
+    }
+}
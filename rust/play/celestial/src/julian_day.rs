@@ -1,109 +1,217 @@
-//use std::ops::{Add, Div, Mul, Sub},
+//! Julian days, counted from noon on 4713 BC January 1 of the Julian
+//! calendar, after Meeus, *Astronomical Algorithms*, chapter 7.
+//!
+//! Dates before 1582 October 15 are read as dates in the Julian calendar and
+//! later ones as Gregorian, as Meeus does.  Chrono's dates are all proleptic
+//! Gregorian, so its 1582 October 5 to 14 are read as Julian dates that fall
+//! after the reform, and a Julian leap day such as 1500 February 29 has no
+//! `DateTime` at all.
+
+use std::{
+    convert::TryFrom,
+    fmt,
+    ops::{Add, Sub},
+};
+
+use chrono::{Date, DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+
+use crate::{
+    calendar::{self, Calendar, Gregorian, GREGORIAN_REFORM},
+    error::{Error, Result},
+};
+
+const NANOS_PER_DAY: i64 = 86_400_000_000_000;
+
+/// Days either side of zero beyond which no date is worth computing, well
+/// outside chrono's range.
+const MAX_DAYS: i64 = 1_000_000_000;
+
+/// A Julian day, kept as whole days and the fraction of a day since noon so
+/// that it resolves well under a nanosecond.  It has no particular time
+/// scale: converting from a `DateTime` gives UTC, while the ephemerides
+/// expect TT, which [`crate::timescale::JulianDate`] converts to.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct JulianDay {
+    day: i64,
+    fraction: f64,
+}
 
-//use float_eq::derive_float_eq;
+impl JulianDay {
+    /// The standard epoch J2000.0, 2000 January 1.5.
+    pub const J2000: JulianDay = JulianDay {
+        day: 2_451_545,
+        fraction: 0.,
+    };
+
+    /// Whole days and a fraction, which need not be between 0 and 1.
+    pub fn new(day: i64, fraction: f64) -> Self {
+        let whole = fraction.floor();
+        let (day, fraction) = (day.saturating_add(whole as i64), fraction - whole);
+
+        // A tiny negative fraction can round up to a whole day
+        match fraction < 1. || fraction.is_nan() {
+            true => JulianDay { day, fraction },
+            false => JulianDay {
+                day: day.saturating_add(1),
+                fraction: 0.,
+            },
+        }
+    }
 
-/*
-#[derive_float_eq(
-    ulps_tol = "JulianDayUlps",
-    debug_ulps_diff = "JulianDayDebugUlpsDiff",
-    all_tol = "f64"
-)]
-*/
+    /// The whole days, counting from noon.
+    pub fn day(&self) -> i64 {
+        self.day
+    }
 
-/*
-impl Add<f64> for JulianDay {
-    type Output = JulianDay;
+    /// The fraction of a day since noon, from 0 up to 1.
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
 
-    fn add(self, rhs: f64) -> Self::Output {
-        JulianDay(self.0 + rhs)
+    /// As a single number, which keeps a time only to tens of microseconds.
+    pub fn to_f64(&self) -> f64 {
+        self.day as f64 + self.fraction
     }
-}
 
-impl Add<JulianDay> for f64 {
-    type Output = f64;
+    fn from_nanos(day: i64, nanos: i64) -> Self {
+        JulianDay {
+            day: day + nanos.div_euclid(NANOS_PER_DAY),
+            fraction: nanos.rem_euclid(NANOS_PER_DAY) as f64 / NANOS_PER_DAY as f64,
+        }
+    }
+}
 
-    fn add(self, rhs: JulianDay) -> Self::Output {
-        self + rhs.0
+/// Julian day number, for noon, of a calendar date.  Dates that would fall
+/// before the reform on the Gregorian calendar are read as Julian dates.
+fn day_number(date: NaiveDate) -> i64 {
+    let (year, month, day) = (date.year() as i64, date.month() as u8, date.day() as u8);
+    match calendar::gregorian_day_number(year, month, day) {
+        day_number if day_number >= GREGORIAN_REFORM => day_number,
+        _ => calendar::julian_day_number(year, month, day),
     }
 }
 
-impl Add<JulianDay> for JulianDay {
-    type Output = JulianDay;
+/// Whole days and the fraction of a day left over in a duration.
+fn split(duration: Duration) -> (i64, f64) {
+    let days = duration.num_days();
+    // What is left is under a day, which always fits in nanoseconds
+    let nanos = (duration - Duration::days(days))
+        .num_nanoseconds()
+        .unwrap_or(0);
 
-    fn add(self, rhs: JulianDay) -> Self::Output {
-        JulianDay(self.0 + rhs.0)
-    }
+    (days, nanos as f64 / NANOS_PER_DAY as f64)
 }
 
-impl Sub<f64> for JulianDay {
-    type Output = JulianDay;
+impl fmt::Display for JulianDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_f64())
+    }
+}
 
-    fn sub(self, rhs: f64) -> Self::Output {
-        JulianDay(self.0 - rhs)
+impl From<f64> for JulianDay {
+    fn from(jd: f64) -> Self {
+        JulianDay::new(0, jd)
     }
 }
 
-impl Sub<JulianDay> for f64 {
-    type Output = f64;
+impl From<JulianDay> for f64 {
+    fn from(jd: JulianDay) -> Self {
+        jd.to_f64()
+    }
+}
 
-    fn sub(self, rhs: JulianDay) -> Self::Output {
-        self - rhs.0
+impl<Tz: TimeZone> From<Date<Tz>> for JulianDay {
+    fn from(d: Date<Tz>) -> Self {
+        JulianDay::from(d.and_hms(0, 0, 0))
     }
 }
 
-impl Sub<JulianDay> for JulianDay {
-    type Output = JulianDay;
+impl<Tz: TimeZone> From<DateTime<Tz>> for JulianDay {
+    fn from(dt: DateTime<Tz>) -> Self {
+        let utc = dt.naive_utc();
+        let nanos =
+            utc.num_seconds_from_midnight() as i64 * 1_000_000_000 + utc.nanosecond() as i64;
 
-    fn sub(self, rhs: JulianDay) -> Self::Output {
-        JulianDay(self.0 - rhs.0)
+        // The day number is for noon, and the day began half a day earlier
+        JulianDay::from_nanos(day_number(utc.date()) - 1, nanos + NANOS_PER_DAY / 2)
     }
 }
 
-impl Mul<f64> for JulianDay {
-    type Output = JulianDay;
-
-    fn mul(self, rhs: f64) -> Self::Output {
-        JulianDay(self.0 * rhs)
+impl TryFrom<JulianDay> for DateTime<Utc> {
+    type Error = Error;
+
+    /// The nearest nanosecond, or an error for a Julian leap day that the
+    /// Gregorian calendar lacks or a date beyond chrono's range.
+    fn try_from(jd: JulianDay) -> Result<Self> {
+        if !(-MAX_DAYS..=MAX_DAYS).contains(&jd.day) || !jd.fraction.is_finite() {
+            return Err(Error::InvalidDate(format!(
+                "Julian day {} out of range",
+                jd
+            )));
+        }
+        let nanos = (jd.fraction * NANOS_PER_DAY as f64).round() as i64 + NANOS_PER_DAY / 2;
+        let z = jd.day + nanos.div_euclid(NANOS_PER_DAY);
+        let nanos = nanos.rem_euclid(NANOS_PER_DAY);
+
+        let date = Gregorian::default().date(z);
+        let naive = i32::try_from(date.year)
+            .ok()
+            .and_then(|year| NaiveDate::from_ymd_opt(year, date.month as u32, date.day as u32))
+            .ok_or_else(|| Error::InvalidDate(format!("{} for Julian day {}", date, jd)))?;
+        let time = NaiveTime::from_num_seconds_from_midnight(
+            (nanos / 1_000_000_000) as u32,
+            (nanos % 1_000_000_000) as u32,
+        );
+
+        Ok(DateTime::from_utc(naive.and_time(time), Utc))
     }
 }
 
-impl Mul<JulianDay> for f64 {
-    type Output = f64;
+impl Add<Duration> for JulianDay {
+    type Output = JulianDay;
 
-    fn mul(self, rhs: JulianDay) -> Self::Output {
-        self * rhs.0
+    fn add(self, rhs: Duration) -> Self::Output {
+        let (days, fraction) = split(rhs);
+        JulianDay::new(self.day.saturating_add(days), self.fraction + fraction)
     }
 }
 
-impl Mul<JulianDay> for JulianDay {
+impl Sub<Duration> for JulianDay {
     type Output = JulianDay;
 
-    fn mul(self, rhs: JulianDay) -> Self::Output {
-        JulianDay(self.0 * rhs.0)
+    fn sub(self, rhs: Duration) -> Self::Output {
+        let (days, fraction) = split(rhs);
+        JulianDay::new(self.day.saturating_sub(days), self.fraction - fraction)
     }
 }
 
-impl Div<f64> for JulianDay {
+impl Add<f64> for JulianDay {
     type Output = JulianDay;
 
-    fn div(self, rhs: f64) -> Self::Output {
-        JulianDay(self.0 / rhs)
+    /// Add a number of days.
+    fn add(self, rhs: f64) -> Self::Output {
+        let whole = rhs.floor();
+        JulianDay::new(
+            self.day.saturating_add(whole as i64),
+            self.fraction + (rhs - whole),
+        )
     }
 }
 
-impl Div<JulianDay> for f64 {
-    type Output = f64;
+impl Sub<f64> for JulianDay {
+    type Output = JulianDay;
 
-    fn div(self, rhs: JulianDay) -> Self::Output {
-        self / rhs.0
+    /// Subtract a number of days.
+    fn sub(self, rhs: f64) -> Self::Output {
+        self + -rhs
     }
 }
 
-impl Div<JulianDay> for JulianDay {
-    type Output = JulianDay;
+impl Sub<JulianDay> for JulianDay {
+    type Output = f64;
 
-    fn div(self, rhs: JulianDay) -> Self::Output {
-        JulianDay(self.0 / rhs.0)
+    /// Days between two Julian days.
+    fn sub(self, rhs: JulianDay) -> Self::Output {
+        self.day.saturating_sub(rhs.day) as f64 + (self.fraction - rhs.fraction)
     }
 }
-*/
@@ -0,0 +1,465 @@
+//! Position and phases of the Moon, after Meeus, *Astronomical Algorithms*,
+//! chapters 47 to 49 and 53.  The position uses the larger terms of the ELP
+//! series and is good to about 10" in longitude.
+
+use std::{
+    convert::TryFrom,
+    ops::{Bound, RangeBounds},
+};
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::{
+    coords::{nutation, obliquity, Ecliptic, Equatorial},
+    degrees,
+    sun::SolarPosition,
+    timescale::{self, JulianDate, Tt},
+    JulianDay,
+};
+
+/// Equatorial radius of the Earth in kilometres.
+const EARTH_RADIUS: f64 = 6378.14;
+
+/// Kilometres in an astronomical unit.
+const AU: f64 = 149_597_870.7;
+
+/// Mean length of a lunation in days.
+const SYNODIC_MONTH: f64 = 29.530_588_861;
+
+/// Mean new moon of 2000 January 6, in Julian days.
+const NEW_MOON_2000: f64 = 2_451_550.097_66;
+
+/// Inclination of the mean lunar equator to the ecliptic, in degrees.
+const LUNAR_EQUATOR_INCLINATION: f64 = 1.542_42;
+
+/// Periodic terms for longitude (10⁻⁶ degrees) and distance (10⁻³ km), as
+/// multiples of D, M, M' and F.  Table 47.A, down to 500.
+#[rustfmt::skip]
+const LONGITUDE_DISTANCE: [(i8, i8, i8, i8, f64, f64); 49] = [
+    (0, 0, 1, 0, 6_288_774., -20_905_355.),
+    (2, 0, -1, 0, 1_274_027., -3_699_111.),
+    (2, 0, 0, 0, 658_314., -2_955_968.),
+    (0, 0, 2, 0, 213_618., -569_925.),
+    (0, 1, 0, 0, -185_116., 48_888.),
+    (0, 0, 0, 2, -114_332., -3_149.),
+    (2, 0, -2, 0, 58_793., 246_158.),
+    (2, -1, -1, 0, 57_066., -152_138.),
+    (2, 0, 1, 0, 53_322., -170_733.),
+    (2, -1, 0, 0, 45_758., -204_586.),
+    (0, 1, -1, 0, -40_923., -129_620.),
+    (1, 0, 0, 0, -34_720., 108_743.),
+    (0, 1, 1, 0, -30_383., 104_755.),
+    (2, 0, 0, -2, 15_327., 10_321.),
+    (0, 0, 1, 2, -12_528., 0.),
+    (0, 0, 1, -2, 10_980., 79_661.),
+    (4, 0, -1, 0, 10_675., -34_782.),
+    (0, 0, 3, 0, 10_034., -23_210.),
+    (4, 0, -2, 0, 8_548., -21_636.),
+    (2, 1, -1, 0, -7_888., 24_208.),
+    (2, 1, 0, 0, -6_766., 30_824.),
+    (1, 0, -1, 0, -5_163., -8_379.),
+    (1, 1, 0, 0, 4_987., -16_675.),
+    (2, -1, 1, 0, 4_036., -12_831.),
+    (2, 0, 2, 0, 3_994., -10_445.),
+    (4, 0, 0, 0, 3_861., -11_650.),
+    (2, 0, -3, 0, 3_665., 14_403.),
+    (0, 1, -2, 0, -2_689., -7_003.),
+    (2, 0, -1, 2, -2_602., 0.),
+    (2, -1, -2, 0, 2_390., 10_056.),
+    (1, 0, 1, 0, -2_348., 6_322.),
+    (2, -2, 0, 0, 2_236., -9_884.),
+    (0, 1, 2, 0, -2_120., 5_751.),
+    (0, 2, 0, 0, -2_069., 0.),
+    (2, -2, -1, 0, 2_048., -4_950.),
+    (2, 0, 1, -2, -1_773., 4_130.),
+    (2, 0, 0, 2, -1_595., 0.),
+    (4, -1, -1, 0, 1_215., -3_958.),
+    (0, 0, 2, 2, -1_110., 0.),
+    (3, 0, -1, 0, -892., 3_258.),
+    (2, 1, 1, 0, -810., 2_616.),
+    (4, -1, -2, 0, 759., -1_897.),
+    (0, 2, -1, 0, -713., -2_117.),
+    (2, 2, -1, 0, -700., 2_354.),
+    (2, 1, -2, 0, 691., 0.),
+    (2, -1, 0, -2, 596., 0.),
+    (4, 0, 1, 0, 549., -1_423.),
+    (0, 0, 4, 0, 537., -1_117.),
+    (4, -1, 0, 0, 520., -1_571.),
+];
+
+/// Periodic terms for latitude (10⁻⁶ degrees).  Table 47.B, down to 800.
+#[rustfmt::skip]
+const LATITUDE: [(i8, i8, i8, i8, f64); 30] = [
+    (0, 0, 0, 1, 5_128_122.),
+    (0, 0, 1, 1, 280_602.),
+    (0, 0, 1, -1, 277_693.),
+    (2, 0, 0, -1, 173_237.),
+    (2, 0, -1, 1, 55_413.),
+    (2, 0, -1, -1, 46_271.),
+    (2, 0, 0, 1, 32_573.),
+    (0, 0, 2, 1, 17_198.),
+    (2, 0, 1, -1, 9_266.),
+    (0, 0, 2, -1, 8_822.),
+    (2, -1, 0, -1, 8_216.),
+    (2, 0, -2, -1, 4_324.),
+    (2, 0, 1, 1, 4_200.),
+    (2, 1, 0, -1, -3_359.),
+    (2, -1, -1, 1, 2_463.),
+    (2, -1, 0, 1, 2_211.),
+    (2, -1, -1, -1, 2_065.),
+    (0, 1, -1, -1, -1_870.),
+    (4, 0, -1, -1, 1_828.),
+    (0, 1, 0, 1, -1_794.),
+    (0, 0, 0, 3, -1_749.),
+    (0, 1, -1, 1, -1_565.),
+    (1, 0, 0, 1, -1_491.),
+    (0, 1, 1, 1, -1_475.),
+    (0, 1, 1, -1, -1_410.),
+    (0, 1, 0, -1, -1_344.),
+    (1, 0, 0, -1, -1_335.),
+    (0, 0, 3, 1, 1_107.),
+    (4, 0, 0, -1, 1_021.),
+    (4, 0, -1, 1, 833.),
+];
+
+/// Geocentric position of the Moon at an instant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LunarPosition {
+    /// Geometric ecliptic coordinates, referred to the mean equinox of the
+    /// date.
+    pub ecliptic: Ecliptic,
+    /// Ecliptic longitude in degrees, corrected for nutation.
+    pub apparent_longitude: f64,
+    /// Apparent right ascension and declination.
+    pub equatorial: Equatorial,
+    /// Equatorial horizontal parallax in degrees.
+    pub parallax: f64,
+    /// Distance from the centre of the Earth in kilometres.
+    pub distance: f64,
+}
+
+/// The principal phases of the Moon.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Phase {
+    NewMoon,
+    FirstQuarter,
+    FullMoon,
+    LastQuarter,
+}
+
+/// Optical libration in degrees: the selenographic longitude and latitude
+/// of the centre of the Moon's disc as seen from the Earth.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Libration {
+    pub longitude: f64,
+    pub latitude: f64,
+}
+
+/// Fundamental arguments of the lunar theory, in degrees.
+struct Arguments {
+    /// Julian centuries from J2000.0.
+    t: f64,
+    /// Mean longitude of the Moon.
+    mean_longitude: f64,
+    /// Mean elongation of the Moon from the Sun.
+    elongation: f64,
+    /// Mean anomaly of the Sun.
+    sun_anomaly: f64,
+    /// Mean anomaly of the Moon.
+    moon_anomaly: f64,
+    /// Argument of latitude of the Moon.
+    latitude: f64,
+}
+
+impl Arguments {
+    fn new(jd: JulianDay) -> Self {
+        let t = (jd - JulianDay::J2000) / 36_525.;
+        let poly =
+            |c: [f64; 5]| c[0] + c[1] * t + c[2] * t.powi(2) + c[3] * t.powi(3) + c[4] * t.powi(4);
+
+        Arguments {
+            t,
+            mean_longitude: poly([
+                218.316_447_7,
+                481_267.881_234_21,
+                -0.001_578_6,
+                1. / 538_841.,
+                -1. / 65_194_000.,
+            ]),
+            elongation: poly([
+                297.850_192_1,
+                445_267.111_403_4,
+                -0.001_881_9,
+                1. / 545_868.,
+                -1. / 113_065_000.,
+            ]),
+            sun_anomaly: poly([
+                357.529_109_2,
+                35_999.050_290_9,
+                -0.000_153_6,
+                1. / 24_490_000.,
+                0.,
+            ]),
+            moon_anomaly: poly([
+                134.963_396_4,
+                477_198.867_505_5,
+                0.008_741_4,
+                1. / 69_699.,
+                -1. / 14_712_000.,
+            ]),
+            latitude: poly([
+                93.272_095,
+                483_202.017_523_3,
+                -0.003_653_9,
+                -1. / 3_526_000.,
+                1. / 863_310_000.,
+            ]),
+        }
+    }
+
+    /// Sum of `D·d + M·m + M'·m' + F·f` for a term, in radians.
+    fn argument(&self, d: i8, m: i8, mp: i8, f: i8) -> f64 {
+        (d as f64 * self.elongation
+            + m as f64 * self.sun_anomaly
+            + mp as f64 * self.moon_anomaly
+            + f as f64 * self.latitude)
+            .to_radians()
+    }
+
+    /// Factor for terms involving the Sun's anomaly, for the decreasing
+    /// eccentricity of the Earth's orbit.
+    fn eccentricity(&self, m: i8) -> f64 {
+        let e = 1. - 0.002_516 * self.t - 0.000_007_4 * self.t.powi(2);
+        e.powi(m.abs() as i32)
+    }
+}
+
+impl LunarPosition {
+    /// The position at a Julian day on TT.
+    pub fn from_julian_day(jd: JulianDay) -> Self {
+        let args = Arguments::new(jd);
+        let t = args.t;
+        let a1 = (119.75 + 131.849 * t).to_radians();
+        let a2 = (53.09 + 479_264.29 * t).to_radians();
+        let a3 = (313.45 + 481_266.484 * t).to_radians();
+        let (l, f, mp) = (
+            args.mean_longitude.to_radians(),
+            args.latitude.to_radians(),
+            args.moon_anomaly.to_radians(),
+        );
+
+        let (mut sum_l, mut sum_r) = (0., 0.);
+        for &(d, m, mp, f, coeff_l, coeff_r) in LONGITUDE_DISTANCE.iter() {
+            let arg = args.argument(d, m, mp, f);
+            let e = args.eccentricity(m);
+            sum_l += coeff_l * e * arg.sin();
+            sum_r += coeff_r * e * arg.cos();
+        }
+        sum_l += 3958. * a1.sin() + 1962. * (l - f).sin() + 318. * a2.sin();
+
+        let mut sum_b = 0.;
+        for &(d, m, mp, f, coeff) in LATITUDE.iter() {
+            sum_b += coeff * args.eccentricity(m) * args.argument(d, m, mp, f).sin();
+        }
+        sum_b += -2235. * l.sin()
+            + 382. * a3.sin()
+            + 175. * (a1 - f).sin()
+            + 175. * (a1 + f).sin()
+            + 127. * (l - mp).sin()
+            - 115. * (l + mp).sin();
+
+        let ecliptic = Ecliptic::new(
+            degrees((args.mean_longitude + sum_l / 1e6).rem_euclid(360.)),
+            degrees(sum_b / 1e6),
+        );
+        let distance = 385_000.56 + sum_r / 1000.;
+
+        let (nutation_lon, nutation_obl) = nutation(jd);
+        let apparent_longitude =
+            (ecliptic.lon_degrees() + nutation_lon.to_degrees().value).rem_euclid(360.);
+        let equatorial = Ecliptic::new(degrees(apparent_longitude), ecliptic.lat)
+            .to_equatorial(obliquity(jd) + nutation_obl);
+
+        LunarPosition {
+            ecliptic,
+            apparent_longitude,
+            equatorial,
+            parallax: (EARTH_RADIUS / distance).asin().to_degrees(),
+            distance,
+        }
+    }
+
+    /// Angular radius in degrees.
+    pub fn semi_diameter(&self) -> f64 {
+        0.2725 * self.parallax
+    }
+}
+
+impl<Tz: TimeZone> From<DateTime<Tz>> for LunarPosition {
+    fn from(dt: DateTime<Tz>) -> Self {
+        LunarPosition::from_julian_day(JulianDate::<Tt>::from_datetime(dt).jd)
+    }
+}
+
+/// Angle Sun–Moon–Earth in degrees: 0 at full moon and 180 at new moon.
+pub fn phase_angle<Tz: TimeZone>(dt: DateTime<Tz>) -> f64 {
+    let jd = JulianDate::<Tt>::from_datetime(dt).jd;
+    let (moon, sun) = (
+        LunarPosition::from_julian_day(jd),
+        SolarPosition::from_julian_day(jd),
+    );
+
+    // Elongation of the Moon from the Sun
+    let cos_psi = moon.ecliptic.lat.to_radians().value.cos()
+        * (moon.apparent_longitude - sun.apparent_longitude)
+            .to_radians()
+            .cos();
+    let psi = cos_psi.acos();
+    let sun_distance = sun.distance * AU;
+
+    (sun_distance * psi.sin())
+        .atan2(moon.distance - sun_distance * cos_psi)
+        .to_degrees()
+}
+
+/// Fraction of the Moon's disc that is lit, from 0 to 1.
+pub fn illuminated_fraction<Tz: TimeZone>(dt: DateTime<Tz>) -> f64 {
+    (1. + phase_angle(dt).to_radians().cos()) / 2.
+}
+
+/// Optical libration in longitude and latitude.
+pub fn libration<Tz: TimeZone>(dt: DateTime<Tz>) -> Libration {
+    let jd = JulianDate::<Tt>::from_datetime(dt).jd;
+    let args = Arguments::new(jd);
+    let moon = LunarPosition::from_julian_day(jd);
+    let t = args.t;
+
+    // Longitude of the mean ascending node
+    let node = 125.044_547_9 - 1_934.136_289_1 * t + 0.002_075_4 * t.powi(2) + t.powi(3) / 467_441.
+        - t.powi(4) / 60_616_000.;
+    let w = (moon.ecliptic.lon_degrees() - node).to_radians();
+    let (beta, i) = (
+        moon.ecliptic.lat.to_radians().value,
+        LUNAR_EQUATOR_INCLINATION.to_radians(),
+    );
+
+    let a = (w.sin() * beta.cos() * i.cos() - beta.sin() * i.sin()).atan2(w.cos() * beta.cos());
+    let latitude = (-w.sin() * beta.cos() * i.sin() - beta.sin() * i.cos()).asin();
+
+    Libration {
+        longitude: (a.to_degrees() - args.latitude + 180.).rem_euclid(360.) - 180.,
+        latitude: latitude.to_degrees(),
+    }
+}
+
+impl Phase {
+    /// Apparent longitude of the Moon less that of the Sun, in degrees.
+    pub fn elongation(&self) -> f64 {
+        match self {
+            Phase::NewMoon => 0.,
+            Phase::FirstQuarter => 90.,
+            Phase::FullMoon => 180.,
+            Phase::LastQuarter => 270.,
+        }
+    }
+
+    fn from_quarter(quarter: i64) -> Self {
+        match quarter.rem_euclid(4) {
+            0 => Phase::NewMoon,
+            1 => Phase::FirstQuarter,
+            2 => Phase::FullMoon,
+            _ => Phase::LastQuarter,
+        }
+    }
+}
+
+/// The Julian day on TT of a phase near a guess, found by iterating on the
+/// difference in longitude between the Moon and the Sun.
+fn phase_time(phase: Phase, guess: JulianDay) -> JulianDay {
+    let mut jd = guess;
+
+    for _ in 0..20 {
+        let moon = LunarPosition::from_julian_day(jd);
+        let sun = SolarPosition::from_julian_day(jd);
+        let error = (moon.apparent_longitude - sun.apparent_longitude - phase.elongation() + 180.)
+            .rem_euclid(360.)
+            - 180.;
+        // The Moon gains about 12.19° a day on the Sun
+        let correction = -error / 12.190_749;
+        jd = jd + correction;
+        if correction.abs() < 1e-7 {
+            break;
+        }
+    }
+
+    jd
+}
+
+/// An iterator over the principal phases of the Moon in a time range.
+#[derive(Clone, Debug)]
+pub struct LunarPhases {
+    /// Quarters of a lunation since the new moon of 2000 January 6.
+    quarter: i64,
+    start: Bound<DateTime<Utc>>,
+    end: Bound<DateTime<Utc>>,
+}
+
+/// The new moons, first quarters, full moons and last quarters in a time
+/// range, in order.  An unbounded start begins from now.
+pub fn lunar_phases<R: RangeBounds<DateTime<Utc>>>(range: R) -> LunarPhases {
+    let start = range.start_bound().cloned();
+    let first = match start {
+        Bound::Included(x) | Bound::Excluded(x) => x,
+        Bound::Unbounded => Utc::now(),
+    };
+    let lunations = (JulianDay::from(first).to_f64() - NEW_MOON_2000) / SYNODIC_MONTH;
+
+    LunarPhases {
+        // Start a quarter early, since true phases stray from mean ones
+        quarter: (lunations * 4.).floor() as i64 - 1,
+        start: match start {
+            Bound::Unbounded => Bound::Included(first),
+            bound => bound,
+        },
+        end: range.end_bound().cloned(),
+    }
+}
+
+impl Iterator for LunarPhases {
+    type Item = (Phase, DateTime<Utc>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let phase = Phase::from_quarter(self.quarter);
+            let guess = NEW_MOON_2000 + self.quarter as f64 / 4. * SYNODIC_MONTH;
+            let tt = JulianDate::<Tt>::new(phase_time(phase, JulianDay::from(guess)));
+            let utc = tt.to::<timescale::Utc>();
+            self.quarter += 1;
+
+            // Skip a phase on a Julian leap day, which chrono lacks, but stop
+            // at the end of chrono's range, where the next day is missing too
+            let time = match DateTime::<Utc>::try_from(utc) {
+                Ok(time) => time,
+                Err(_) if DateTime::<Utc>::try_from(utc.jd + 1.).is_ok() => continue,
+                Err(_) => return None,
+            };
+
+            let after_start = match self.start {
+                Bound::Included(start) => time >= start,
+                Bound::Excluded(start) => time > start,
+                Bound::Unbounded => true,
+            };
+            let before_end = match self.end {
+                Bound::Included(end) => time <= end,
+                Bound::Excluded(end) => time < end,
+                Bound::Unbounded => true,
+            };
+
+            match (after_start, before_end) {
+                (true, true) => return Some((phase, time)),
+                (_, false) => return None,
+                (false, true) => {}
+            }
+        }
+    }
+}
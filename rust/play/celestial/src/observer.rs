@@ -0,0 +1,263 @@
+//! Where an observer stands, and what that changes about the sky: the
+//! geocentric place of a site on the WGS-84 ellipsoid, after Meeus,
+//! *Astronomical Algorithms*, chapter 11, and the diurnal parallax of
+//! nearby bodies, chapter 40.
+//!
+//! Longitudes are positive east of Greenwich throughout the crate.  Meeus
+//! and many almanacs count them positive west, so a longitude copied from
+//! them needs its sign changed, or can be parsed with its hemisphere as
+//! `"38.9214N 77.0656W"`.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, TimeZone};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    coords::{Equatorial, Horizon},
+    corrections::Atmosphere,
+    degrees,
+    error::{Error, Result},
+    hours,
+    sidereal::SiderealTime,
+    JulianDay,
+};
+
+/// Equatorial radius of the WGS-84 ellipsoid, in kilometres.
+pub const WGS84_RADIUS: f64 = 6_378.137;
+
+/// Flattening of the WGS-84 ellipsoid.
+pub const WGS84_FLATTENING: f64 = 1. / 298.257_223_563;
+
+/// A place on the Earth, with the air and the clocks there.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Observer {
+    /// Geodetic latitude in degrees, positive north.
+    pub latitude: f64,
+    /// Longitude in degrees, positive east.
+    pub longitude: f64,
+    /// Height above the ellipsoid in metres.
+    #[serde(default)]
+    pub elevation: f64,
+    /// Air temperature in degrees Celsius.
+    #[serde(default = "standard_temperature")]
+    pub temperature: f64,
+    /// Air pressure in millibars.
+    #[serde(default = "standard_pressure")]
+    pub pressure: f64,
+    /// The time zone of local dates.
+    #[serde(default = "utc")]
+    pub tz: Tz,
+}
+
+impl Observer {
+    /// At sea level in the standard atmosphere, keeping UTC.
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Observer {
+            latitude,
+            longitude,
+            elevation: 0.,
+            temperature: standard_temperature(),
+            pressure: standard_pressure(),
+            tz: utc(),
+        }
+    }
+
+    pub fn with_elevation(self, elevation: f64) -> Self {
+        Observer { elevation, ..self }
+    }
+
+    pub fn with_atmosphere(self, temperature: f64, pressure: f64) -> Self {
+        Observer {
+            temperature,
+            pressure,
+            ..self
+        }
+    }
+
+    pub fn with_timezone(self, tz: Tz) -> Self {
+        Observer { tz, ..self }
+    }
+
+    /// The air at the site, for refraction.
+    pub fn atmosphere(&self) -> Atmosphere {
+        Atmosphere::new(self.temperature, self.pressure)
+    }
+
+    /// A moment on the observer's clocks.
+    pub fn local_time<T: TimeZone>(&self, dt: &DateTime<T>) -> DateTime<Tz> {
+        dt.with_timezone(&self.tz)
+    }
+
+    /// ρ sin φ′ and ρ cos φ′, the distance from the Earth's centre in
+    /// equatorial radii resolved along the axis and the equator.
+    pub fn geocentric(&self) -> (f64, f64) {
+        let latitude = self.latitude.to_radians();
+        let height = self.elevation / 1_000. / WGS84_RADIUS;
+        let u = ((1. - WGS84_FLATTENING) * latitude.tan()).atan();
+
+        (
+            (1. - WGS84_FLATTENING) * u.sin() + height * latitude.sin(),
+            u.cos() + height * latitude.cos(),
+        )
+    }
+
+    /// Geocentric latitude φ′ in degrees, which is smaller than the
+    /// geodetic latitude by up to 0.19° at 45°.
+    pub fn geocentric_latitude(&self) -> f64 {
+        let (rho_sin, rho_cos) = self.geocentric();
+        rho_sin.atan2(rho_cos).to_degrees()
+    }
+
+    /// Distance from the centre of the Earth in kilometres.
+    pub fn geocentric_distance(&self) -> f64 {
+        let (rho_sin, rho_cos) = self.geocentric();
+        WGS84_RADIUS * rho_sin.hypot(rho_cos)
+    }
+
+    /// Place in kilometres in the frame turning with the Earth, with x
+    /// towards the Greenwich meridian on the equator and z to the north
+    /// pole.
+    pub fn earth_fixed(&self) -> [f64; 3] {
+        let (rho_sin, rho_cos) = self.geocentric();
+        let longitude = self.longitude.to_radians();
+        [
+            WGS84_RADIUS * rho_cos * longitude.cos(),
+            WGS84_RADIUS * rho_cos * longitude.sin(),
+            WGS84_RADIUS * rho_sin,
+        ]
+    }
+
+    /// Place in kilometres along the equator and equinox, at a Greenwich
+    /// sidereal time.
+    pub fn position(&self, sidereal: SiderealTime) -> [f64; 3] {
+        let [x, y, z] = self.earth_fixed();
+        let theta = sidereal.degrees().to_radians();
+        [
+            theta.cos() * x - theta.sin() * y,
+            theta.sin() * x + theta.cos() * y,
+            z,
+        ]
+    }
+
+    /// Where a geocentric place is in the sky at a Julian day on UT, without
+    /// parallax or refraction.
+    pub fn horizon(&self, position: Equatorial, jd: JulianDay) -> Horizon {
+        let lst = SiderealTime::apparent(jd)
+            .local(degrees(self.longitude))
+            .angle();
+        position
+            .to_hour_angle(lst)
+            .to_horizon(degrees(self.latitude))
+    }
+
+    /// Topocentric place of a body with an equatorial horizontal parallax in
+    /// degrees, such as the Moon's, at a Julian day on UT (Meeus, chapter
+    /// 40).  The Moon moves by up to a degree.
+    pub fn topocentric(&self, position: Equatorial, parallax: f64, jd: JulianDay) -> Equatorial {
+        let (rho_sin, rho_cos) = self.geocentric();
+        let lst = SiderealTime::apparent(jd)
+            .local(degrees(self.longitude))
+            .hours();
+        let (hour_angle, dec, sin_pi) = (
+            ((lst - position.ra_hours()) * 15.).to_radians(),
+            position.dec.to_radians().value,
+            parallax.to_radians().sin(),
+        );
+
+        let denominator = dec.cos() - rho_cos * sin_pi * hour_angle.cos();
+        let d_ra = (-rho_cos * sin_pi * hour_angle.sin()).atan2(denominator);
+        let dec = ((dec.sin() - rho_sin * sin_pi) * d_ra.cos()).atan2(denominator);
+
+        Equatorial {
+            ra: hours((position.ra_hours() + d_ra.to_degrees() / 15.).rem_euclid(24.)),
+            dec: degrees(dec.to_degrees()),
+        }
+    }
+
+    /// Dip of the sea horizon below the horizontal from the elevation,
+    /// refraction included, in degrees.
+    pub fn dip(&self) -> f64 {
+        2.076 * self.elevation.max(0.).sqrt() / 60.
+    }
+}
+
+impl FromStr for Observer {
+    type Err = Error;
+
+    /// Latitude and longitude in decimal degrees, and optionally an
+    /// elevation in metres, separated by commas or spaces.  A latitude may
+    /// end in N or S and a longitude in E or W instead of a sign, as in
+    /// `"51.4769N, 0.0005W, 46m"`.
+    fn from_str(s: &str) -> Result<Self> {
+        let fields: Vec<_> = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|field| !field.is_empty())
+            .collect();
+        let (latitude, longitude, elevation) = match fields[..] {
+            [latitude, longitude] => (latitude, longitude, None),
+            [latitude, longitude, elevation] => (latitude, longitude, Some(elevation)),
+            _ => {
+                return Err(Error::Parse(format!(
+                    "expected a latitude, a longitude and an elevation, got {:?}",
+                    s
+                )))
+            }
+        };
+
+        let observer = Observer::new(
+            coordinate(latitude, 'N', 'S', 90.)?,
+            coordinate(longitude, 'E', 'W', 180.)?,
+        );
+        match elevation {
+            Some(elevation) => {
+                let metres = elevation.strip_suffix('m').unwrap_or(elevation);
+                let elevation = metres
+                    .parse()
+                    .map_err(|_| Error::Parse(format!("bad elevation {:?}", elevation)))?;
+                Ok(observer.with_elevation(elevation))
+            }
+            None => Ok(observer),
+        }
+    }
+}
+
+/// A longitude in decimal degrees, signed positive east or ending in E or
+/// W, as in `"77.0656W"`.
+pub fn parse_longitude(s: &str) -> Result<f64> {
+    coordinate(s, 'E', 'W', 180.)
+}
+
+/// A signed coordinate in degrees, or one ending in a hemisphere.
+fn coordinate(s: &str, positive: char, negative: char, limit: f64) -> Result<f64> {
+    let upper = s.to_ascii_uppercase();
+    let (number, hemisphere) = match upper.chars().last() {
+        Some(c) if c == positive => (&upper[..upper.len() - 1], Some(1.)),
+        Some(c) if c == negative => (&upper[..upper.len() - 1], Some(-1.)),
+        _ => (&upper[..], None),
+    };
+    let degrees = match hemisphere {
+        Some(_) if number.starts_with(['-', '+']) => None,
+        _ => number.parse::<f64>().ok(),
+    }
+    .map(|x| x * hemisphere.unwrap_or(1.))
+    .ok_or_else(|| Error::Parse(format!("bad coordinate {:?}", s)))?;
+
+    match degrees.abs() <= limit {
+        true => Ok(degrees),
+        false => Err(Error::Parse(format!("coordinate {:?} out of range", s))),
+    }
+}
+
+fn standard_temperature() -> f64 {
+    Atmosphere::default().temperature
+}
+
+fn standard_pressure() -> f64 {
+    Atmosphere::default().pressure
+}
+
+fn utc() -> Tz {
+    Tz::UTC
+}
@@ -0,0 +1,465 @@
+//! Positions of the planets, from Keplerian mean elements or from truncated
+//! VSOP87 series, after Meeus, *Astronomical Algorithms*, chapters 30 to 33
+//! and 41.
+//!
+//! Heliocentric and geocentric coordinates are referred to the ecliptic and
+//! equinox of the date, at Julian days on TT.  The mean elements, from
+//! Standish's fit to the JPL ephemeris, are good to a few arcminutes between
+//! 1800 and 2050; the VSOP87 series to a few arcseconds over several thousand
+//! years.
+
+use std::{convert::TryFrom, f64::consts::PI, str::FromStr};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    coords::{nutation, obliquity, Ecliptic, Equatorial},
+    degrees,
+    error::{Error, Result},
+    timescale::{self, JulianDate, Tt},
+    vsop87::{self, Vsop87},
+    JulianDay,
+};
+
+/// Light travel time for one AU, in days.
+const LIGHT_TIME: f64 = 0.005_775_518_3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Planet {
+    Mercury,
+    Venus,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+}
+
+/// How to compute a planet's orbit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Theory {
+    /// Mean orbital elements and their secular rates.
+    Kepler,
+    /// Truncated VSOP87 series.
+    Vsop87,
+}
+
+/// Heliocentric ecliptic coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Heliocentric {
+    pub lon: f64,
+    pub lat: f64,
+    /// Distance from the Sun in AU.
+    pub radius: f64,
+}
+
+/// Where a planet appears from the centre of the Earth.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlanetPosition {
+    /// Apparent ecliptic coordinates, corrected for light time, aberration
+    /// and nutation.
+    pub ecliptic: Ecliptic,
+    pub equatorial: Equatorial,
+    /// Distance from the Earth in AU.
+    pub distance: f64,
+    /// Distance from the Sun in AU.
+    pub radius: f64,
+    /// Angular distance from the Sun, in degrees.
+    pub elongation: f64,
+    /// Angle Sun–planet–Earth, in degrees.
+    pub phase_angle: f64,
+    /// Visual magnitude, ignoring Saturn's rings.
+    pub magnitude: f64,
+}
+
+/// Alignments of a planet with the Sun, in ecliptic longitude.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Configuration {
+    Conjunction,
+    Opposition,
+}
+
+/// Mean elements at J2000.0 and their rates per Julian century: semi-major
+/// axis (AU), eccentricity, inclination, mean longitude, longitude of
+/// perihelion and longitude of the ascending node (degrees), referred to
+/// the ecliptic and equinox of J2000.0.
+type Elements = ([f64; 6], [f64; 6]);
+
+#[rustfmt::skip]
+const MERCURY: Elements = (
+    [0.38709927, 0.20563593, 7.00497902, 252.25032350, 77.45779628, 48.33076593],
+    [0.00000037, 0.00001906, -0.00594749, 149472.67411175, 0.16047689, -0.12534081],
+);
+#[rustfmt::skip]
+const VENUS: Elements = (
+    [0.72333566, 0.00677672, 3.39467605, 181.97909950, 131.60246718, 76.67984255],
+    [0.00000390, -0.00004107, -0.00078890, 58517.81538729, 0.00268329, -0.27769418],
+);
+#[rustfmt::skip]
+const EARTH_MOON: Elements = (
+    [1.00000261, 0.01671123, -0.00001531, 100.46457166, 102.93768193, 0.0],
+    [0.00000562, -0.00004392, -0.01294668, 35999.37244981, 0.32327364, 0.0],
+);
+#[rustfmt::skip]
+const MARS: Elements = (
+    [1.52371034, 0.09339410, 1.84969142, -4.55343205, -23.94362959, 49.55953891],
+    [0.00001847, 0.00007882, -0.00813131, 19140.30268499, 0.44441088, -0.29257343],
+);
+#[rustfmt::skip]
+const JUPITER: Elements = (
+    [5.20288700, 0.04838624, 1.30439695, 34.39644051, 14.72847983, 100.47390909],
+    [-0.00011607, -0.00013253, -0.00183714, 3034.74612775, 0.21252668, 0.20469106],
+);
+#[rustfmt::skip]
+const SATURN: Elements = (
+    [9.53667594, 0.05386179, 2.48599187, 49.95424423, 92.59887831, 113.66242448],
+    [-0.00125060, -0.00050991, 0.00193609, 1222.49362201, -0.41897216, -0.28867794],
+);
+#[rustfmt::skip]
+const URANUS: Elements = (
+    [19.18916464, 0.04725744, 0.77263783, 313.23810451, 170.95427630, 74.01692503],
+    [-0.00196176, -0.00004397, -0.00242939, 428.48202785, 0.40805281, 0.04240589],
+);
+#[rustfmt::skip]
+const NEPTUNE: Elements = (
+    [30.06992276, 0.00859048, 1.77004347, -55.12002969, 44.96476227, 131.78422574],
+    [0.00026291, 0.00005105, 0.00035372, 218.45945325, -0.32241464, -0.00508664],
+);
+
+impl FromStr for Planet {
+    type Err = Error;
+
+    /// A planet's name, in any case.
+    fn from_str(s: &str) -> Result<Self> {
+        Planet::ALL
+            .iter()
+            .find(|planet| planet.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| Error::Parse(format!("unknown planet {:?}", s)))
+    }
+}
+
+/// Solve Kepler's equation `E - e sin E = M` for the eccentric anomaly, in
+/// radians, of an elliptical orbit.  Newton's method usually converges in a
+/// few steps; for orbits so eccentric that it doesn't, this falls back to
+/// bisection, which always does.
+///
+/// # Panics
+///
+/// If the eccentricity isn't from 0 up to 1.  The planets' own elements
+/// always are.
+pub fn solve_kepler(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    assert!(
+        (0. ..1.).contains(&eccentricity),
+        "eccentricity {} is not elliptical",
+        eccentricity
+    );
+    let e = eccentricity;
+    let m = (mean_anomaly + PI).rem_euclid(2. * PI) - PI;
+    let turns = mean_anomaly - m;
+
+    let mut ecc = m + 0.85 * e * m.sin().signum();
+    for _ in 0..50 {
+        let delta = (ecc - e * ecc.sin() - m) / (1. - e * ecc.cos());
+        ecc -= delta;
+        if delta.abs() < 1e-14 {
+            return ecc + turns;
+        }
+    }
+
+    // Meeus's third method: E - e sin E increases with E
+    let (mut low, mut high) = (-PI, PI);
+    for _ in 0..64 {
+        let middle = (low + high) / 2.;
+        match middle - e * middle.sin() < m {
+            true => low = middle,
+            false => high = middle,
+        }
+    }
+
+    (low + high) / 2. + turns
+}
+
+/// Heliocentric position of the Earth.  The mean elements are those of the
+/// Earth–Moon barycentre.
+pub fn earth(jd: JulianDay, theory: Theory) -> Heliocentric {
+    match theory {
+        Theory::Kepler => from_elements(&EARTH_MOON, jd),
+        Theory::Vsop87 => from_series(&vsop87::EARTH, jd),
+    }
+}
+
+impl Planet {
+    /// The planets in order from the Sun.
+    pub const ALL: [Planet; 7] = [
+        Planet::Mercury,
+        Planet::Venus,
+        Planet::Mars,
+        Planet::Jupiter,
+        Planet::Saturn,
+        Planet::Uranus,
+        Planet::Neptune,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Planet::Mercury => "Mercury",
+            Planet::Venus => "Venus",
+            Planet::Mars => "Mars",
+            Planet::Jupiter => "Jupiter",
+            Planet::Saturn => "Saturn",
+            Planet::Uranus => "Uranus",
+            Planet::Neptune => "Neptune",
+        }
+    }
+
+    /// Whether the planet orbits inside the Earth's orbit, so it is never
+    /// in opposition.
+    pub fn is_inferior(&self) -> bool {
+        matches!(self, Planet::Mercury | Planet::Venus)
+    }
+
+    pub fn heliocentric(&self, jd: JulianDay, theory: Theory) -> Heliocentric {
+        match theory {
+            Theory::Kepler => from_elements(self.elements(), jd),
+            Theory::Vsop87 => from_series(self.series(), jd),
+        }
+    }
+
+    /// Apparent position from the centre of the Earth.
+    pub fn geocentric(&self, jd: JulianDay, theory: Theory) -> PlanetPosition {
+        apparent(
+            jd,
+            theory,
+            |jd| self.heliocentric(jd, theory),
+            |radius, distance, phase_angle| self.magnitude(radius, distance, phase_angle),
+        )
+    }
+
+    /// The first conjunction or opposition after a moment, or `None` for an
+    /// opposition of Mercury or Venus.  Conjunctions of Mercury and Venus
+    /// alternate between inferior and superior.
+    pub fn next(
+        &self,
+        configuration: Configuration,
+        after: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let target = match configuration {
+            Configuration::Conjunction => 0.,
+            Configuration::Opposition if self.is_inferior() => return None,
+            Configuration::Opposition => 180.,
+        };
+        // Longitude of the planet less that of the Sun, from -180° to 180°
+        let offset = |jd: JulianDay| {
+            let planet = self.geocentric(jd, Theory::Vsop87).ecliptic.lon_degrees();
+            (planet - sun_longitude(jd) - target + 180.).rem_euclid(360.) - 180.
+        };
+
+        // Step a day at a time to bracket the moment, then bisect
+        let start = JulianDate::<Tt>::from_datetime(after).jd;
+        let mut previous = (start, offset(start));
+        for day in 1..=1000 {
+            let jd = start + day as f64;
+            let current = (jd, offset(jd));
+            let crossed = previous.1.signum() != current.1.signum()
+                && previous.1.abs() < 90.
+                && current.1.abs() < 90.;
+
+            if crossed {
+                let (mut low, mut high) = (previous, current);
+                while high.0 - low.0 > 1e-6 {
+                    let middle = low.0 + (high.0 - low.0) / 2.;
+                    let value = offset(middle);
+                    match value.signum() == low.1.signum() {
+                        true => low = (middle, value),
+                        false => high = (middle, value),
+                    }
+                }
+                let tt = JulianDate::<Tt>::new(low.0 + (high.0 - low.0) / 2.);
+                return DateTime::try_from(tt.to::<timescale::Utc>()).ok();
+            }
+            previous = current;
+        }
+
+        None
+    }
+
+    fn elements(&self) -> &'static Elements {
+        match self {
+            Planet::Mercury => &MERCURY,
+            Planet::Venus => &VENUS,
+            Planet::Mars => &MARS,
+            Planet::Jupiter => &JUPITER,
+            Planet::Saturn => &SATURN,
+            Planet::Uranus => &URANUS,
+            Planet::Neptune => &NEPTUNE,
+        }
+    }
+
+    fn series(&self) -> &'static Vsop87 {
+        match self {
+            Planet::Mercury => &vsop87::MERCURY,
+            Planet::Venus => &vsop87::VENUS,
+            Planet::Mars => &vsop87::MARS,
+            Planet::Jupiter => &vsop87::JUPITER,
+            Planet::Saturn => &vsop87::SATURN,
+            Planet::Uranus => &vsop87::URANUS,
+            Planet::Neptune => &vsop87::NEPTUNE,
+        }
+    }
+
+    /// Visual magnitude from the formulae in the 1984 *Astronomical
+    /// Almanac*, for distances in AU and a phase angle in degrees.
+    fn magnitude(&self, radius: f64, distance: f64, phase_angle: f64) -> f64 {
+        let i = phase_angle;
+        let distance_term = 5. * (radius * distance).log10();
+
+        distance_term
+            + match self {
+                Planet::Mercury => {
+                    -0.42 + 0.0380 * i - 0.000_273 * i.powi(2) + 0.000_002 * i.powi(3)
+                }
+                Planet::Venus => {
+                    -4.40 + 0.0009 * i + 0.000_239 * i.powi(2) - 0.000_000_65 * i.powi(3)
+                }
+                Planet::Mars => -1.52 + 0.016 * i,
+                Planet::Jupiter => -9.40 + 0.005 * i,
+                Planet::Saturn => -8.88,
+                Planet::Uranus => -7.19,
+                Planet::Neptune => -6.87,
+            }
+    }
+}
+
+/// Apparent position from the centre of the Earth of any body going round
+/// the Sun, with its visual magnitude from the distances from the Sun and
+/// the Earth in AU and the phase angle in degrees.  The body is taken where
+/// it was when its light left, and the direction to it from where the Earth
+/// was then, which allows for aberration.
+pub(crate) fn apparent(
+    jd: JulianDay,
+    theory: Theory,
+    heliocentric: impl Fn(JulianDay) -> Heliocentric,
+    magnitude: impl Fn(f64, f64, f64) -> f64,
+) -> PlanetPosition {
+    let earth_now = earth(jd, theory);
+    let mut planet = heliocentric(jd);
+    let mut distance = norm(difference(&planet, &earth_now));
+    for _ in 0..3 {
+        planet = heliocentric(jd - LIGHT_TIME * distance);
+        distance = norm(difference(&planet, &earth_now));
+    }
+
+    let earth_then = earth(jd - LIGHT_TIME * distance, theory);
+    let (x, y, z) = difference(&planet, &earth_then);
+    let (nutation_lon, nutation_obl) = nutation(jd);
+    let ecliptic = Ecliptic::new(
+        degrees((y.atan2(x).to_degrees() + nutation_lon.to_degrees().value).rem_euclid(360.)),
+        degrees(z.atan2(x.hypot(y)).to_degrees()),
+    );
+
+    let (r, big_r) = (planet.radius, earth_now.radius);
+    let phase_angle = ((r.powi(2) + distance.powi(2) - big_r.powi(2)) / (2. * r * distance))
+        .clamp(-1., 1.)
+        .acos()
+        .to_degrees();
+    let elongation = ((big_r.powi(2) + distance.powi(2) - r.powi(2)) / (2. * big_r * distance))
+        .clamp(-1., 1.)
+        .acos()
+        .to_degrees();
+
+    PlanetPosition {
+        ecliptic,
+        equatorial: ecliptic.to_equatorial(obliquity(jd) + nutation_obl),
+        distance,
+        radius: r,
+        elongation,
+        phase_angle,
+        magnitude: magnitude(r, distance, phase_angle),
+    }
+}
+
+/// Apparent geocentric longitude of the Sun, consistent with the planets.
+fn sun_longitude(jd: JulianDay) -> f64 {
+    let earth = earth(jd, Theory::Vsop87);
+    let aberration = -20.4898 / 3600. / earth.radius;
+    (earth.lon + 180. + nutation(jd).0.to_degrees().value + aberration).rem_euclid(360.)
+}
+
+fn from_series(series: &Vsop87, jd: JulianDay) -> Heliocentric {
+    let (lon, lat, radius) = series.heliocentric(jd);
+    Heliocentric { lon, lat, radius }
+}
+
+fn from_elements(elements: &Elements, jd: JulianDay) -> Heliocentric {
+    let t = (jd - JulianDay::J2000) / 36_525.;
+    let (base, rate) = elements;
+    let element = |i: usize| base[i] + rate[i] * t;
+    let (a, e) = (element(0), element(1));
+    let (incl, mean_lon, perihelion, node) = (
+        element(2).to_radians(),
+        element(3),
+        element(4),
+        element(5).to_radians(),
+    );
+
+    let argument = perihelion.to_radians() - node;
+    let ecc = solve_kepler((mean_lon - perihelion).to_radians(), e);
+    let x_orbit = a * (ecc.cos() - e);
+    let y_orbit = a * (1. - e.powi(2)).sqrt() * ecc.sin();
+
+    in_space((x_orbit, y_orbit), argument, node, incl, t)
+}
+
+/// Heliocentric coordinates of the equinox of the date for a place in the
+/// plane of an orbit, with `x` towards perihelion, given the argument of
+/// perihelion, the ascending node and the inclination in radians, referred
+/// to the ecliptic and equinox of J2000.0, and the time from J2000.0 in
+/// Julian centuries.
+pub(crate) fn in_space(
+    (x_orbit, y_orbit): (f64, f64),
+    argument: f64,
+    node: f64,
+    incl: f64,
+    t: f64,
+) -> Heliocentric {
+    let (cw, sw, cn, sn, ci, si) = (
+        argument.cos(),
+        argument.sin(),
+        node.cos(),
+        node.sin(),
+        incl.cos(),
+        incl.sin(),
+    );
+    let x = (cw * cn - sw * sn * ci) * x_orbit + (-sw * cn - cw * sn * ci) * y_orbit;
+    let y = (cw * sn + sw * cn * ci) * x_orbit + (-sw * sn + cw * cn * ci) * y_orbit;
+    let z = sw * si * x_orbit + cw * si * y_orbit;
+
+    // Precession in longitude from J2000.0 to the equinox of the date
+    let precession = 1.396_971 * t + 0.000_308_6 * t.powi(2);
+
+    Heliocentric {
+        lon: (y.atan2(x).to_degrees() + precession).rem_euclid(360.),
+        lat: z.atan2(x.hypot(y)).to_degrees(),
+        radius: (x.powi(2) + y.powi(2) + z.powi(2)).sqrt(),
+    }
+}
+
+/// Rectangular ecliptic coordinates of `a` relative to `b`.
+fn difference(a: &Heliocentric, b: &Heliocentric) -> (f64, f64, f64) {
+    let rectangular = |p: &Heliocentric| {
+        let (lon, lat) = (p.lon.to_radians(), p.lat.to_radians());
+        (
+            p.radius * lat.cos() * lon.cos(),
+            p.radius * lat.cos() * lon.sin(),
+            p.radius * lat.sin(),
+        )
+    };
+    let (a, b) = (rectangular(a), rectangular(b));
+
+    (a.0 - b.0, a.1 - b.1, a.2 - b.2)
+}
+
+fn norm((x, y, z): (f64, f64, f64)) -> f64 {
+    (x.powi(2) + y.powi(2) + z.powi(2)).sqrt()
+}
@@ -0,0 +1,300 @@
+//! Rising, transit and setting times, after Meeus, *Astronomical Algorithms*,
+//! chapter 15.  Each event is refined by iterating on the body's position and
+//! the local sidereal time until it moves by less than a second.
+
+use std::convert::TryFrom;
+
+use chrono::{Date, DateTime, Duration, Offset, TimeZone, Utc};
+
+use crate::{
+    coords::{local_sidereal_time, Equatorial, HourAngleCoords},
+    degrees, hours,
+    moon::LunarPosition,
+    observer::Observer,
+    planets::{Planet, Theory},
+    sun::SolarPosition,
+    timescale::{self, JulianDate, Tt},
+    Angle, JulianDay,
+};
+
+/// Atmospheric refraction at the horizon, in degrees.
+pub const REFRACTION: f64 = 34. / 60.;
+
+/// Length of a sidereal day in days.
+const SIDEREAL_DAY: f64 = 0.997_269_566;
+
+/// Stop iterating when an event moves by less than this many days.
+const TOLERANCE: f64 = 1e-5;
+
+const MAX_ITERATIONS: usize = 20;
+
+/// Something in the sky that rises and sets.
+pub trait Body {
+    /// Apparent geocentric right ascension and declination.
+    fn equatorial(&self, tt: JulianDate<Tt>) -> Equatorial;
+
+    /// Geometric altitude of the body's centre when it appears to an
+    /// observer to rise or set.
+    fn standard_altitude(&self, tt: JulianDate<Tt>, observer: &Observer) -> Angle;
+}
+
+/// The Sun, for sunrise and sunset or for the start and end of twilight.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sun {
+    /// The upper limb on the horizon, allowing for refraction.
+    Disc,
+    /// The centre 6° below the horizon.
+    CivilTwilight,
+    /// The centre 12° below the horizon.
+    NauticalTwilight,
+    /// The centre 18° below the horizon.
+    AstronomicalTwilight,
+}
+
+/// The Moon, whose upper limb touches the horizon at rising and setting.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Moon;
+
+/// A star, or anything else fixed on the celestial sphere.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Star(pub Equatorial);
+
+/// The events of one local day.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RiseTransitSet<Tz: TimeZone> {
+    /// The body crosses the horizon.  The Moon sometimes skips rising or
+    /// setting on a day, so either may be missing.
+    Crosses {
+        rise: Option<DateTime<Tz>>,
+        transit: Option<DateTime<Tz>>,
+        set: Option<DateTime<Tz>>,
+    },
+    /// The body stays above the horizon all day.
+    Circumpolar { transit: Option<DateTime<Tz>> },
+    /// The body stays below the horizon all day.
+    NeverRises { transit: Option<DateTime<Tz>> },
+}
+
+impl Body for Sun {
+    fn equatorial(&self, tt: JulianDate<Tt>) -> Equatorial {
+        SolarPosition::from_julian_day(tt.jd).equatorial
+    }
+
+    /// The disc rises over the sea horizon, which an elevated observer sees
+    /// below the horizontal, while twilight is reckoned from the true
+    /// horizon.
+    fn standard_altitude(&self, tt: JulianDate<Tt>, observer: &Observer) -> Angle {
+        degrees(match self {
+            Sun::Disc => {
+                let distance = SolarPosition::from_julian_day(tt.jd).distance;
+                let semi_diameter = 0.266_56 / distance;
+                let parallax = 0.002_443 / distance;
+                parallax - REFRACTION - semi_diameter - observer.dip()
+            }
+            Sun::CivilTwilight => -6.,
+            Sun::NauticalTwilight => -12.,
+            Sun::AstronomicalTwilight => -18.,
+        })
+    }
+}
+
+impl Body for Moon {
+    fn equatorial(&self, tt: JulianDate<Tt>) -> Equatorial {
+        LunarPosition::from_julian_day(tt.jd).equatorial
+    }
+
+    fn standard_altitude(&self, tt: JulianDate<Tt>, observer: &Observer) -> Angle {
+        let moon = LunarPosition::from_julian_day(tt.jd);
+        degrees(moon.parallax - REFRACTION - moon.semi_diameter() - observer.dip())
+    }
+}
+
+impl Body for Star {
+    fn equatorial(&self, _tt: JulianDate<Tt>) -> Equatorial {
+        self.0
+    }
+
+    fn standard_altitude(&self, _tt: JulianDate<Tt>, observer: &Observer) -> Angle {
+        degrees(-REFRACTION - observer.dip())
+    }
+}
+
+/// Planets are points of light, rising and setting like stars.
+impl Body for Planet {
+    fn equatorial(&self, tt: JulianDate<Tt>) -> Equatorial {
+        self.geocentric(tt.jd, Theory::Vsop87).equatorial
+    }
+
+    fn standard_altitude(&self, _tt: JulianDate<Tt>, observer: &Observer) -> Angle {
+        degrees(-REFRACTION - observer.dip())
+    }
+}
+
+impl<Tz: TimeZone> RiseTransitSet<Tz> {
+    pub fn rise(&self) -> Option<&DateTime<Tz>> {
+        match self {
+            RiseTransitSet::Crosses { rise, .. } => rise.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn transit(&self) -> Option<&DateTime<Tz>> {
+        match self {
+            RiseTransitSet::Crosses { transit, .. }
+            | RiseTransitSet::Circumpolar { transit }
+            | RiseTransitSet::NeverRises { transit } => transit.as_ref(),
+        }
+    }
+
+    pub fn set(&self) -> Option<&DateTime<Tz>> {
+        match self {
+            RiseTransitSet::Crosses { set, .. } => set.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Rising, transit and setting of a body on a local date, as seen by an
+/// observer.  Whether the body crosses the horizon at all is decided by its
+/// position at local noon.
+pub fn rise_transit_set<B: Body, Tz: TimeZone>(
+    body: &B,
+    observer: &Observer,
+    date: Date<Tz>,
+) -> RiseTransitSet<Tz> {
+    let tz = date.timezone();
+    let start = JulianDay::from(midnight(&date));
+    let end = JulianDay::from(midnight(&date.succ()));
+    let local = |jd: Option<JulianDay>| {
+        jd.and_then(|x| DateTime::<Utc>::try_from(x).ok())
+            .map(|x| x.with_timezone(&tz))
+    };
+
+    // Events are found on UTC, but the body's position wants TT
+    let tt = |jd: JulianDay| JulianDate::<timescale::Utc>::new(jd).to::<Tt>();
+
+    // Where the body is at noon gives the first guesses
+    let noon = start + (end - start) / 2.;
+    let position = body.equatorial(tt(noon));
+    let transit_guess = start
+        + hour_angle(position, start, observer)
+            .map_or(0., |x| (-x).rem_euclid(24.) / 24. * SIDEREAL_DAY);
+    let transit = local(find_event(transit_guess, start, end, |jd| {
+        hour_angle(body.equatorial(tt(jd)), jd, observer).map(|x| -x / 24. * SIDEREAL_DAY)
+    }));
+
+    let semi_arc = match semi_diurnal_arc(
+        position,
+        body.standard_altitude(tt(noon), observer),
+        observer,
+    ) {
+        Ok(semi_arc) => semi_arc,
+        Err(Always::Above) => return RiseTransitSet::Circumpolar { transit },
+        Err(Always::Below) => return RiseTransitSet::NeverRises { transit },
+    };
+    let crossing = |jd: JulianDay| {
+        let (position, h0) = (
+            body.equatorial(tt(jd)),
+            body.standard_altitude(tt(jd), observer),
+        );
+        let coords = HourAngleCoords {
+            hour_angle: hours(hour_angle(position, jd, observer)?),
+            dec: position.dec,
+        };
+        let altitude = coords.to_horizon(degrees(observer.latitude)).altitude;
+        let rate = 360.
+            * position.dec.to_radians().value.cos()
+            * observer.latitude.to_radians().cos()
+            * coords.hour_angle.to_radians().value.sin();
+        match rate.abs() > 1e-6 {
+            true => Some((altitude - h0).to_degrees().value / rate),
+            false => None,
+        }
+    };
+    let offset = semi_arc / 360. * SIDEREAL_DAY;
+
+    RiseTransitSet::Crosses {
+        rise: local(find_event(transit_guess - offset, start, end, crossing)),
+        transit,
+        set: local(find_event(transit_guess + offset, start, end, crossing)),
+    }
+}
+
+enum Always {
+    Above,
+    Below,
+}
+
+/// Hour angle at rising or setting, in degrees.
+fn semi_diurnal_arc(position: Equatorial, h0: Angle, observer: &Observer) -> Result<f64, Always> {
+    let (dec, lat) = (
+        position.dec.to_radians().value,
+        observer.latitude.to_radians(),
+    );
+    let cos_h0 = (h0.to_radians().value.sin() - lat.sin() * dec.sin()) / (lat.cos() * dec.cos());
+
+    if cos_h0 < -1. {
+        Err(Always::Above)
+    } else if cos_h0 > 1. {
+        Err(Always::Below)
+    } else {
+        Ok(cos_h0.acos().to_degrees())
+    }
+}
+
+/// Hour angle in hours, between -12 and 12, iterating on sidereal time.
+fn hour_angle(position: Equatorial, jd: JulianDay, observer: &Observer) -> Option<f64> {
+    let dt = DateTime::<Utc>::try_from(jd).ok()?;
+    let lst = local_sidereal_time(dt, observer).to_hours().value;
+    Some((lst - position.ra_hours() + 12.).rem_euclid(24.) - 12.)
+}
+
+/// Refine a guess at an event that may be a sidereal day early or late,
+/// returning the first that converges within `[start, end)`.
+fn find_event<F>(
+    guess: JulianDay,
+    start: JulianDay,
+    end: JulianDay,
+    correction: F,
+) -> Option<JulianDay>
+where
+    F: Fn(JulianDay) -> Option<f64>,
+{
+    let guess = start + (guess - start).rem_euclid(SIDEREAL_DAY);
+
+    [guess, guess + SIDEREAL_DAY, guess - SIDEREAL_DAY]
+        .iter()
+        .filter_map(|x| refine(*x, &correction))
+        .find(|x| (start..end).contains(x))
+}
+
+fn refine<F>(mut jd: JulianDay, correction: F) -> Option<JulianDay>
+where
+    F: Fn(JulianDay) -> Option<f64>,
+{
+    for _ in 0..MAX_ITERATIONS {
+        let delta = correction(jd)?;
+        jd = jd + delta;
+        if delta.abs() < TOLERANCE {
+            return Some(jd);
+        }
+    }
+
+    None
+}
+
+/// The first moment of a local date: the earlier midnight where the clocks
+/// go back over it, and the moment they jump where they skip it.
+pub(crate) fn midnight<Tz: TimeZone>(date: &Date<Tz>) -> DateTime<Tz> {
+    let tz = date.timezone();
+    let naive = date.naive_local().and_hms(0, 0, 0);
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .unwrap_or_else(|| {
+            // Midnight on the clocks of the evening before is when they jump
+            let evening = tz.offset_from_utc_datetime(&(naive - Duration::hours(6)));
+            tz.from_utc_datetime(
+                &(naive - Duration::seconds(evening.fix().local_minus_utc() as i64)),
+            )
+        })
+}
@@ -0,0 +1,1418 @@
+//! Earth satellites from NORAD two-line element sets, propagated with SGP4
+//! and, for periods over 225 minutes, SDP4, after Vallado, Crawford, Hujsak
+//! and Kelso, *Revisiting Spacetrack Report #3* (AIAA 2006-6753).  The names
+//! of the intermediate quantities follow Vallado's code, so that they can be
+//! checked against it.
+//!
+//! Propagation gives a position in the TEME frame (true equator, mean
+//! equinox of date), which the mean sidereal time turns into a place in the
+//! sky for an observer.  Element sets are only good for a few days either
+//! side of their epoch, and UTC is taken as UT1 throughout.
+
+use std::{
+    f64::consts::{PI, TAU},
+    fs,
+    path::Path,
+    str::FromStr,
+};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::{
+    coords::Horizon,
+    degrees,
+    error::{Error, Result},
+    observer::Observer,
+    sidereal::SiderealTime,
+    sun::SolarPosition,
+    Angle, JulianDay,
+};
+
+/// WGS-72 constants, which the element sets are fitted with.
+const EARTH_RADIUS: f64 = 6378.135;
+const XKE: f64 = 0.074_366_916_133_173_4;
+const J2: f64 = 0.001_082_616;
+const J3: f64 = -0.000_002_538_81;
+const J4: f64 = -0.000_001_655_97;
+const J3OJ2: f64 = J3 / J2;
+const X2O3: f64 = 2. / 3.;
+
+/// The Sun's altitude in degrees below which the sky is dark enough to see
+/// a satellite, the end of civil twilight.
+pub const DARKNESS: f64 = -6.;
+
+/// A two-line element set.  Angles are in degrees and the mean motion in
+/// revolutions a day, as written.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tle {
+    /// The name from a title line above the elements, if there was one.
+    pub name: Option<String>,
+    pub catalog_number: u32,
+    /// Epoch of the elements on UTC.
+    pub epoch: JulianDay,
+    /// Half the first derivative of mean motion, in revolutions a day².
+    pub ndot: f64,
+    /// A sixth of the second derivative of mean motion, in revolutions a
+    /// day³.
+    pub nddot: f64,
+    /// Drag term in inverse Earth radii.
+    pub bstar: f64,
+    pub inclination: f64,
+    pub right_ascension: f64,
+    pub eccentricity: f64,
+    pub argument_of_perigee: f64,
+    pub mean_anomaly: f64,
+    pub mean_motion: f64,
+}
+
+/// Position in km and velocity in km/s in the TEME frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Teme {
+    pub position: [f64; 3],
+    pub velocity: [f64; 3],
+}
+
+/// Where a satellite is seen from, with its distance in km.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Topocentric {
+    pub horizon: Horizon,
+    pub range: f64,
+}
+
+/// A moment in a pass and where the satellite is then.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PassEvent<Tz: TimeZone> {
+    pub time: DateTime<Tz>,
+    pub horizon: Horizon,
+}
+
+/// A pass of a satellite over an observer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pass<Tz: TimeZone> {
+    pub rise: PassEvent<Tz>,
+    pub culmination: PassEvent<Tz>,
+    pub set: PassEvent<Tz>,
+    /// Whether the satellite is sunlit at some point while the observer is
+    /// in darkness, so that it can be seen.
+    pub visible: bool,
+}
+
+/// A satellite ready to propagate, from its element set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sgp4 {
+    tle: Tle,
+    // Mean elements at epoch, in radians and radians a minute
+    ecco: f64,
+    inclo: f64,
+    nodeo: f64,
+    argpo: f64,
+    mo: f64,
+    no: f64,
+    bstar: f64,
+    // Secular and drag coefficients
+    isimp: bool,
+    aycof: f64,
+    con41: f64,
+    cc1: f64,
+    cc4: f64,
+    cc5: f64,
+    d2: f64,
+    d3: f64,
+    d4: f64,
+    delmo: f64,
+    eta: f64,
+    argpdot: f64,
+    omgcof: f64,
+    sinmao: f64,
+    t2cof: f64,
+    t3cof: f64,
+    t4cof: f64,
+    t5cof: f64,
+    x1mth2: f64,
+    x7thm1: f64,
+    mdot: f64,
+    nodedot: f64,
+    xlcof: f64,
+    xmcof: f64,
+    nodecf: f64,
+    deep_space: Option<Box<DeepSpace>>,
+}
+
+/// Lunar and solar perturbations and resonance terms for periods over 225
+/// minutes.
+#[derive(Clone, Debug, PartialEq)]
+struct DeepSpace {
+    gsto: f64,
+    // Lunar-solar periodics
+    e3: f64,
+    ee2: f64,
+    se2: f64,
+    se3: f64,
+    sgh2: f64,
+    sgh3: f64,
+    sgh4: f64,
+    sh2: f64,
+    sh3: f64,
+    si2: f64,
+    si3: f64,
+    sl2: f64,
+    sl3: f64,
+    sl4: f64,
+    xgh2: f64,
+    xgh3: f64,
+    xgh4: f64,
+    xh2: f64,
+    xh3: f64,
+    xi2: f64,
+    xi3: f64,
+    xl2: f64,
+    xl3: f64,
+    xl4: f64,
+    zmol: f64,
+    zmos: f64,
+    // Secular rates
+    dedt: f64,
+    didt: f64,
+    dmdt: f64,
+    dnodt: f64,
+    domdt: f64,
+    resonance: Resonance,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Resonance {
+    None,
+    /// One-day orbits, geosynchronous.
+    Synchronous {
+        del1: f64,
+        del2: f64,
+        del3: f64,
+        xfact: f64,
+        xlamo: f64,
+    },
+    /// Half-day orbits of high eccentricity, Molniya.
+    HalfDay {
+        d: [f64; 10],
+        xfact: f64,
+        xlamo: f64,
+    },
+}
+
+/// Intermediate values from Vallado's `dscom`, shared by the deep space
+/// initialisation.
+struct Dscom {
+    day: f64,
+    emsq: f64,
+    gam: f64,
+    sinim: f64,
+    cosim: f64,
+    s: [f64; 7],
+    ss: [f64; 7],
+    z: [[f64; 3]; 4],
+    sz: [[f64; 3]; 4],
+}
+
+impl<Tz: TimeZone> Pass<Tz> {
+    /// The highest altitude reached.
+    pub fn max_elevation(&self) -> Angle {
+        self.culmination.horizon.altitude
+    }
+}
+
+impl Tle {
+    /// Read every element set in a file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Vec<Self>> {
+        parse_tles(&fs::read_to_string(path)?)
+    }
+}
+
+impl FromStr for Tle {
+    type Err = Error;
+
+    /// Parse a single element set, with or without a title line.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut tles = parse_tles(s)?;
+        match tles.len() {
+            1 => Ok(tles.remove(0)),
+            n => Err(Error::Parse(format!(
+                "expected one element set, found {}",
+                n
+            ))),
+        }
+    }
+}
+
+/// Parse any number of element sets, each with or without a title line.
+/// Checksums are not checked.
+pub fn parse_tles(s: &str) -> Result<Vec<Tle>> {
+    let lines: Vec<&str> = s
+        .lines()
+        .map(str::trim_end)
+        .filter(|l| !l.is_empty())
+        .collect();
+    let mut tles = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let name = match lines[i].starts_with("1 ") {
+            true => None,
+            false => {
+                i += 1;
+                Some(lines[i - 1].trim_start_matches("0 ").trim().to_string())
+            }
+        };
+        match (lines.get(i), lines.get(i + 1)) {
+            (Some(line1), Some(line2)) if line1.starts_with("1 ") && line2.starts_with("2 ") => {
+                tles.push(parse_tle(name, line1, line2)?);
+                i += 2;
+            }
+            _ => {
+                return Err(Error::Parse(format!(
+                    "expected two element lines after line {}",
+                    i
+                )))
+            }
+        }
+    }
+
+    Ok(tles)
+}
+
+fn parse_tle(name: Option<String>, line1: &str, line2: &str) -> Result<Tle> {
+    let number = |line: &str, from: usize, to: usize, what: &str| -> Result<f64> {
+        let text = field(line, from, to, what)?;
+        text.parse()
+            .map_err(|_| Error::Parse(format!("bad {}: {:?}", what, text)))
+    };
+
+    let catalog_number = number(line1, 3, 7, "catalog number")? as u32;
+    let year = number(line1, 19, 20, "epoch year")? as i32;
+    let year = if year < 57 { 2000 + year } else { 1900 + year };
+    let day = number(line1, 21, 32, "epoch day")?;
+    let epoch = JulianDay::from(Utc.ymd(year, 1, 1)) + (day - 1.);
+
+    Ok(Tle {
+        name,
+        catalog_number,
+        epoch,
+        ndot: number(line1, 34, 43, "first derivative")?,
+        nddot: exponential(field(line1, 45, 52, "second derivative")?)?,
+        bstar: exponential(field(line1, 54, 61, "drag term")?)?,
+        inclination: number(line2, 9, 16, "inclination")?,
+        right_ascension: number(line2, 18, 25, "right ascension")?,
+        eccentricity: number(line2, 27, 33, "eccentricity")? / 1e7,
+        argument_of_perigee: number(line2, 35, 42, "argument of perigee")?,
+        mean_anomaly: number(line2, 44, 51, "mean anomaly")?,
+        mean_motion: number(line2, 53, 63, "mean motion")?,
+    })
+}
+
+/// Columns of a line, counted from one as the format describes them.
+fn field<'a>(line: &'a str, from: usize, to: usize, what: &str) -> Result<&'a str> {
+    line.get(from - 1..to.min(line.len()))
+        .map(str::trim)
+        .ok_or_else(|| Error::Parse(format!("element set too short for {}", what)))
+}
+
+/// A number written with an assumed leading decimal point and a power of
+/// ten, such as `-11606-4` for -0.11606e-4.
+fn exponential(text: &str) -> Result<f64> {
+    let bad = || Error::Parse(format!("bad exponential field: {:?}", text));
+    let split = text.rfind(['-', '+']).filter(|&i| i > 0);
+    let (mantissa, exponent) = match split {
+        Some(i) => (&text[..i], &text[i..]),
+        None => (text, "0"),
+    };
+    let (sign, digits) = match mantissa.strip_prefix('-') {
+        Some(digits) => (-1., digits),
+        None => (1., mantissa.trim_start_matches('+')),
+    };
+    if digits.is_empty() {
+        return Ok(0.);
+    }
+    let mantissa: f64 = format!("0.{}", digits).parse().map_err(|_| bad())?;
+    let exponent: i32 = exponent.parse().map_err(|_| bad())?;
+
+    Ok(sign * mantissa * 10_f64.powi(exponent))
+}
+
+impl Sgp4 {
+    /// Initialise the propagator from an element set, as Vallado's
+    /// `sgp4init` does in its improved mode.
+    #[allow(clippy::many_single_char_names)]
+    pub fn new(tle: &Tle) -> Result<Self> {
+        let minutes_per_day = 1440.;
+        let ecco = tle.eccentricity;
+        let inclo = tle.inclination.to_radians();
+        let nodeo = tle.right_ascension.to_radians();
+        let argpo = tle.argument_of_perigee.to_radians();
+        let mo = tle.mean_anomaly.to_radians();
+        let no_kozai = tle.mean_motion * TAU / minutes_per_day;
+        let bstar = tle.bstar;
+
+        // initl: recover the Brouwer mean motion from the Kozai one
+        let eccsq = ecco * ecco;
+        let omeosq = 1. - eccsq;
+        let rteosq = omeosq.sqrt();
+        let cosio = inclo.cos();
+        let cosio2 = cosio * cosio;
+        let ak = (XKE / no_kozai).powf(X2O3);
+        let d1 = 0.75 * J2 * (3. * cosio2 - 1.) / (rteosq * omeosq);
+        let del = d1 / (ak * ak);
+        let adel = ak * (1. - del * del - del * (1. / 3. + 134. * del * del / 81.));
+        let del = d1 / (adel * adel);
+        let no = no_kozai / (1. + del);
+        let ao = (XKE / no).powf(X2O3);
+        let sinio = inclo.sin();
+        let po = ao * omeosq;
+        let con42 = 1. - 5. * cosio2;
+        let con41 = -con42 - cosio2 - cosio2;
+        let posq = po * po;
+        let rp = ao * (1. - ecco);
+
+        if !(0. ..1.).contains(&ecco) || no <= 0. {
+            return Err(Error::Propagation(format!(
+                "elements for {} are not an orbit",
+                tle.catalog_number
+            )));
+        }
+
+        let mut ss = 78. / EARTH_RADIUS + 1.;
+        let mut qzms2t = ((120. - 78.) / EARTH_RADIUS).powi(4);
+        let perige = (rp - 1.) * EARTH_RADIUS;
+        if perige < 156. {
+            ss = if perige < 98. { 20. } else { perige - 78. };
+            qzms2t = ((120. - ss) / EARTH_RADIUS).powi(4);
+            ss = ss / EARTH_RADIUS + 1.;
+        }
+
+        let pinvsq = 1. / posq;
+        let tsi = 1. / (ao - ss);
+        let eta = ao * ecco * tsi;
+        let etasq = eta * eta;
+        let eeta = ecco * eta;
+        let psisq = (1. - etasq).abs();
+        let coef = qzms2t * tsi.powi(4);
+        let coef1 = coef / psisq.powf(3.5);
+        let cc2 = coef1
+            * no
+            * (ao * (1. + 1.5 * etasq + eeta * (4. + etasq))
+                + 0.375 * J2 * tsi / psisq * con41 * (8. + 3. * etasq * (8. + etasq)));
+        let cc1 = bstar * cc2;
+        let cc3 = match ecco > 1e-4 {
+            true => -2. * coef * tsi * J3OJ2 * no * sinio / ecco,
+            false => 0.,
+        };
+        let x1mth2 = 1. - cosio2;
+        let cc4 = 2.
+            * no
+            * coef1
+            * ao
+            * omeosq
+            * (eta * (2. + 0.5 * etasq) + ecco * (0.5 + 2. * etasq)
+                - J2 * tsi / (ao * psisq)
+                    * (-3. * con41 * (1. - 2. * eeta + etasq * (1.5 - 0.5 * eeta))
+                        + 0.75 * x1mth2 * (2. * etasq - eeta * (1. + etasq)) * (2. * argpo).cos()));
+        let cc5 = 2. * coef1 * ao * omeosq * (1. + 2.75 * (etasq + eeta) + eeta * etasq);
+        let cosio4 = cosio2 * cosio2;
+        let temp1 = 1.5 * J2 * pinvsq * no;
+        let temp2 = 0.5 * temp1 * J2 * pinvsq;
+        let temp3 = -0.468_75 * J4 * pinvsq * pinvsq * no;
+        let mdot = no
+            + 0.5 * temp1 * rteosq * con41
+            + 0.0625 * temp2 * rteosq * (13. - 78. * cosio2 + 137. * cosio4);
+        let argpdot = -0.5 * temp1 * con42
+            + 0.0625 * temp2 * (7. - 114. * cosio2 + 395. * cosio4)
+            + temp3 * (3. - 36. * cosio2 + 49. * cosio4);
+        let xhdot1 = -temp1 * cosio;
+        let nodedot =
+            xhdot1 + (0.5 * temp2 * (4. - 19. * cosio2) + 2. * temp3 * (3. - 7. * cosio2)) * cosio;
+        let xpidot = argpdot + nodedot;
+        let omgcof = bstar * cc3 * argpo.cos();
+        let xmcof = match ecco > 1e-4 {
+            true => -X2O3 * coef * bstar / eeta,
+            false => 0.,
+        };
+        let nodecf = 3.5 * omeosq * xhdot1 * cc1;
+        let t2cof = 1.5 * cc1;
+        let xlcof = long_period_xlcof(sinio, cosio);
+        let aycof = -0.5 * J3OJ2 * sinio;
+        let delmo = (1. + eta * mo.cos()).powi(3);
+        let sinmao = mo.sin();
+        let x7thm1 = 7. * cosio2 - 1.;
+
+        let mut sgp4 = Sgp4 {
+            tle: tle.clone(),
+            ecco,
+            inclo,
+            nodeo,
+            argpo,
+            mo,
+            no,
+            bstar,
+            isimp: rp < 220. / EARTH_RADIUS + 1.,
+            aycof,
+            con41,
+            cc1,
+            cc4,
+            cc5,
+            d2: 0.,
+            d3: 0.,
+            d4: 0.,
+            delmo,
+            eta,
+            argpdot,
+            omgcof,
+            sinmao,
+            t2cof,
+            t3cof: 0.,
+            t4cof: 0.,
+            t5cof: 0.,
+            x1mth2,
+            x7thm1,
+            mdot,
+            nodedot,
+            xlcof,
+            xmcof,
+            nodecf,
+            deep_space: None,
+        };
+
+        if TAU / no >= 225. {
+            sgp4.isimp = true;
+            let gsto = SiderealTime::mean(tle.epoch).hours() * TAU / 24.;
+            let epoch = tle.epoch - JulianDay::from(2_415_020.);
+            sgp4.deep_space = Some(Box::new(DeepSpace::new(&sgp4, epoch, gsto, xpidot)));
+        }
+
+        if !sgp4.isimp {
+            let cc1sq = cc1 * cc1;
+            let d2 = 4. * ao * tsi * cc1sq;
+            let temp = d2 * tsi * cc1 / 3.;
+            let d3 = (17. * ao + ss) * temp;
+            let d4 = 0.5 * temp * ao * tsi * (221. * ao + 31. * ss) * cc1;
+            sgp4.d2 = d2;
+            sgp4.d3 = d3;
+            sgp4.d4 = d4;
+            sgp4.t3cof = d2 + 2. * cc1sq;
+            sgp4.t4cof = 0.25 * (3. * d3 + cc1 * (12. * d2 + 10. * cc1sq));
+            sgp4.t5cof =
+                0.2 * (3. * d4 + 12. * cc1 * d3 + 6. * d2 * d2 + 15. * cc1sq * (2. * d2 + cc1sq));
+        }
+
+        Ok(sgp4)
+    }
+
+    pub fn tle(&self) -> &Tle {
+        &self.tle
+    }
+
+    /// Position and velocity a number of minutes after the epoch of the
+    /// elements, or an error once the orbit has decayed.
+    #[allow(clippy::many_single_char_names)]
+    pub fn propagate(&self, minutes: f64) -> Result<Teme> {
+        let t = minutes;
+        let fail = |what: &str| {
+            Err(Error::Propagation(format!(
+                "{} for {} at {} minutes",
+                what, self.tle.catalog_number, t
+            )))
+        };
+
+        // Secular gravity and atmospheric drag
+        let xmdf = self.mo + self.mdot * t;
+        let argpdf = self.argpo + self.argpdot * t;
+        let nodedf = self.nodeo + self.nodedot * t;
+        let mut argpm = argpdf;
+        let mut mm = xmdf;
+        let t2 = t * t;
+        let mut nodem = nodedf + self.nodecf * t2;
+        let mut tempa = 1. - self.cc1 * t;
+        let mut tempe = self.bstar * self.cc4 * t;
+        let mut templ = self.t2cof * t2;
+
+        if !self.isimp {
+            let delomg = self.omgcof * t;
+            let delm = self.xmcof * ((1. + self.eta * xmdf.cos()).powi(3) - self.delmo);
+            let temp = delomg + delm;
+            mm = xmdf + temp;
+            argpm = argpdf - temp;
+            let t3 = t2 * t;
+            let t4 = t3 * t;
+            tempa = tempa - self.d2 * t2 - self.d3 * t3 - self.d4 * t4;
+            tempe += self.bstar * self.cc5 * (mm.sin() - self.sinmao);
+            templ += self.t3cof * t3 + t4 * (self.t4cof + t * self.t5cof);
+        }
+
+        let mut nm = self.no;
+        let mut em = self.ecco;
+        let mut inclm = self.inclo;
+        if let Some(deep) = &self.deep_space {
+            deep.secular(
+                self, t, &mut em, &mut argpm, &mut inclm, &mut mm, &mut nodem, &mut nm,
+            );
+        }
+
+        if nm <= 0. {
+            return fail("mean motion gone");
+        }
+        let am = (XKE / nm).powf(X2O3) * tempa * tempa;
+        nm = XKE / am.powf(1.5);
+        em -= tempe;
+        if !(-0.001..1.).contains(&em) {
+            return fail("eccentricity out of range");
+        }
+        em = em.max(1e-6);
+        mm += self.no * templ;
+        let xlm = mm + argpm + nodem;
+        nodem %= TAU;
+        argpm %= TAU;
+        let xlm = xlm % TAU;
+        mm = (xlm - argpm - nodem) % TAU;
+
+        // Lunar-solar periodics
+        let (mut ep, mut xincp, mut argpp, mut nodep, mut mp) = (em, inclm, argpm, nodem, mm);
+        let (mut sinip, mut cosip) = (inclm.sin(), inclm.cos());
+        let (mut aycof, mut xlcof) = (self.aycof, self.xlcof);
+        let (mut con41, mut x1mth2, mut x7thm1) = (self.con41, self.x1mth2, self.x7thm1);
+        if let Some(deep) = &self.deep_space {
+            deep.periodics(t, &mut ep, &mut xincp, &mut nodep, &mut argpp, &mut mp);
+            if xincp < 0. {
+                xincp = -xincp;
+                nodep += PI;
+                argpp -= PI;
+            }
+            if !(0. ..=1.).contains(&ep) {
+                return fail("eccentricity out of range");
+            }
+
+            sinip = xincp.sin();
+            cosip = xincp.cos();
+            aycof = -0.5 * J3OJ2 * sinip;
+            xlcof = long_period_xlcof(sinip, cosip);
+            let cosisq = cosip * cosip;
+            con41 = 3. * cosisq - 1.;
+            x1mth2 = 1. - cosisq;
+            x7thm1 = 7. * cosisq - 1.;
+        }
+
+        // Long period periodics
+        let axnl = ep * argpp.cos();
+        let temp = 1. / (am * (1. - ep * ep));
+        let aynl = ep * argpp.sin() + temp * aycof;
+        let xl = mp + argpp + nodep + temp * xlcof * axnl;
+
+        // Kepler's equation
+        let u = (xl - nodep) % TAU;
+        let mut eo1 = u;
+        let (mut sineo1, mut coseo1) = (eo1.sin(), eo1.cos());
+        for _ in 0..10 {
+            sineo1 = eo1.sin();
+            coseo1 = eo1.cos();
+            let step =
+                (u - aynl * coseo1 + axnl * sineo1 - eo1) / (1. - coseo1 * axnl - sineo1 * aynl);
+            eo1 += step.clamp(-0.95, 0.95);
+            if step.abs() < 1e-12 {
+                break;
+            }
+        }
+
+        // Short period periodics
+        let ecose = axnl * coseo1 + aynl * sineo1;
+        let esine = axnl * sineo1 - aynl * coseo1;
+        let el2 = axnl * axnl + aynl * aynl;
+        let pl = am * (1. - el2);
+        if pl < 0. {
+            return fail("semi-latus rectum negative");
+        }
+        let rl = am * (1. - ecose);
+        let rdotl = am.sqrt() * esine / rl;
+        let rvdotl = pl.sqrt() / rl;
+        let betal = (1. - el2).sqrt();
+        let temp = esine / (1. + betal);
+        let sinu = am / rl * (sineo1 - aynl - axnl * temp);
+        let cosu = am / rl * (coseo1 - axnl + aynl * temp);
+        let su = sinu.atan2(cosu);
+        let sin2u = (cosu + cosu) * sinu;
+        let cos2u = 1. - 2. * sinu * sinu;
+        let temp = 1. / pl;
+        let temp1 = 0.5 * J2 * temp;
+        let temp2 = temp1 * temp;
+
+        let mrt = rl * (1. - 1.5 * temp2 * betal * con41) + 0.5 * temp1 * x1mth2 * cos2u;
+        let su = su - 0.25 * temp2 * x7thm1 * sin2u;
+        let xnode = nodep + 1.5 * temp2 * cosip * sin2u;
+        let xinc = xincp + 1.5 * temp2 * cosip * sinip * cos2u;
+        let mvt = rdotl - nm * temp1 * x1mth2 * sin2u / XKE;
+        let rvdot = rvdotl + nm * temp1 * (x1mth2 * cos2u + 1.5 * con41) / XKE;
+
+        // Orientation vectors
+        let (sinsu, cossu) = (su.sin(), su.cos());
+        let (snod, cnod) = (xnode.sin(), xnode.cos());
+        let (sini, cosi) = (xinc.sin(), xinc.cos());
+        let xmx = -snod * cosi;
+        let xmy = cnod * cosi;
+        let ux = [
+            xmx * sinsu + cnod * cossu,
+            xmy * sinsu + snod * cossu,
+            sini * sinsu,
+        ];
+        let vx = [
+            xmx * cossu - cnod * sinsu,
+            xmy * cossu - snod * sinsu,
+            sini * cossu,
+        ];
+
+        if mrt < 1. {
+            return fail("decayed");
+        }
+        let speed = EARTH_RADIUS * XKE / 60.;
+        Ok(Teme {
+            position: [0, 1, 2].map(|i| mrt * ux[i] * EARTH_RADIUS),
+            velocity: [0, 1, 2].map(|i| (mvt * ux[i] + rvdot * vx[i]) * speed),
+        })
+    }
+
+    /// Position and velocity at a time.
+    pub fn at<Tz: TimeZone>(&self, dt: DateTime<Tz>) -> Result<Teme> {
+        self.propagate((JulianDay::from(dt) - self.tle.epoch) * 1440.)
+    }
+
+    /// Where an observer sees the satellite at a time.
+    pub fn observe<Tz: TimeZone>(
+        &self,
+        dt: DateTime<Tz>,
+        observer: &Observer,
+    ) -> Result<Topocentric> {
+        let jd = JulianDay::from(dt.clone());
+        Ok(self.at(dt)?.topocentric(jd, observer))
+    }
+
+    /// Passes above an altitude between two times, found to the second.  A pass under way at either end of the window is cut short
+    /// there.
+    pub fn passes<Tz: TimeZone>(
+        &self,
+        observer: &Observer,
+        from: DateTime<Tz>,
+        to: DateTime<Tz>,
+        altitude: Angle,
+    ) -> Result<Vec<Pass<Tz>>> {
+        // Short enough that even a low satellite can't rise and set between
+        // samples unseen
+        let step = Duration::seconds(30);
+        let above = |dt: &DateTime<Tz>| -> Result<bool> {
+            Ok(self.observe(dt.clone(), observer)?.horizon.altitude >= altitude)
+        };
+
+        let mut passes = Vec::new();
+        let mut rise = match above(&from)? {
+            true => Some(from.clone()),
+            false => None,
+        };
+        let mut t = from.clone();
+        while t < to {
+            let next = std::cmp::min(t.clone() + step, to.clone());
+            let up = above(&next)?;
+            match (&rise, up) {
+                (None, true) => rise = Some(self.crossing(&t, &next, &above)?),
+                (Some(start), false) => {
+                    let end = self.crossing(&t, &next, &above)?;
+                    passes.push(self.pass(start.clone(), end, observer)?);
+                    rise = None;
+                }
+                _ => {}
+            }
+            t = next;
+        }
+        if let Some(start) = rise {
+            passes.push(self.pass(start, to, observer)?);
+        }
+
+        Ok(passes)
+    }
+
+    /// The second at which the satellite crosses the altitude between two
+    /// times either side of it, taken on the side where it is above.
+    fn crossing<Tz: TimeZone>(
+        &self,
+        before: &DateTime<Tz>,
+        after: &DateTime<Tz>,
+        above: &impl Fn(&DateTime<Tz>) -> Result<bool>,
+    ) -> Result<DateTime<Tz>> {
+        let was_up = above(before)?;
+        let (mut a, mut b) = (before.clone(), after.clone());
+        while b.clone() - a.clone() > Duration::seconds(1) {
+            let middle = a.clone() + (b.clone() - a.clone()) / 2;
+            match above(&middle)? == was_up {
+                true => a = middle,
+                false => b = middle,
+            }
+        }
+        Ok(if was_up { a } else { b })
+    }
+
+    fn pass<Tz: TimeZone>(
+        &self,
+        rise: DateTime<Tz>,
+        set: DateTime<Tz>,
+        observer: &Observer,
+    ) -> Result<Pass<Tz>> {
+        let event = |time: DateTime<Tz>| -> Result<PassEvent<Tz>> {
+            let horizon = self.observe(time.clone(), observer)?.horizon;
+            Ok(PassEvent { time, horizon })
+        };
+
+        // The highest point, by golden section search
+        let ratio = (5_f64.sqrt() - 1.) / 2.;
+        let (mut a, mut b) = (rise.clone(), set.clone());
+        while b.clone() - a.clone() > Duration::seconds(1) {
+            let span = (b.clone() - a.clone()).num_milliseconds() as f64;
+            let offset = Duration::milliseconds((span * (1. - ratio)) as i64);
+            let (c, d) = (a.clone() + offset, b.clone() - offset);
+            match self.observe(c.clone(), observer)?.horizon.altitude
+                < self.observe(d.clone(), observer)?.horizon.altitude
+            {
+                true => a = c,
+                false => b = d,
+            }
+        }
+
+        // Visible if lit in a dark sky at any minute, or at the highest
+        // point for a short pass
+        let mut visible = false;
+        let mut t = rise.clone();
+        while t <= set && !visible {
+            visible = self.is_visible(&t, observer)?;
+            t = t + Duration::minutes(1);
+        }
+        visible = visible || self.is_visible(&a, observer)?;
+
+        Ok(Pass {
+            rise: event(rise)?,
+            culmination: event(a)?,
+            set: event(set)?,
+            visible,
+        })
+    }
+
+    fn is_visible<Tz: TimeZone>(&self, dt: &DateTime<Tz>, observer: &Observer) -> Result<bool> {
+        let jd = JulianDay::from(dt.clone());
+        let sun = SolarPosition::from_julian_day(jd).equatorial;
+        let lst = SiderealTime::mean(jd)
+            .local(degrees(observer.longitude))
+            .angle();
+        let dark = sun
+            .to_hour_angle(lst)
+            .to_horizon(degrees(observer.latitude))
+            .altitude
+            < degrees(DARKNESS);
+
+        Ok(dark && self.at(dt.clone())?.is_sunlit(jd))
+    }
+}
+
+impl DeepSpace {
+    /// Vallado's `dscom` and `dsinit`, at the epoch.
+    fn new(sgp4: &Sgp4, epoch: f64, gsto: f64, xpidot: f64) -> Self {
+        let com = Dscom::new(
+            epoch, sgp4.ecco, sgp4.argpo, sgp4.inclo, sgp4.nodeo, sgp4.no,
+        );
+        let (s, ss, z, sz) = (&com.s, &com.ss, &com.z, &com.sz);
+        let emsq = com.emsq;
+
+        // Solar terms for the periodics (dscom)
+        let zes = 0.016_75;
+        let zel = 0.0549;
+        let se2 = 2. * ss[0] * ss[5];
+        let se3 = 2. * ss[0] * ss[6];
+        let si2 = 2. * ss[1] * sz[1][1];
+        let si3 = 2. * ss[1] * (sz[1][2] - sz[1][0]);
+        let sl2 = -2. * ss[2] * sz[0][1];
+        let sl3 = -2. * ss[2] * (sz[0][2] - sz[0][0]);
+        let sl4 = -2. * ss[2] * (-21. - 9. * emsq) * zes;
+        let sgh2 = 2. * ss[3] * sz[3][1];
+        let sgh3 = 2. * ss[3] * (sz[3][2] - sz[3][0]);
+        let sgh4 = -18. * ss[3] * zes;
+        let sh2 = -2. * ss[1] * sz[2][1];
+        let sh3 = -2. * ss[1] * (sz[2][2] - sz[2][0]);
+        // Lunar terms
+        let ee2 = 2. * s[0] * s[5];
+        let e3 = 2. * s[0] * s[6];
+        let xi2 = 2. * s[1] * z[1][1];
+        let xi3 = 2. * s[1] * (z[1][2] - z[1][0]);
+        let xl2 = -2. * s[2] * z[0][1];
+        let xl3 = -2. * s[2] * (z[0][2] - z[0][0]);
+        let xl4 = -2. * s[2] * (-21. - 9. * emsq) * zel;
+        let xgh2 = 2. * s[3] * z[3][1];
+        let xgh3 = 2. * s[3] * (z[3][2] - z[3][0]);
+        let xgh4 = -18. * s[3] * zel;
+        let xh2 = -2. * s[1] * z[2][1];
+        let xh3 = -2. * s[1] * (z[2][2] - z[2][0]);
+        let zmol = (4.719_967_2 + 0.229_971_50 * com.day - com.gam) % TAU;
+        let zmos = (6.256_583_7 + 0.017_201_977 * com.day) % TAU;
+
+        // Secular rates (dsinit)
+        let znl = 1.583_521_8e-4;
+        let zns = 1.194_59e-5;
+        let (sinim, cosim) = (com.sinim, com.cosim);
+        let equatorial = sgp4.inclo < 5.235_987_7e-2 || sgp4.inclo > PI - 5.235_987_7e-2;
+
+        let ses = ss[0] * zns * ss[4];
+        let sis = ss[1] * zns * (sz[1][0] + sz[1][2]);
+        let sls = -zns * ss[2] * (sz[0][0] + sz[0][2] - 14. - 6. * emsq);
+        let sghs = ss[3] * zns * (sz[3][0] + sz[3][2] - 6.);
+        let mut shs = -zns * ss[1] * (sz[2][0] + sz[2][2]);
+        if equatorial {
+            shs = 0.;
+        }
+        if sinim != 0. {
+            shs /= sinim;
+        }
+        let sgs = sghs - cosim * shs;
+
+        let dedt = ses + s[0] * znl * s[4];
+        let didt = sis + s[1] * znl * (z[1][0] + z[1][2]);
+        let dmdt = sls - znl * s[2] * (z[0][0] + z[0][2] - 14. - 6. * emsq);
+        let sghl = s[3] * znl * (z[3][0] + z[3][2] - 6.);
+        let mut shll = -znl * s[1] * (z[2][0] + z[2][2]);
+        if equatorial {
+            shll = 0.;
+        }
+        let mut domdt = sgs + sghl;
+        let mut dnodt = shs;
+        if sinim != 0. {
+            domdt -= cosim / sinim * shll;
+            dnodt += shll / sinim;
+        }
+
+        let resonance = Resonance::new(sgp4, &com, gsto, xpidot, dmdt, domdt, dnodt);
+
+        DeepSpace {
+            gsto,
+            e3,
+            ee2,
+            se2,
+            se3,
+            sgh2,
+            sgh3,
+            sgh4,
+            sh2,
+            sh3,
+            si2,
+            si3,
+            sl2,
+            sl3,
+            sl4,
+            xgh2,
+            xgh3,
+            xgh4,
+            xh2,
+            xh3,
+            xi2,
+            xi3,
+            xl2,
+            xl3,
+            xl4,
+            zmol,
+            zmos,
+            dedt,
+            didt,
+            dmdt,
+            dnodt,
+            domdt,
+            resonance,
+        }
+    }
+
+    /// Secular effects and resonance, Vallado's `dspace`.  The resonance
+    /// integrator restarts from the epoch each time, which gives the same
+    /// steps as keeping its state.
+    #[allow(clippy::too_many_arguments)]
+    fn secular(
+        &self,
+        sgp4: &Sgp4,
+        t: f64,
+        em: &mut f64,
+        argpm: &mut f64,
+        inclm: &mut f64,
+        mm: &mut f64,
+        nodem: &mut f64,
+        nm: &mut f64,
+    ) {
+        const RPTIM: f64 = 4.375_269_088_011_3e-3;
+        const STEP: f64 = 720.;
+        const STEP2: f64 = 259_200.;
+
+        let theta = (self.gsto + t * RPTIM) % TAU;
+        *em += self.dedt * t;
+        *inclm += self.didt * t;
+        *argpm += self.domdt * t;
+        *nodem += self.dnodt * t;
+        *mm += self.dmdt * t;
+
+        let (xfact, xlamo) = match self.resonance {
+            Resonance::None => return,
+            Resonance::Synchronous { xfact, xlamo, .. } => (xfact, xlamo),
+            Resonance::HalfDay { xfact, xlamo, .. } => (xfact, xlamo),
+        };
+        let rates = |atime: f64, xli: f64, xni: f64| -> (f64, f64, f64) {
+            let xldot = xni + xfact;
+            let (xndt, xnddt) = match &self.resonance {
+                Resonance::Synchronous {
+                    del1, del2, del3, ..
+                } => {
+                    let (fasx2, fasx4, fasx6) = (0.131_309_08, 2.884_319_8, 0.374_480_87);
+                    (
+                        del1 * (xli - fasx2).sin()
+                            + del2 * (2. * (xli - fasx4)).sin()
+                            + del3 * (3. * (xli - fasx6)).sin(),
+                        del1 * (xli - fasx2).cos()
+                            + 2. * del2 * (2. * (xli - fasx4)).cos()
+                            + 3. * del3 * (3. * (xli - fasx6)).cos(),
+                    )
+                }
+                Resonance::HalfDay { d, .. } => {
+                    let (g22, g32, g44, g52, g54) = (
+                        5.768_639_6,
+                        0.952_408_98,
+                        1.801_499_8,
+                        1.050_833,
+                        4.410_889_8,
+                    );
+                    let xomi = sgp4.argpo + sgp4.argpdot * atime;
+                    let x2omi = xomi + xomi;
+                    let x2li = xli + xli;
+                    let angles = [
+                        x2omi + xli - g22,
+                        xli - g22,
+                        xomi + xli - g32,
+                        -xomi + xli - g32,
+                        x2omi + x2li - g44,
+                        x2li - g44,
+                        xomi + xli - g52,
+                        -xomi + xli - g52,
+                        xomi + x2li - g54,
+                        -xomi + x2li - g54,
+                    ];
+                    // The terms in twice the longitude change twice as fast
+                    let factors = [1., 1., 1., 1., 2., 2., 1., 1., 2., 2.];
+                    (0..10).fold((0., 0.), |(ndt, nddt), i| {
+                        (
+                            ndt + d[i] * angles[i].sin(),
+                            nddt + factors[i] * d[i] * angles[i].cos(),
+                        )
+                    })
+                }
+                Resonance::None => (0., 0.),
+            };
+            (xldot, xndt, xnddt * xldot)
+        };
+
+        let delt = if t > 0. { STEP } else { -STEP };
+        let (mut atime, mut xli, mut xni) = (0., xlamo, sgp4.no);
+        let (mut xldot, mut xndt, mut xnddt) = rates(atime, xli, xni);
+        while (t - atime).abs() >= STEP {
+            xli += xldot * delt + xndt * STEP2;
+            xni += xndt * delt + xnddt * STEP2;
+            atime += delt;
+            let next = rates(atime, xli, xni);
+            xldot = next.0;
+            xndt = next.1;
+            xnddt = next.2;
+        }
+        let ft = t - atime;
+
+        *nm = xni + xndt * ft + xnddt * ft * ft * 0.5;
+        let xl = xli + xldot * ft + xndt * ft * ft * 0.5;
+        *mm = match self.resonance {
+            Resonance::Synchronous { .. } => xl - *nodem - *argpm + theta,
+            _ => xl - 2. * *nodem + 2. * theta,
+        };
+    }
+
+    /// Lunar-solar periodics, Vallado's `dpper`.
+    fn periodics(
+        &self,
+        t: f64,
+        ep: &mut f64,
+        inclp: &mut f64,
+        nodep: &mut f64,
+        argpp: &mut f64,
+        mp: &mut f64,
+    ) {
+        let zns = 1.194_59e-5;
+        let zes = 0.016_75;
+        let znl = 1.583_521_8e-4;
+        let zel = 0.0549;
+        // Whole, rather than relative to their values at the epoch, as the
+        // reference code has them
+        let [pe, pinc, pl, pgh, ph] = {
+            let zm = self.zmos + zns * t;
+            let zf = zm + 2. * zes * zm.sin();
+            let sinzf = zf.sin();
+            let f2 = 0.5 * sinzf * sinzf - 0.25;
+            let f3 = -0.5 * sinzf * zf.cos();
+            let ses = self.se2 * f2 + self.se3 * f3;
+            let sis = self.si2 * f2 + self.si3 * f3;
+            let sls = self.sl2 * f2 + self.sl3 * f3 + self.sl4 * sinzf;
+            let sghs = self.sgh2 * f2 + self.sgh3 * f3 + self.sgh4 * sinzf;
+            let shs = self.sh2 * f2 + self.sh3 * f3;
+
+            let zm = self.zmol + znl * t;
+            let zf = zm + 2. * zel * zm.sin();
+            let sinzf = zf.sin();
+            let f2 = 0.5 * sinzf * sinzf - 0.25;
+            let f3 = -0.5 * sinzf * zf.cos();
+            let sel = self.ee2 * f2 + self.e3 * f3;
+            let sil = self.xi2 * f2 + self.xi3 * f3;
+            let sll = self.xl2 * f2 + self.xl3 * f3 + self.xl4 * sinzf;
+            let sghl = self.xgh2 * f2 + self.xgh3 * f3 + self.xgh4 * sinzf;
+            let shll = self.xh2 * f2 + self.xh3 * f3;
+
+            [ses + sel, sis + sil, sls + sll, sghs + sghl, shs + shll]
+        };
+
+        *inclp += pinc;
+        *ep += pe;
+        let (sinip, cosip) = (inclp.sin(), inclp.cos());
+
+        if *inclp >= 0.2 {
+            let ph = ph / sinip;
+            *argpp += pgh - cosip * ph;
+            *nodep += ph;
+            *mp += pl;
+        } else {
+            // Lyddane's modification for low inclinations
+            let (sinop, cosop) = (nodep.sin(), nodep.cos());
+            let alfdp = sinip * sinop + ph * cosop + pinc * cosip * sinop;
+            let betdp = sinip * cosop - ph * sinop + pinc * cosip * cosop;
+            *nodep %= TAU;
+            let xls = *mp + *argpp + cosip * *nodep + pl + pgh - pinc * *nodep * sinip;
+            let xnoh = *nodep;
+            *nodep = alfdp.atan2(betdp);
+            if (xnoh - *nodep).abs() > PI {
+                if *nodep < xnoh {
+                    *nodep += TAU;
+                } else {
+                    *nodep -= TAU;
+                }
+            }
+            *mp += pl;
+            *argpp = xls - *mp - cosip * *nodep;
+        }
+    }
+}
+
+impl Resonance {
+    /// The resonance terms of Vallado's `dsinit`, for orbits of about a day
+    /// or, if eccentric, half a day.
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        sgp4: &Sgp4,
+        com: &Dscom,
+        gsto: f64,
+        xpidot: f64,
+        dmdt: f64,
+        domdt: f64,
+        dnodt: f64,
+    ) -> Self {
+        const RPTIM: f64 = 4.375_269_088_011_3e-3;
+        let (nm, em) = (sgp4.no, sgp4.ecco);
+        let (sinim, cosim) = (com.sinim, com.cosim);
+        let aonv = (nm / XKE).powf(X2O3);
+        let theta = gsto % TAU;
+
+        if nm < 0.005_235_987_7 && nm > 0.003_490_658_5 {
+            let (q22, q31, q33) = (1.789_167_9e-6, 2.146_074_8e-6, 2.212_301_5e-7);
+            let emsq = com.emsq;
+            let g200 = 1. + emsq * (-2.5 + 0.8125 * emsq);
+            let g310 = 1. + 2. * emsq;
+            let g300 = 1. + emsq * (-6. + 6.609_37 * emsq);
+            let f220 = 0.75 * (1. + cosim) * (1. + cosim);
+            let f311 = 0.9375 * sinim * sinim * (1. + 3. * cosim) - 0.75 * (1. + cosim);
+            let f330 = 1.875 * (1. + cosim).powi(3);
+            let del1 = 3. * nm * nm * aonv * aonv;
+            let del2 = 2. * del1 * f220 * g200 * q22;
+            let del3 = 3. * del1 * f330 * g300 * q33 * aonv;
+            let del1 = del1 * f311 * g310 * q31 * aonv;
+
+            Resonance::Synchronous {
+                del1,
+                del2,
+                del3,
+                xfact: sgp4.mdot + xpidot - RPTIM + dmdt + domdt + dnodt - nm,
+                xlamo: (sgp4.mo + sgp4.nodeo + sgp4.argpo - theta) % TAU,
+            }
+        } else if (8.26e-3..=9.24e-3).contains(&nm) && em >= 0.5 {
+            let (root22, root32, root44, root52, root54) = (
+                1.789_167_9e-6,
+                3.739_379_2e-7,
+                7.363_695_3e-9,
+                1.142_863_9e-7,
+                2.176_580_3e-9,
+            );
+            let cosisq = cosim * cosim;
+            let emsq = em * em;
+            let eoc = em * emsq;
+            let g201 = -0.306 - (em - 0.64) * 0.440;
+            let (g211, g310, g322, g410, g422, g520) = if em <= 0.65 {
+                (
+                    3.616 - 13.247 * em + 16.29 * emsq,
+                    -19.302 + 117.39 * em - 228.419 * emsq + 156.591 * eoc,
+                    -18.9068 + 109.7927 * em - 214.6334 * emsq + 146.5816 * eoc,
+                    -41.122 + 242.694 * em - 471.094 * emsq + 313.953 * eoc,
+                    -146.407 + 841.88 * em - 1629.014 * emsq + 1083.435 * eoc,
+                    -532.114 + 3017.977 * em - 5740.032 * emsq + 3708.276 * eoc,
+                )
+            } else {
+                (
+                    -72.099 + 331.819 * em - 508.738 * emsq + 266.724 * eoc,
+                    -346.844 + 1582.851 * em - 2415.925 * emsq + 1246.113 * eoc,
+                    -342.585 + 1554.908 * em - 2366.899 * emsq + 1215.972 * eoc,
+                    -1052.797 + 4758.686 * em - 7193.992 * emsq + 3651.957 * eoc,
+                    -3581.69 + 16178.11 * em - 24462.77 * emsq + 12422.52 * eoc,
+                    match em > 0.715 {
+                        true => -5149.66 + 29936.92 * em - 54087.36 * emsq + 31324.56 * eoc,
+                        false => 1464.74 - 4664.75 * em + 3763.64 * emsq,
+                    },
+                )
+            };
+            let (g533, g521, g532) = if em < 0.7 {
+                (
+                    -919.2277 + 4988.61 * em - 9064.77 * emsq + 5542.21 * eoc,
+                    -822.710_72 + 4568.6173 * em - 8491.4146 * emsq + 5337.524 * eoc,
+                    -853.666 + 4690.25 * em - 8624.77 * emsq + 5341.4 * eoc,
+                )
+            } else {
+                (
+                    -37995.78 + 161_616.52 * em - 229_838.2 * emsq + 109_377.94 * eoc,
+                    -51752.104 + 218_913.95 * em - 309_468.16 * emsq + 146_349.42 * eoc,
+                    -40023.88 + 170_470.89 * em - 242_699.48 * emsq + 115_605.82 * eoc,
+                )
+            };
+
+            let sini2 = sinim * sinim;
+            let f220 = 0.75 * (1. + 2. * cosim + cosisq);
+            let f221 = 1.5 * sini2;
+            let f321 = 1.875 * sinim * (1. - 2. * cosim - 3. * cosisq);
+            let f322 = -1.875 * sinim * (1. + 2. * cosim - 3. * cosisq);
+            let f441 = 35. * sini2 * f220;
+            let f442 = 39.375 * sini2 * sini2;
+            let f522 = 9.843_75
+                * sinim
+                * (sini2 * (1. - 2. * cosim - 5. * cosisq)
+                    + 0.333_333_33 * (-2. + 4. * cosim + 6. * cosisq));
+            let f523 = sinim
+                * (4.921_875_12 * sini2 * (-2. - 4. * cosim + 10. * cosisq)
+                    + 6.562_500_12 * (1. + 2. * cosim - 3. * cosisq));
+            let f542 =
+                29.531_25 * sinim * (2. - 8. * cosim + cosisq * (-12. + 8. * cosim + 10. * cosisq));
+            let f543 =
+                29.531_25 * sinim * (-2. - 8. * cosim + cosisq * (12. + 8. * cosim - 10. * cosisq));
+
+            let temp1 = 3. * nm * nm * aonv * aonv;
+            let temp = temp1 * root22;
+            let (d2201, d2211) = (temp * f220 * g201, temp * f221 * g211);
+            let temp1 = temp1 * aonv;
+            let temp = temp1 * root32;
+            let (d3210, d3222) = (temp * f321 * g310, temp * f322 * g322);
+            let temp1 = temp1 * aonv;
+            let temp = 2. * temp1 * root44;
+            let (d4410, d4422) = (temp * f441 * g410, temp * f442 * g422);
+            let temp1 = temp1 * aonv;
+            let temp = temp1 * root52;
+            let (d5220, d5232) = (temp * f522 * g520, temp * f523 * g532);
+            let temp = 2. * temp1 * root54;
+            let (d5421, d5433) = (temp * f542 * g521, temp * f543 * g533);
+
+            Resonance::HalfDay {
+                d: [
+                    d2201, d2211, d3210, d3222, d4410, d4422, d5220, d5232, d5421, d5433,
+                ],
+                xfact: sgp4.mdot + dmdt + 2. * (sgp4.nodedot + dnodt - RPTIM) - nm,
+                xlamo: (sgp4.mo + sgp4.nodeo + sgp4.nodeo - theta - theta) % TAU,
+            }
+        } else {
+            Resonance::None
+        }
+    }
+}
+
+impl Dscom {
+    /// The lunar and solar terms of Vallado's `dscom`, at a day counted
+    /// from 1900 January 0.5.
+    #[allow(clippy::many_single_char_names)]
+    fn new(day: f64, em: f64, argpp: f64, inclp: f64, nodep: f64, nm: f64) -> Self {
+        let c1ss = 2.986_479_7e-6;
+        let c1l = 4.796_806_5e-7;
+        let zsinis = 0.397_854_16;
+        let zcosis = 0.917_448_67;
+        let zcosgs = 0.194_590_5;
+        let zsings = -0.980_884_58;
+
+        let (snodm, cnodm) = (nodep.sin(), nodep.cos());
+        let (sinomm, cosomm) = (argpp.sin(), argpp.cos());
+        let (sinim, cosim) = (inclp.sin(), inclp.cos());
+        let emsq = em * em;
+        let betasq = 1. - emsq;
+        let rtemsq = betasq.sqrt();
+
+        let xnodce = (4.523_602 - 9.242_202_9e-4 * day) % TAU;
+        let (stem, ctem) = (xnodce.sin(), xnodce.cos());
+        let zcosil = 0.913_751_64 - 0.035_680_96 * ctem;
+        let zsinil = (1. - zcosil * zcosil).sqrt();
+        let zsinhl = 0.089_683_511 * stem / zsinil;
+        let zcoshl = (1. - zsinhl * zsinhl).sqrt();
+        let gam = 5.835_151_4 + 0.001_944_368 * day;
+        let zx = 0.397_854_16 * stem / zsinil;
+        let zy = zcoshl * ctem + 0.917_448_67 * zsinhl * stem;
+        let zx = gam + zx.atan2(zy) - xnodce;
+        let (zcosgl, zsingl) = (zx.cos(), zx.sin());
+
+        // The Sun first, then the Moon
+        let bodies = [
+            (zcosgs, zsings, zcosis, zsinis, cnodm, snodm, c1ss),
+            (
+                zcosgl,
+                zsingl,
+                zcosil,
+                zsinil,
+                zcoshl * cnodm + zsinhl * snodm,
+                snodm * zcoshl - cnodm * zsinhl,
+                c1l,
+            ),
+        ];
+        let terms = bodies.map(|(zcosg, zsing, zcosi, zsini, zcosh, zsinh, cc)| {
+            let a1 = zcosg * zcosh + zsing * zcosi * zsinh;
+            let a3 = -zsing * zcosh + zcosg * zcosi * zsinh;
+            let a7 = -zcosg * zsinh + zsing * zcosi * zcosh;
+            let a8 = zsing * zsini;
+            let a9 = zsing * zsinh + zcosg * zcosi * zcosh;
+            let a10 = zcosg * zsini;
+            let a2 = cosim * a7 + sinim * a8;
+            let a4 = cosim * a9 + sinim * a10;
+            let a5 = -sinim * a7 + cosim * a8;
+            let a6 = -sinim * a9 + cosim * a10;
+
+            let x1 = a1 * cosomm + a2 * sinomm;
+            let x2 = a3 * cosomm + a4 * sinomm;
+            let x3 = -a1 * sinomm + a2 * cosomm;
+            let x4 = -a3 * sinomm + a4 * cosomm;
+            let x5 = a5 * sinomm;
+            let x6 = a6 * sinomm;
+            let x7 = a5 * cosomm;
+            let x8 = a6 * cosomm;
+
+            let z31 = 12. * x1 * x1 - 3. * x3 * x3;
+            let z32 = 24. * x1 * x2 - 6. * x3 * x4;
+            let z33 = 12. * x2 * x2 - 3. * x4 * x4;
+            let z1 = 3. * (a1 * a1 + a2 * a2) + z31 * emsq;
+            let z2 = 6. * (a1 * a3 + a2 * a4) + z32 * emsq;
+            let z3 = 3. * (a3 * a3 + a4 * a4) + z33 * emsq;
+            let z11 = -6. * a1 * a5 + emsq * (-24. * x1 * x7 - 6. * x3 * x5);
+            let z12 = -6. * (a1 * a6 + a3 * a5)
+                + emsq * (-24. * (x2 * x7 + x1 * x8) - 6. * (x3 * x6 + x4 * x5));
+            let z13 = -6. * a3 * a6 + emsq * (-24. * x2 * x8 - 6. * x4 * x6);
+            let z21 = 6. * a2 * a5 + emsq * (24. * x1 * x5 - 6. * x3 * x7);
+            let z22 = 6. * (a4 * a5 + a2 * a6)
+                + emsq * (24. * (x2 * x5 + x1 * x6) - 6. * (x4 * x7 + x3 * x8));
+            let z23 = 6. * a4 * a6 + emsq * (24. * x2 * x6 - 6. * x4 * x8);
+            let z1 = z1 + z1 + betasq * z31;
+            let z2 = z2 + z2 + betasq * z32;
+            let z3 = z3 + z3 + betasq * z33;
+
+            let s3 = cc / nm;
+            let s2 = -0.5 * s3 / rtemsq;
+            let s4 = s3 * rtemsq;
+            let s1 = -15. * em * s4;
+            let s5 = x1 * x3 + x2 * x4;
+            let s6 = x2 * x3 + x1 * x4;
+            let s7 = x2 * x4 - x1 * x3;
+
+            (
+                [s1, s2, s3, s4, s5, s6, s7],
+                [
+                    [z1, z2, z3],
+                    [z11, z12, z13],
+                    [z21, z22, z23],
+                    [z31, z32, z33],
+                ],
+            )
+        });
+        let [(ss, sz), (s, z)] = terms;
+
+        Dscom {
+            day,
+            emsq,
+            gam,
+            sinim,
+            cosim,
+            s,
+            ss,
+            z,
+            sz,
+        }
+    }
+}
+
+fn long_period_xlcof(sinio: f64, cosio: f64) -> f64 {
+    // Avoid dividing by zero for an inclination of 180°
+    let divisor = match (cosio + 1.).abs() > 1.5e-12 {
+        true => 1. + cosio,
+        false => 1.5e-12,
+    };
+    -0.25 * J3OJ2 * sinio * (3. + 5. * cosio) / divisor
+}
+
+impl Teme {
+    /// Where an observer sees this position at a Julian day on UTC, turning
+    /// the frame with the Earth by the mean sidereal time.
+    pub fn topocentric(&self, jd: JulianDay, observer: &Observer) -> Topocentric {
+        let theta = SiderealTime::mean(jd).degrees().to_radians();
+        let [x, y, z] = self.position;
+        let fixed = [
+            theta.cos() * x + theta.sin() * y,
+            -theta.sin() * x + theta.cos() * y,
+            z,
+        ];
+
+        let (lat, lon) = (
+            observer.latitude.to_radians(),
+            observer.longitude.to_radians(),
+        );
+        let site = observer.earth_fixed();
+        let [dx, dy, dz] = [0, 1, 2].map(|i| fixed[i] - site[i]);
+
+        let south = lat.sin() * lon.cos() * dx + lat.sin() * lon.sin() * dy - lat.cos() * dz;
+        let east = -lon.sin() * dx + lon.cos() * dy;
+        let zenith = lat.cos() * lon.cos() * dx + lat.cos() * lon.sin() * dy + lat.sin() * dz;
+        let range = (dx * dx + dy * dy + dz * dz).sqrt();
+
+        Topocentric {
+            horizon: Horizon {
+                azimuth: degrees(east.atan2(-south).to_degrees().rem_euclid(360.)),
+                altitude: degrees((zenith / range).asin().to_degrees()),
+            },
+            range,
+        }
+    }
+
+    /// Whether the Sun shines on this position at a Julian day, outside the
+    /// Earth's shadow taken as a cylinder.
+    pub fn is_sunlit(&self, jd: JulianDay) -> bool {
+        let sun = SolarPosition::from_julian_day(jd);
+        let (ra, dec) = (
+            sun.equatorial.ra.to_radians().value,
+            sun.equatorial.dec.to_radians().value,
+        );
+        let towards = [dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin()];
+        let along: f64 = (0..3).map(|i| self.position[i] * towards[i]).sum();
+        let across: f64 = (0..3)
+            .map(|i| (self.position[i] - along * towards[i]).powi(2))
+            .sum::<f64>()
+            .sqrt();
+
+        // The Sun is far enough away for its rays to be parallel
+        along > 0. || across > EARTH_RADIUS
+    }
+}
@@ -0,0 +1,190 @@
+//! Sidereal time, after Meeus, *Astronomical Algorithms*, chapter 12, and
+//! the IAU 2006 expression of Capitaine, Wallace and Chapront (2003).
+//!
+//! Sidereal time is an angle, the hour angle of the vernal equinox, kept in
+//! hours from 0 up to 24.  Mean sidereal time follows the rotation of the
+//! Earth on UT1; apparent sidereal time adds the equation of the equinoxes,
+//! the nutation of the equinox along the equator.  Times from a `DateTime`
+//! are UTC taken as UT1, which they stay within a second of.
+//!
+//! A sidereal day is about 3 min 56 s shorter than a solar one, so every
+//! sidereal time comes round once a civil day, and the few minutes of
+//! sidereal time just after midnight come round twice.
+
+use std::fmt;
+
+use chrono::{Date, DateTime, Duration, LocalResult, TimeZone};
+
+use crate::{
+    coords::{nutation, obliquity},
+    hours,
+    rise_set::midnight,
+    timescale::{JulianDate, Tt, Ut1},
+    Angle, JulianDay,
+};
+
+/// Sidereal days in a solar day, the rate sidereal time gains on UT1.
+pub const SIDEREAL_RATIO: f64 = 1.002_737_909_350_795;
+
+/// Greenwich or local sidereal time.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct SiderealTime {
+    hours: f64,
+}
+
+impl SiderealTime {
+    /// Any number of hours, wrapped into a day.
+    pub fn from_hours(hours: f64) -> Self {
+        // rem_euclid can round a tiny negative number up to 24
+        let hours = hours.rem_euclid(24.);
+        SiderealTime {
+            hours: if hours < 24. { hours } else { 0. },
+        }
+    }
+
+    /// Any number of degrees, wrapped into a day.
+    pub fn from_degrees(degrees: f64) -> Self {
+        SiderealTime::from_hours(degrees / 15.)
+    }
+
+    pub fn from_hms(hours: u32, minutes: u32, seconds: f64) -> Self {
+        let angle = Angle::from_hms_tuple((hours as f64, minutes as f64, seconds));
+        SiderealTime::from_hours(angle.to_hours().value)
+    }
+
+    /// Hours from 0 up to 24.
+    pub fn hours(&self) -> f64 {
+        self.hours
+    }
+
+    /// Degrees from 0 up to 360.
+    pub fn degrees(&self) -> f64 {
+        self.hours * 15.
+    }
+
+    /// The hour angle of the vernal equinox, in hours.
+    pub fn angle(&self) -> Angle {
+        hours(self.hours)
+    }
+
+    /// Hours, minutes and seconds.
+    pub fn hms(&self) -> (u32, u32, f64) {
+        let (h, m, s) = self.angle().to_hms_tuple();
+        (h as u32, m as u32, s)
+    }
+
+    /// Greenwich mean sidereal time for a Julian day on UT1, from the IAU
+    /// 1982 expression (Meeus 12.4).
+    pub fn mean(jd: JulianDay) -> Self {
+        let d = jd - JulianDay::J2000;
+        let t = d / 36_525.;
+
+        // Whole turns of 360° a day drop out, leaving the fraction of a day
+        let degrees = 280.460_618_37
+            + 360. * jd.fraction()
+            + 0.985_647_366_29 * d
+            + 0.000_387_933 * t.powi(2)
+            - t.powi(3) / 38_710_000.;
+        SiderealTime::from_degrees(degrees)
+    }
+
+    /// Greenwich apparent sidereal time for a Julian day on UT1, the IAU
+    /// 1982 mean time plus the equation of the equinoxes.
+    pub fn apparent(jd: JulianDay) -> Self {
+        SiderealTime::from_hours(SiderealTime::mean(jd).hours + equation_of_equinoxes(jd))
+    }
+
+    /// Greenwich mean sidereal time from the IAU 2006 expression, the Earth
+    /// rotation angle plus a polynomial in TT for the precession.
+    pub fn mean_2006(ut1: JulianDate<Ut1>) -> Self {
+        let t = (ut1.to::<Tt>().jd - JulianDay::J2000) / 36_525.;
+        let polynomial = 0.014_506 + 4_612.156_534 * t + 1.391_581_7 * t.powi(2)
+            - 0.000_000_44 * t.powi(3)
+            - 0.000_029_956 * t.powi(4)
+            - 0.000_000_036_8 * t.powi(5);
+
+        SiderealTime::from_degrees(earth_rotation_angle(ut1.jd) + polynomial / 3600.)
+    }
+
+    /// Greenwich apparent sidereal time from the IAU 2006 mean time.
+    pub fn apparent_2006(ut1: JulianDate<Ut1>) -> Self {
+        let tt = ut1.to::<Tt>().jd;
+        SiderealTime::from_hours(SiderealTime::mean_2006(ut1).hours + equation_of_equinoxes(tt))
+    }
+
+    /// Local sidereal time at a longitude east of Greenwich, from Greenwich
+    /// sidereal time.
+    pub fn local(self, longitude: Angle) -> Self {
+        SiderealTime::from_hours(self.hours + longitude.to_hours().value)
+    }
+
+    /// Greenwich sidereal time, from local sidereal time at a longitude east
+    /// of Greenwich.
+    pub fn greenwich(self, longitude: Angle) -> Self {
+        SiderealTime::from_hours(self.hours - longitude.to_hours().value)
+    }
+
+    /// The moments in a civil day at which this is the Greenwich mean
+    /// sidereal time: usually one, but two when it falls in the first few
+    /// minutes after the sidereal time of midnight, and none should the day
+    /// be cut short by a change of clocks.  A day whose midnight the clocks
+    /// skip starts when they jump.
+    pub fn to_ut<Tz: TimeZone>(self, date: Date<Tz>) -> LocalResult<DateTime<Tz>> {
+        let start = midnight(&date);
+        let end = midnight(&date.succ());
+        let length = (end - start.clone()).num_seconds() as f64 / 3600.;
+        let midnight = JulianDay::from(start.clone());
+
+        // Solar hours after midnight, refined since the rate is not quite
+        // constant
+        let solve = |guess: f64| {
+            (0..3).fold(guess, |hours, _| {
+                let st = SiderealTime::mean(midnight + hours / 24.);
+                hours + ((self.hours - st.hours + 12.).rem_euclid(24.) - 12.) / SIDEREAL_RATIO
+            })
+        };
+        let first = (self.hours - SiderealTime::mean(midnight).hours).rem_euclid(24.);
+        let sidereal = [first, first + 24.];
+        let mut times = sidereal
+            .iter()
+            .map(|sidereal| solve(sidereal / SIDEREAL_RATIO))
+            .filter(|hours| (0. ..length).contains(hours))
+            .map(|hours| start.clone() + Duration::nanoseconds((hours * 3.6e12).round() as i64));
+
+        match (times.next(), times.next()) {
+            (Some(first), Some(second)) => LocalResult::Ambiguous(first, second),
+            (Some(time), None) => LocalResult::Single(time),
+            _ => LocalResult::None,
+        }
+    }
+}
+
+/// Greenwich mean sidereal time of the instant, taking UTC as UT1.
+impl<Tz: TimeZone> From<DateTime<Tz>> for SiderealTime {
+    fn from(dt: DateTime<Tz>) -> Self {
+        SiderealTime::mean(JulianDay::from(dt))
+    }
+}
+
+/// Hours, minutes and seconds, to the nearest second or to as many decimal
+/// places as the precision asks for.
+impl fmt::Display for SiderealTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.angle().display_clock(), f)
+    }
+}
+
+/// The equation of the equinoxes in hours, nutation in longitude projected
+/// onto the equator.
+pub fn equation_of_equinoxes(jd: JulianDay) -> f64 {
+    let (longitude, obl) = nutation(jd);
+    longitude.to_hours().value * (obliquity(jd) + obl).to_radians().value.cos()
+}
+
+/// The Earth rotation angle in degrees for a Julian day on UT1 (IERS
+/// Conventions 2010, 5.15).
+pub fn earth_rotation_angle(jd: JulianDay) -> f64 {
+    let d = jd - JulianDay::J2000;
+    let turns = 0.779_057_273_264 + jd.fraction() + 0.002_737_811_911_354_48 * d;
+    turns.rem_euclid(1.) * 360.
+}
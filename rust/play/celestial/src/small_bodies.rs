@@ -0,0 +1,483 @@
+//! Comets and asteroids from Minor Planet Center orbital elements, after
+//! Meeus, *Astronomical Algorithms*, chapters 33 to 35, with the orbit
+//! solved in universal variables (Danby, *Fundamentals of Celestial
+//! Mechanics*, chapter 6) so that elliptic, parabolic and hyperbolic orbits
+//! are all handled alike.
+//!
+//! Elements are osculating elements referred to the ecliptic and equinox of
+//! J2000.0.  Orbits are taken as unperturbed two-body orbits about the Sun,
+//! which is good to arcminutes for a few months either side of the epoch of
+//! the elements.  `geocentric` takes a Julian day on TT, the dynamical time
+//! of the orbit; `ephemeris` takes civil times and converts them.
+
+use std::{
+    f64::consts::{PI, TAU},
+    fs,
+    io::Write,
+    path::Path,
+    str::FromStr,
+};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::{
+    error::{Error, Result},
+    planets::{self, Heliocentric, PlanetPosition, Theory},
+    timescale::{JulianDate, Tt},
+    JulianDay,
+};
+
+/// The Gaussian gravitational constant, the square root of the Sun's
+/// gravitational parameter in AU^1.5 a day.
+const GAUSS: f64 = 0.017_202_098_95;
+
+/// The slope parameter MPCORB leaves out when it isn't known.
+const DEFAULT_SLOPE: f64 = 0.15;
+
+/// The shape, size and orientation of an orbit about the Sun, and when the
+/// body is at perihelion.  Angles are in degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Orbit {
+    /// Perihelion distance in AU.
+    pub perihelion_distance: f64,
+    pub eccentricity: f64,
+    pub inclination: f64,
+    /// Longitude of the ascending node.
+    pub node: f64,
+    pub argument_of_perihelion: f64,
+    pub perihelion_time: JulianDay,
+}
+
+/// How bright a body is at unit distances from the Sun and the Earth, and
+/// how that changes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Magnitude {
+    /// The H, G system for asteroids, which allows for the phase angle.
+    Asteroid { h: f64, g: f64 },
+    /// A comet's total magnitude, `h + 5 log Δ + k log r`.
+    Comet { h: f64, k: f64 },
+}
+
+/// A comet or asteroid.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmallBody {
+    /// Name and number, such as `(1) Ceres` or `2P/Encke`, or the packed
+    /// designation if the elements don't give one.
+    pub designation: String,
+    pub orbit: Orbit,
+    pub magnitude: Option<Magnitude>,
+}
+
+/// Solve Kepler's equation in universal variables for the universal anomaly
+/// in AU^½, some time in days after perihelion.  It is `√a E` for an
+/// ellipse, `√q tan(ν/2)` times a constant for a parabola, and `√-a H` for a
+/// hyperbola.
+///
+/// The equation increases with the anomaly and, over half an orbit, bends
+/// upwards, so that Newton's method converges from any start.
+///
+/// # Panics
+///
+/// If the perihelion distance isn't positive or the eccentricity is
+/// negative.  Elements read by `parse_elements` never are.
+pub fn solve_universal(perihelion_distance: f64, eccentricity: f64, days: f64) -> f64 {
+    assert!(
+        perihelion_distance > 0. && eccentricity >= 0.,
+        "no orbit with q = {} and e = {}",
+        perihelion_distance,
+        eccentricity
+    );
+    let (q, e) = (perihelion_distance, eccentricity);
+    let alpha = (1. - e) / q;
+
+    // Time scaled by the Gaussian constant, within half a period of
+    // perihelion for an ellipse
+    let mut tau = GAUSS * days;
+    if alpha > 0. {
+        let period = TAU / alpha.powf(1.5);
+        tau = (tau + period / 2.).rem_euclid(period) - period / 2.;
+    }
+    let target = tau.abs();
+
+    // Vallado's first guess for an ellipse.  For a parabola or hyperbola
+    // the time is more than qχ and eχ³/6, which bound the anomaly, unless
+    // Vallado's guess is lower still
+    let mut chi = match alpha > 0. {
+        true => target * alpha,
+        false => (target / q).min((6. * target / e).cbrt()),
+    };
+    if alpha < 0. {
+        let root = (-1. / alpha).sqrt();
+        let guess = root * (-2. * alpha * target / (root * e)).ln();
+        if guess > 0. && guess < chi {
+            chi = guess;
+        }
+    }
+
+    for _ in 0..200 {
+        let (c, s) = stumpff(alpha * chi.powi(2));
+        let time = e * chi.powi(3) * s + q * chi;
+        let radius = e * chi.powi(2) * c + q;
+        let delta = (time - target) / radius;
+        chi -= delta;
+        if delta.abs() <= 1e-14 * chi.max(1.) {
+            break;
+        }
+    }
+
+    chi.copysign(tau)
+}
+
+impl Orbit {
+    /// An elliptic orbit from its semi-major axis and the mean anomaly at an
+    /// epoch, as MPCORB gives them, or an error if they aren't an ellipse.
+    pub fn from_mean_anomaly(
+        semi_major_axis: f64,
+        eccentricity: f64,
+        inclination: f64,
+        node: f64,
+        argument_of_perihelion: f64,
+        mean_anomaly: f64,
+        epoch: JulianDay,
+    ) -> Result<Self> {
+        if !(semi_major_axis > 0.
+            && semi_major_axis.is_finite()
+            && (0. ..1.).contains(&eccentricity))
+        {
+            return Err(Error::Parse(format!(
+                "no ellipse with a = {} and e = {}",
+                semi_major_axis, eccentricity
+            )));
+        }
+        let motion = GAUSS / semi_major_axis.powf(1.5);
+        let anomaly = (mean_anomaly.to_radians() + PI).rem_euclid(TAU) - PI;
+
+        Ok(Orbit {
+            perihelion_distance: semi_major_axis * (1. - eccentricity),
+            eccentricity,
+            inclination,
+            node,
+            argument_of_perihelion,
+            perihelion_time: epoch - anomaly / motion,
+        })
+    }
+
+    /// Semi-major axis in AU, or `None` for a parabola or hyperbola.
+    pub fn semi_major_axis(&self) -> Option<f64> {
+        match self.eccentricity < 1. {
+            true => Some(self.perihelion_distance / (1. - self.eccentricity)),
+            false => None,
+        }
+    }
+
+    /// Period in days, or `None` for an orbit that doesn't close.
+    pub fn period(&self) -> Option<f64> {
+        self.semi_major_axis().map(|a| TAU * a.powf(1.5) / GAUSS)
+    }
+
+    /// Heliocentric coordinates on the ecliptic and equinox of the date.
+    /// Panics, as `solve_universal` does, for a perihelion distance that
+    /// isn't positive or a negative eccentricity.
+    pub fn heliocentric(&self, jd: JulianDay) -> Heliocentric {
+        let (q, e) = (self.perihelion_distance, self.eccentricity);
+        let days = jd - self.perihelion_time;
+        let chi = solve_universal(q, e, days);
+
+        // Kepler's equation again for the time, as it was solved for the
+        // time within half a period
+        let alpha = (1. - e) / q;
+        let (c, s) = stumpff(alpha * chi.powi(2));
+        let tau = e * chi.powi(3) * s + q * chi;
+        let x = q - chi.powi(2) * c;
+        let y = (tau - chi.powi(3) * s) * ((1. + e) / q).sqrt();
+
+        planets::in_space(
+            (x, y),
+            self.argument_of_perihelion.to_radians(),
+            self.node.to_radians(),
+            self.inclination.to_radians(),
+            (jd - JulianDay::J2000) / 36_525.,
+        )
+    }
+}
+
+impl Magnitude {
+    /// Visual magnitude for distances from the Sun and the Earth in AU and
+    /// a phase angle in degrees.
+    pub fn at(&self, radius: f64, distance: f64, phase_angle: f64) -> f64 {
+        match *self {
+            Magnitude::Asteroid { h, g } => {
+                let tan = (phase_angle.to_radians() / 2.).tan();
+                let phi1 = (-3.33 * tan.powf(0.63)).exp();
+                let phi2 = (-1.87 * tan.powf(1.22)).exp();
+                h + 5. * (radius * distance).log10() - 2.5 * ((1. - g) * phi1 + g * phi2).log10()
+            }
+            Magnitude::Comet { h, k } => h + 5. * distance.log10() + k * radius.log10(),
+        }
+    }
+}
+
+impl SmallBody {
+    /// Read a file of elements in either of the MPC's formats.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Vec<Self>> {
+        parse_elements(&fs::read_to_string(path)?)
+    }
+
+    /// Apparent position from the centre of the Earth, with the magnitude
+    /// not a number if the elements don't give one.
+    pub fn geocentric(&self, jd: JulianDay) -> PlanetPosition {
+        planets::apparent(
+            jd,
+            Theory::Vsop87,
+            |jd| self.orbit.heliocentric(jd),
+            |radius, distance, phase_angle| match self.magnitude {
+                Some(magnitude) => magnitude.at(radius, distance, phase_angle),
+                None => f64::NAN,
+            },
+        )
+    }
+
+    /// Positions from one moment to another at a fixed step, both ends
+    /// included.
+    pub fn ephemeris<Tz: TimeZone>(
+        &self,
+        from: DateTime<Tz>,
+        to: DateTime<Tz>,
+        step: Duration,
+    ) -> Result<Vec<(DateTime<Tz>, PlanetPosition)>> {
+        if step <= Duration::zero() {
+            return Err(Error::InvalidDate(format!(
+                "ephemeris step of {} is not forward",
+                step
+            )));
+        }
+
+        let mut rows = Vec::new();
+        let mut dt = from;
+        while dt <= to {
+            let tt = JulianDate::<Tt>::from_datetime(dt.clone()).jd;
+            rows.push((dt.clone(), self.geocentric(tt)));
+            dt = dt + step;
+        }
+
+        Ok(rows)
+    }
+
+    /// Write an ephemeris as a table of the date, the place in the sky, the
+    /// distances from the Earth and the Sun in AU, the elongation and phase
+    /// angle in degrees and the magnitude.
+    pub fn write_ephemeris<W: Write>(
+        &self,
+        out: &mut W,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        step: Duration,
+    ) -> Result<()> {
+        writeln!(out, "{}", self.designation)?;
+        writeln!(
+            out,
+            "{:<16}  {:>13}  {:>12}  {:>8}  {:>8}  {:>5}  {:>5}  {:>5}",
+            "Date (UTC)", "RA", "Dec", "Delta", "r", "Elong", "Phase", "Mag"
+        )?;
+
+        for (dt, position) in self.ephemeris(from, to, step)? {
+            let equatorial = position.equatorial;
+            writeln!(
+                out,
+                "{:<16}  {:>13}  {:>12}  {:>8.5}  {:>8.5}  {:>5.1}  {:>5.1}  {:>5.1}",
+                dt.format("%Y-%m-%d %H:%M").to_string(),
+                format!("{:.1}", equatorial.ra.display_hms()),
+                format!("{:.0}", equatorial.dec.display_dms()),
+                position.distance,
+                position.radius,
+                position.elongation,
+                position.phase_angle,
+                position.magnitude
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for SmallBody {
+    type Err = Error;
+
+    /// Parse a single line of elements in either format.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut bodies = parse_elements(s)?;
+        match bodies.len() {
+            1 => Ok(bodies.remove(0)),
+            n => Err(Error::Parse(format!("expected one orbit, found {}", n))),
+        }
+    }
+}
+
+/// Parse elements in the one-line format of MPCORB, for asteroids, or that
+/// of the MPC's comet files, line by line, telling the two apart by the
+/// packed epoch in MPCORB.  Any header above a line of dashes, as in
+/// `MPCORB.DAT`, is skipped.
+pub fn parse_elements(s: &str) -> Result<Vec<SmallBody>> {
+    let lines: Vec<&str> = s.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("-----"))
+        .map_or(0, |dashes| dashes + 1);
+
+    lines
+        .iter()
+        .enumerate()
+        .skip(start)
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(n, line)| {
+            let is_mpcorb = line
+                .get(20..21)
+                .is_some_and(|c| c.chars().all(|c| c.is_ascii_alphabetic()));
+            match is_mpcorb {
+                true => parse_mpcorb(line),
+                false => parse_comet(line),
+            }
+            .map_err(|error| match error {
+                Error::Parse(message) => Error::Parse(format!("{} on line {}", message, n + 1)),
+                error => error,
+            })
+        })
+        .collect()
+}
+
+/// One line of MPCORB.
+fn parse_mpcorb(line: &str) -> Result<SmallBody> {
+    let epoch = unpack_date(field(line, 21, 25))?;
+    let orbit = Orbit::from_mean_anomaly(
+        number(line, 93, 103, "semi-major axis")?,
+        number(line, 71, 79, "eccentricity")?,
+        number(line, 60, 68, "inclination")?,
+        number(line, 49, 57, "node")?,
+        number(line, 38, 46, "argument of perihelion")?,
+        number(line, 27, 35, "mean anomaly")?,
+        epoch,
+    )?;
+    let magnitude = match field(line, 9, 13) {
+        "" => None,
+        _ => Some(Magnitude::Asteroid {
+            h: number(line, 9, 13, "absolute magnitude")?,
+            g: match field(line, 15, 19) {
+                "" => DEFAULT_SLOPE,
+                _ => number(line, 15, 19, "slope parameter")?,
+            },
+        }),
+    };
+
+    Ok(SmallBody {
+        designation: designation(field(line, 167, 194), field(line, 1, 7)),
+        orbit,
+        magnitude,
+    })
+}
+
+/// One line of a comet file.
+fn parse_comet(line: &str) -> Result<SmallBody> {
+    let (year, month) = (
+        number(line, 15, 18, "year")?,
+        number(line, 20, 21, "month")?,
+    );
+    let day = number(line, 23, 29, "day")?;
+    let perihelion_time = Utc
+        .ymd_opt(year as i32, month as u32, 1)
+        .single()
+        .map(|date| JulianDay::from(date) + (day - 1.))
+        .ok_or_else(|| Error::InvalidDate(format!("perihelion in {}-{}", year, month)))?;
+
+    let orbit = Orbit {
+        perihelion_distance: number(line, 31, 39, "perihelion distance")?,
+        eccentricity: number(line, 42, 49, "eccentricity")?,
+        inclination: number(line, 72, 79, "inclination")?,
+        node: number(line, 62, 69, "node")?,
+        argument_of_perihelion: number(line, 52, 59, "argument of perihelion")?,
+        perihelion_time,
+    };
+    if !(orbit.perihelion_distance > 0.
+        && orbit.perihelion_distance.is_finite()
+        && orbit.eccentricity >= 0.
+        && orbit.eccentricity.is_finite())
+    {
+        return Err(Error::Parse(format!(
+            "no orbit with q = {} and e = {}",
+            orbit.perihelion_distance, orbit.eccentricity
+        )));
+    }
+    let magnitude = match (field(line, 92, 95), field(line, 97, 100)) {
+        ("", _) | (_, "") => None,
+        _ => Some(Magnitude::Comet {
+            h: number(line, 92, 95, "absolute magnitude")?,
+            k: number(line, 97, 100, "slope parameter")?,
+        }),
+    };
+    let packed = format!("{}{}", field(line, 1, 5), field(line, 6, 12));
+
+    Ok(SmallBody {
+        designation: designation(field(line, 103, 158), &packed),
+        orbit,
+        magnitude,
+    })
+}
+
+/// The columns from one to another, counting from one, trimmed and empty if
+/// the line stops short.
+fn field(line: &str, from: usize, to: usize) -> &str {
+    line.get(from - 1..to.min(line.len())).unwrap_or("").trim()
+}
+
+fn number(line: &str, from: usize, to: usize, what: &str) -> Result<f64> {
+    let text = field(line, from, to);
+    text.parse()
+        .map_err(|_| Error::Parse(format!("bad {}: {:?}", what, text)))
+}
+
+fn designation(readable: &str, packed: &str) -> String {
+    match readable {
+        "" => packed.to_string(),
+        _ => readable.to_string(),
+    }
+}
+
+/// A date packed into five characters, such as `K2155` for 2021 May 5.0:
+/// the century and year, then the month and day as digits or the letters
+/// `A` for 10 onwards.
+fn unpack_date(packed: &str) -> Result<JulianDay> {
+    let error = || Error::Parse(format!("bad packed date: {:?}", packed));
+    let digits = packed
+        .chars()
+        .map(|c| c.to_digit(32))
+        .collect::<Option<Vec<_>>>()
+        .filter(|digits| digits.len() == 5)
+        .ok_or_else(error)?;
+    let year = digits[0] * 100 + digits[1] * 10 + digits[2];
+
+    Utc.ymd_opt(year as i32, digits[3], digits[4])
+        .single()
+        .map(JulianDay::from)
+        .ok_or_else(error)
+}
+
+/// The Stumpff functions `c2` and `c3` of `z = α χ²`, by their series near
+/// zero, where the closed forms lose precision.
+fn stumpff(z: f64) -> (f64, f64) {
+    if z.abs() < 0.1 {
+        let (mut c, mut s, mut term) = (0., 0., 1.);
+        for k in 1..12 {
+            // term is (-z)^(k-1) / (2k)!
+            term /= (2 * k - 1) as f64 * (2 * k) as f64;
+            c += term;
+            s += term / (2 * k + 1) as f64;
+            term *= -z;
+        }
+        (c, s)
+    } else if z > 0. {
+        let root = z.sqrt();
+        ((1. - root.cos()) / z, (root - root.sin()) / (z * root))
+    } else {
+        let root = (-z).sqrt();
+        ((root.cosh() - 1.) / -z, (root.sinh() - root) / (-z * root))
+    }
+}
@@ -0,0 +1,176 @@
+//! Position of the Sun, after Meeus, *Astronomical Algorithms*, chapters 25,
+//! 27 and 28.  Positions are good to about 0.01°.  Julian days are on TT,
+//! the dynamical time of the theory; civil times are converted from UTC.
+
+use std::convert::TryFrom;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::{
+    coords::{obliquity, Ecliptic, Equatorial},
+    degrees,
+    timescale::{self, JulianDate, Tt},
+    JulianDay,
+};
+
+/// Position of the Sun at an instant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SolarPosition {
+    /// Geometric ecliptic longitude in degrees, referred to the mean equinox
+    /// of the date.
+    pub true_longitude: f64,
+    /// Ecliptic longitude in degrees, corrected for nutation and aberration.
+    pub apparent_longitude: f64,
+    /// Distance from the Earth in astronomical units.
+    pub distance: f64,
+    /// Apparent right ascension and declination.
+    pub equatorial: Equatorial,
+}
+
+/// The four principal points of the Sun's yearly path.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Season {
+    MarchEquinox,
+    JuneSolstice,
+    SeptemberEquinox,
+    DecemberSolstice,
+}
+
+/// Quantities shared by the position and the equation of time.
+struct Elements {
+    /// Julian centuries from J2000.0.
+    t: f64,
+    /// Geometric mean longitude, degrees.
+    mean_longitude: f64,
+    /// Mean anomaly, degrees.
+    mean_anomaly: f64,
+    /// Eccentricity of the Earth's orbit.
+    eccentricity: f64,
+    /// Longitude of the Moon's ascending node, for nutation, degrees.
+    omega: f64,
+}
+
+impl Elements {
+    fn new(jd: JulianDay) -> Self {
+        let t = (jd - JulianDay::J2000) / 36_525.;
+
+        Elements {
+            t,
+            mean_longitude: (280.466_46 + 36_000.769_83 * t + 0.000_303_2 * t.powi(2))
+                .rem_euclid(360.),
+            mean_anomaly: (357.529_11 + 35_999.050_29 * t - 0.000_153_7 * t.powi(2))
+                .rem_euclid(360.),
+            eccentricity: 0.016_708_634 - 0.000_042_037 * t - 0.000_000_126_7 * t.powi(2),
+            omega: 125.04 - 1934.136 * t,
+        }
+    }
+}
+
+impl SolarPosition {
+    /// The position at a Julian day on TT.
+    pub fn from_julian_day(jd: JulianDay) -> Self {
+        let el = Elements::new(jd);
+        let (t, m) = (el.t, el.mean_anomaly.to_radians());
+
+        // Equation of the center
+        let c = (1.914_602 - 0.004_817 * t - 0.000_014 * t.powi(2)) * m.sin()
+            + (0.019_993 - 0.000_101 * t) * (2. * m).sin()
+            + 0.000_289 * (3. * m).sin();
+        let true_longitude = (el.mean_longitude + c).rem_euclid(360.);
+        let true_anomaly = (el.mean_anomaly + c).to_radians();
+        let distance = 1.000_001_018 * (1. - el.eccentricity.powi(2))
+            / (1. + el.eccentricity * true_anomaly.cos());
+
+        let omega = el.omega.to_radians();
+        let apparent_longitude =
+            (true_longitude - 0.005_69 - 0.004_78 * omega.sin()).rem_euclid(360.);
+        let apparent_obliquity = obliquity(jd) + degrees(0.002_56 * omega.cos());
+        let equatorial = Ecliptic::new(degrees(apparent_longitude), degrees(0.))
+            .to_equatorial(apparent_obliquity);
+
+        SolarPosition {
+            true_longitude,
+            apparent_longitude,
+            distance,
+            equatorial,
+        }
+    }
+}
+
+impl<Tz: TimeZone> From<DateTime<Tz>> for SolarPosition {
+    fn from(dt: DateTime<Tz>) -> Self {
+        SolarPosition::from_julian_day(JulianDate::<Tt>::from_datetime(dt).jd)
+    }
+}
+
+/// Apparent minus mean solar time.  Positive when a sundial is ahead of the
+/// clock.
+pub fn equation_of_time<Tz: TimeZone>(dt: DateTime<Tz>) -> Duration {
+    let jd = JulianDate::<Tt>::from_datetime(dt).jd;
+    let el = Elements::new(jd);
+    let y = (obliquity(jd).to_radians().value / 2.).tan().powi(2);
+    let (l0, m, e) = (
+        el.mean_longitude.to_radians(),
+        el.mean_anomaly.to_radians(),
+        el.eccentricity,
+    );
+
+    let radians = y * (2. * l0).sin() - 2. * e * m.sin() + 4. * e * y * m.sin() * (2. * l0).cos()
+        - 0.5 * y.powi(2) * (4. * l0).sin()
+        - 1.25 * e.powi(2) * (2. * m).sin();
+    let minutes = radians.to_degrees() * 4.;
+
+    Duration::nanoseconds((minutes * 60e9).round() as i64)
+}
+
+impl Season {
+    /// Apparent solar longitude at the start of the season, in degrees.
+    pub fn longitude(&self) -> f64 {
+        match self {
+            Season::MarchEquinox => 0.,
+            Season::JuneSolstice => 90.,
+            Season::SeptemberEquinox => 180.,
+            Season::DecemberSolstice => 270.,
+        }
+    }
+
+    /// The instant the season starts in a year, found by iterating on the
+    /// Sun's apparent longitude.  Good to about ten minutes, or `None` for a
+    /// year beyond chrono's range.
+    pub fn start(&self, year: i32) -> Option<DateTime<Utc>> {
+        let (month, day) = match self {
+            Season::MarchEquinox => (3, 20),
+            Season::JuneSolstice => (6, 21),
+            Season::SeptemberEquinox => (9, 22),
+            Season::DecemberSolstice => (12, 21),
+        };
+        let date = Utc.ymd_opt(year, month, day).single()?;
+        let mut jd = JulianDate::<Tt>::from_datetime(date.and_hms(0, 0, 0)).jd;
+
+        // Meeus (27.1): the Sun moves about 360° in 365.24 days
+        for _ in 0..20 {
+            let sun = SolarPosition::from_julian_day(jd);
+            let correction = 58.
+                * (self.longitude() - sun.apparent_longitude)
+                    .to_radians()
+                    .sin();
+            jd = jd + correction;
+            if correction.abs() < 1e-7 {
+                break;
+            }
+        }
+
+        DateTime::try_from(JulianDate::<Tt>::new(jd).to::<timescale::Utc>()).ok()
+    }
+}
+
+/// The equinoxes and solstices of a year, in order.
+pub fn seasons(year: i32) -> Option<[(Season, DateTime<Utc>); 4]> {
+    let start = |season: Season| season.start(year).map(|start| (season, start));
+    Some([
+        start(Season::MarchEquinox)?,
+        start(Season::JuneSolstice)?,
+        start(Season::SeptemberEquinox)?,
+        start(Season::DecemberSolstice)?,
+    ])
+}
@@ -0,0 +1,427 @@
+//! Astronomical time scales and the conversions between them.
+//!
+//! * UTC is civil time, kept within a second of UT1 by leap seconds since
+//!   1972.  Earlier UTC is taken to be UT1.
+//! * TAI is atomic time, ahead of UTC by the leap seconds so far.
+//! * TT is TAI plus 32.184 s, the dynamical time the ephemerides in this
+//!   crate expect (Meeus' TD).
+//! * UT1 follows the rotation of the Earth, behind TT by ΔT.
+//! * TDB differs from TT by periodic terms of under 2 ms.
+//!
+//! Every scale converts through TAI.  Leap seconds come from a table built
+//! into the crate or read from an IERS `leap-seconds.list` file; TAI − UTC
+//! is assumed to stay at its last value after the end of the table.
+
+use std::{borrow::Cow, convert::TryFrom, fmt, fs, marker::PhantomData, path::Path, str::FromStr};
+
+use chrono::{DateTime, TimeZone};
+
+use crate::{
+    error::{Error, Result},
+    JulianDay,
+};
+
+const SECONDS_PER_DAY: f64 = 86_400.;
+
+/// MJD of the NTP epoch, 1900 January 1.0, which `leap-seconds.list` counts
+/// from.
+const NTP_EPOCH: f64 = 15_020.;
+
+/// TT − TAI in seconds.
+const TT_MINUS_TAI: f64 = 32.184;
+
+/// TAI − UTC in seconds from an MJD on UTC, up to the leap second at the end
+/// of 2016.
+#[rustfmt::skip]
+const LEAP_SECONDS: [(f64, f64); 28] = [
+    (41317., 10.), // 1972-01-01
+    (41499., 11.), // 1972-07-01
+    (41683., 12.), // 1973-01-01
+    (42048., 13.), // 1974-01-01
+    (42413., 14.), // 1975-01-01
+    (42778., 15.), // 1976-01-01
+    (43144., 16.), // 1977-01-01
+    (43509., 17.), // 1978-01-01
+    (43874., 18.), // 1979-01-01
+    (44239., 19.), // 1980-01-01
+    (44786., 20.), // 1981-07-01
+    (45151., 21.), // 1982-07-01
+    (45516., 22.), // 1983-07-01
+    (46247., 23.), // 1985-07-01
+    (47161., 24.), // 1988-01-01
+    (47892., 25.), // 1990-01-01
+    (48257., 26.), // 1991-01-01
+    (48804., 27.), // 1992-07-01
+    (49169., 28.), // 1993-07-01
+    (49534., 29.), // 1994-07-01
+    (50083., 30.), // 1996-01-01
+    (50630., 31.), // 1997-07-01
+    (51179., 32.), // 1999-01-01
+    (53736., 33.), // 2006-01-01
+    (54832., 34.), // 2009-01-01
+    (56109., 35.), // 2012-07-01
+    (57204., 36.), // 2015-07-01
+    (57754., 37.), // 2017-01-01
+];
+
+static BUILTIN: LeapSeconds = LeapSeconds {
+    steps: Cow::Borrowed(&LEAP_SECONDS),
+    expires: None,
+};
+
+/// A time scale, defined by how it converts to and from TAI.
+pub trait TimeScale: Copy + fmt::Debug + PartialEq {
+    const NAME: &'static str;
+
+    /// Julian day on TAI for a Julian day on this scale.
+    fn to_tai(jd: JulianDay, leap_seconds: &LeapSeconds) -> JulianDay;
+
+    /// Julian day on this scale for a Julian day on TAI.
+    fn from_tai(jd: JulianDay, leap_seconds: &LeapSeconds) -> JulianDay;
+}
+
+/// Coordinated Universal Time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Utc;
+
+/// International Atomic Time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tai;
+
+/// Terrestrial Time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tt;
+
+/// Universal Time, from the rotation of the Earth.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ut1;
+
+/// Barycentric Dynamical Time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tdb;
+
+/// A Julian day on a particular time scale.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JulianDate<S: TimeScale> {
+    pub jd: JulianDay,
+    scale: PhantomData<S>,
+}
+
+/// A table of leap seconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeapSeconds {
+    /// MJD on UTC from which each TAI − UTC applies, in order.
+    steps: Cow<'static, [(f64, f64)]>,
+    /// MJD on UTC after which the table may be out of date.
+    expires: Option<f64>,
+}
+
+/// ΔT = TT − UT1 in seconds, from the polynomials of Espenak and Meeus
+/// (NASA, 2006).  Good to about a second since 1900, and to minutes or hours
+/// in antiquity or far in the future.
+pub fn delta_t(jd: JulianDay) -> f64 {
+    let y = 2000. + (jd - JulianDay::J2000) / 365.25;
+    let long_term = |y: f64| -20. + 32. * ((y - 1820.) / 100.).powi(2);
+    let polynomial = |t: f64, coefficients: &[f64]| {
+        coefficients
+            .iter()
+            .rev()
+            .fold(0., |sum, coefficient| sum * t + coefficient)
+    };
+
+    match y {
+        y if y < -500. => long_term(y),
+        y if y < 500. => polynomial(
+            y / 100.,
+            &[
+                10_583.6,
+                -1_014.41,
+                33.783_11,
+                -5.952_053,
+                -0.179_845_2,
+                0.022_174_192,
+                0.009_031_652_1,
+            ],
+        ),
+        y if y < 1600. => polynomial(
+            (y - 1000.) / 100.,
+            &[
+                1_574.2,
+                -556.01,
+                71.234_72,
+                0.319_781,
+                -0.850_346_3,
+                -0.005_050_998,
+                0.008_357_207_3,
+            ],
+        ),
+        y if y < 1700. => polynomial(y - 1600., &[120., -0.980_8, -0.015_32, 1. / 7_129.]),
+        y if y < 1800. => polynomial(
+            y - 1700.,
+            &[8.83, 0.160_3, -0.005_928_5, 0.000_133_36, -1. / 1_174_000.],
+        ),
+        y if y < 1860. => polynomial(
+            y - 1800.,
+            &[
+                13.72,
+                -0.332_447,
+                0.006_861_2,
+                0.004_111_6,
+                -0.000_374_36,
+                0.000_012_127_2,
+                -0.000_000_169_9,
+                0.000_000_000_875,
+            ],
+        ),
+        y if y < 1900. => polynomial(
+            y - 1860.,
+            &[
+                7.62,
+                0.573_7,
+                -0.251_754,
+                0.016_806_68,
+                -0.000_447_362_4,
+                1. / 233_174.,
+            ],
+        ),
+        y if y < 1920. => polynomial(
+            y - 1900.,
+            &[-2.79, 1.494_119, -0.059_893_9, 0.006_196_6, -0.000_197],
+        ),
+        y if y < 1941. => polynomial(y - 1920., &[21.20, 0.844_93, -0.076_100, 0.002_093_6]),
+        y if y < 1961. => polynomial(y - 1950., &[29.07, 0.407, -1. / 233., 1. / 2_547.]),
+        y if y < 1986. => polynomial(y - 1975., &[45.45, 1.067, -1. / 260., -1. / 718.]),
+        y if y < 2005. => polynomial(
+            y - 2000.,
+            &[
+                63.86,
+                0.334_5,
+                -0.060_374,
+                0.001_727_5,
+                0.000_651_814,
+                0.000_023_735_99,
+            ],
+        ),
+        y if y < 2050. => polynomial(y - 2000., &[62.92, 0.322_17, 0.005_589]),
+        y if y < 2150. => long_term(y) - 0.562_8 * (2150. - y),
+        y => long_term(y),
+    }
+}
+
+/// Julian day of MJD zero, 1858 November 17.0.
+fn mjd_epoch() -> JulianDay {
+    JulianDay::new(2_400_000, 0.5)
+}
+
+/// TDB − TT in seconds, from the two largest periodic terms.
+fn tdb_minus_tt(jd: JulianDay) -> f64 {
+    let g = (357.53 + 0.985_600_28 * (jd - JulianDay::J2000)).to_radians();
+    0.001_657 * g.sin() + 0.000_014 * (2. * g).sin()
+}
+
+impl TimeScale for Utc {
+    const NAME: &'static str = "UTC";
+
+    fn to_tai(jd: JulianDay, leap_seconds: &LeapSeconds) -> JulianDay {
+        let offset = leap_seconds
+            .tai_minus_utc(jd - mjd_epoch())
+            .unwrap_or_else(|| delta_t(jd) - TT_MINUS_TAI);
+        jd + offset / SECONDS_PER_DAY
+    }
+
+    fn from_tai(jd: JulianDay, leap_seconds: &LeapSeconds) -> JulianDay {
+        // Each step starts at a moment on UTC, so find it on TAI
+        let mjd = jd - mjd_epoch();
+        let step = leap_seconds
+            .steps
+            .iter()
+            .rev()
+            .find(|(start, offset)| start + offset / SECONDS_PER_DAY <= mjd);
+
+        match step {
+            Some((_, offset)) => jd - offset / SECONDS_PER_DAY,
+            None => {
+                let mut utc = jd;
+                for _ in 0..3 {
+                    utc = jd - (delta_t(utc) - TT_MINUS_TAI) / SECONDS_PER_DAY;
+                }
+                utc
+            }
+        }
+    }
+}
+
+impl TimeScale for Tai {
+    const NAME: &'static str = "TAI";
+
+    fn to_tai(jd: JulianDay, _leap_seconds: &LeapSeconds) -> JulianDay {
+        jd
+    }
+
+    fn from_tai(jd: JulianDay, _leap_seconds: &LeapSeconds) -> JulianDay {
+        jd
+    }
+}
+
+impl TimeScale for Tt {
+    const NAME: &'static str = "TT";
+
+    fn to_tai(jd: JulianDay, _leap_seconds: &LeapSeconds) -> JulianDay {
+        jd - TT_MINUS_TAI / SECONDS_PER_DAY
+    }
+
+    fn from_tai(jd: JulianDay, _leap_seconds: &LeapSeconds) -> JulianDay {
+        jd + TT_MINUS_TAI / SECONDS_PER_DAY
+    }
+}
+
+impl TimeScale for Ut1 {
+    const NAME: &'static str = "UT1";
+
+    fn to_tai(jd: JulianDay, leap_seconds: &LeapSeconds) -> JulianDay {
+        let tt = jd + delta_t(jd) / SECONDS_PER_DAY;
+        Tt::to_tai(tt, leap_seconds)
+    }
+
+    fn from_tai(jd: JulianDay, leap_seconds: &LeapSeconds) -> JulianDay {
+        let tt = Tt::from_tai(jd, leap_seconds);
+        tt - delta_t(tt) / SECONDS_PER_DAY
+    }
+}
+
+impl TimeScale for Tdb {
+    const NAME: &'static str = "TDB";
+
+    fn to_tai(jd: JulianDay, leap_seconds: &LeapSeconds) -> JulianDay {
+        Tt::to_tai(jd - tdb_minus_tt(jd) / SECONDS_PER_DAY, leap_seconds)
+    }
+
+    fn from_tai(jd: JulianDay, leap_seconds: &LeapSeconds) -> JulianDay {
+        let tt = Tt::from_tai(jd, leap_seconds);
+        tt + tdb_minus_tt(tt) / SECONDS_PER_DAY
+    }
+}
+
+impl<S: TimeScale> JulianDate<S> {
+    pub fn new(jd: JulianDay) -> Self {
+        JulianDate {
+            jd,
+            scale: PhantomData,
+        }
+    }
+
+    pub fn from_mjd(mjd: f64) -> Self {
+        JulianDate::new(mjd_epoch() + mjd)
+    }
+
+    /// The moment a civil date and time falls on this scale.
+    pub fn from_datetime<Tz: TimeZone>(dt: DateTime<Tz>) -> Self {
+        JulianDate::<Utc>::from(dt).to()
+    }
+
+    /// Modified Julian day, counted from 1858 November 17.0.
+    pub fn mjd(&self) -> f64 {
+        self.jd - mjd_epoch()
+    }
+
+    /// The same moment on another scale, with the built-in leap seconds.
+    pub fn to<T: TimeScale>(&self) -> JulianDate<T> {
+        self.to_with(LeapSeconds::builtin())
+    }
+
+    /// The same moment on another scale, with a table of leap seconds.
+    pub fn to_with<T: TimeScale>(&self, leap_seconds: &LeapSeconds) -> JulianDate<T> {
+        let tai = S::to_tai(self.jd, leap_seconds);
+        JulianDate::new(T::from_tai(tai, leap_seconds))
+    }
+}
+
+impl<S: TimeScale> fmt::Display for JulianDate<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.jd, S::NAME)
+    }
+}
+
+impl<Tz: TimeZone> From<DateTime<Tz>> for JulianDate<Utc> {
+    fn from(dt: DateTime<Tz>) -> Self {
+        JulianDate::new(JulianDay::from(dt))
+    }
+}
+
+impl TryFrom<JulianDate<Utc>> for DateTime<chrono::Utc> {
+    type Error = Error;
+
+    fn try_from(jd: JulianDate<Utc>) -> Result<Self> {
+        DateTime::try_from(jd.jd)
+    }
+}
+
+impl LeapSeconds {
+    /// The table built into the crate.
+    pub fn builtin() -> &'static Self {
+        &BUILTIN
+    }
+
+    /// Read an IERS `leap-seconds.list` file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// TAI − UTC in seconds at an MJD on UTC, or `None` before 1972.
+    pub fn tai_minus_utc(&self, mjd: f64) -> Option<f64> {
+        self.steps
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= mjd)
+            .map(|(_, offset)| *offset)
+    }
+
+    /// When the file said it should be replaced, if it did.
+    pub fn expires(&self) -> Option<JulianDate<Utc>> {
+        self.expires.map(JulianDate::from_mjd)
+    }
+}
+
+impl Default for LeapSeconds {
+    fn default() -> Self {
+        BUILTIN.clone()
+    }
+}
+
+impl FromStr for LeapSeconds {
+    type Err = Error;
+
+    /// Parse the IERS format: lines of NTP seconds and TAI − UTC, with the
+    /// expiry date on a line starting `#@`.
+    fn from_str(s: &str) -> Result<Self> {
+        let number = |field: Option<&str>, line: &str| {
+            field
+                .and_then(|x| x.parse::<f64>().ok())
+                .ok_or_else(|| Error::Parse(format!("bad leap second line: {:?}", line)))
+        };
+        let mut steps = Vec::new();
+        let mut expires = None;
+
+        for line in s.lines().map(str::trim) {
+            if let Some(expiry) = line.strip_prefix("#@") {
+                let seconds = number(expiry.split_whitespace().next(), line)?;
+                expires = Some(NTP_EPOCH + seconds / SECONDS_PER_DAY);
+            } else if !line.is_empty() && !line.starts_with('#') {
+                let mut fields = line.split_whitespace();
+                let start = number(fields.next(), line)?;
+                let offset = number(fields.next(), line)?;
+                steps.push((NTP_EPOCH + start / SECONDS_PER_DAY, offset));
+            }
+        }
+
+        if steps.is_empty() {
+            return Err(Error::Parse("no leap seconds found".to_string()));
+        }
+        steps.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        Ok(LeapSeconds {
+            steps: Cow::Owned(steps),
+            expires,
+        })
+    }
+}
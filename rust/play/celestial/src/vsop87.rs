@@ -0,0 +1,1366 @@
+//! Truncated VSOP87D series for the planets, from the tables in Meeus,
+//! *Astronomical Algorithms*, appendix III.  Longitude and latitude are
+//! referred to the mean ecliptic and equinox of the date.
+
+// Phases near π are table values, not approximations of it
+#![allow(clippy::approx_constant)]
+
+use crate::JulianDay;
+
+/// Terms `A cos(B + Cτ)`, with A in 10⁻⁸ radians or AU, B in radians and C
+/// in radians per Julian millennium.
+type Series = &'static [(f64, f64, f64)];
+
+/// Series for heliocentric longitude, latitude and radius vector, each a
+/// polynomial in τ whose coefficients are sums of periodic terms.
+pub(crate) struct Vsop87 {
+    l: &'static [Series],
+    b: &'static [Series],
+    r: &'static [Series],
+}
+
+impl Vsop87 {
+    /// Heliocentric longitude and latitude in degrees, and radius vector in
+    /// AU.
+    pub(crate) fn heliocentric(&self, jd: JulianDay) -> (f64, f64, f64) {
+        let tau = (jd - JulianDay::J2000) / 365_250.;
+
+        (
+            evaluate(self.l, tau).to_degrees().rem_euclid(360.),
+            evaluate(self.b, tau).to_degrees(),
+            evaluate(self.r, tau),
+        )
+    }
+}
+
+fn evaluate(series: &[Series], tau: f64) -> f64 {
+    series
+        .iter()
+        .enumerate()
+        .map(|(power, terms)| {
+            let sum: f64 = terms.iter().map(|(a, b, c)| a * (b + c * tau).cos()).sum();
+            sum * tau.powi(power as i32)
+        })
+        .sum::<f64>()
+        / 1e8
+}
+
+pub(crate) const MERCURY: Vsop87 = Vsop87 {
+    l: &[MERCURY_L0, MERCURY_L1, MERCURY_L2, MERCURY_L3, MERCURY_L4, MERCURY_L5],
+    b: &[MERCURY_B0, MERCURY_B1, MERCURY_B2, MERCURY_B3, MERCURY_B4],
+    r: &[MERCURY_R0, MERCURY_R1, MERCURY_R2, MERCURY_R3],
+};
+
+pub(crate) const VENUS: Vsop87 = Vsop87 {
+    l: &[VENUS_L0, VENUS_L1, VENUS_L2, VENUS_L3, VENUS_L4, VENUS_L5],
+    b: &[VENUS_B0, VENUS_B1, VENUS_B2, VENUS_B3, VENUS_B4],
+    r: &[VENUS_R0, VENUS_R1, VENUS_R2, VENUS_R3, VENUS_R4],
+};
+
+pub(crate) const EARTH: Vsop87 = Vsop87 {
+    l: &[EARTH_L0, EARTH_L1, EARTH_L2, EARTH_L3, EARTH_L4, EARTH_L5],
+    b: &[EARTH_B0, EARTH_B1],
+    r: &[EARTH_R0, EARTH_R1, EARTH_R2, EARTH_R3, EARTH_R4],
+};
+
+pub(crate) const MARS: Vsop87 = Vsop87 {
+    l: &[MARS_L0, MARS_L1, MARS_L2, MARS_L3, MARS_L4, MARS_L5],
+    b: &[MARS_B0, MARS_B1, MARS_B2, MARS_B3, MARS_B4],
+    r: &[MARS_R0, MARS_R1, MARS_R2, MARS_R3, MARS_R4],
+};
+
+pub(crate) const JUPITER: Vsop87 = Vsop87 {
+    l: &[JUPITER_L0, JUPITER_L1, JUPITER_L2, JUPITER_L3, JUPITER_L4, JUPITER_L5],
+    b: &[JUPITER_B0, JUPITER_B1, JUPITER_B2, JUPITER_B3, JUPITER_B4],
+    r: &[JUPITER_R0, JUPITER_R1, JUPITER_R2, JUPITER_R3, JUPITER_R4, JUPITER_R5],
+};
+
+pub(crate) const SATURN: Vsop87 = Vsop87 {
+    l: &[SATURN_L0, SATURN_L1, SATURN_L2, SATURN_L3, SATURN_L4, SATURN_L5],
+    b: &[SATURN_B0, SATURN_B1, SATURN_B2, SATURN_B3, SATURN_B4],
+    r: &[SATURN_R0, SATURN_R1, SATURN_R2, SATURN_R3, SATURN_R4, SATURN_R5],
+};
+
+pub(crate) const URANUS: Vsop87 = Vsop87 {
+    l: &[URANUS_L0, URANUS_L1, URANUS_L2, URANUS_L3, URANUS_L4],
+    b: &[URANUS_B0, URANUS_B1, URANUS_B2, URANUS_B3, URANUS_B4],
+    r: &[URANUS_R0, URANUS_R1, URANUS_R2, URANUS_R3, URANUS_R4],
+};
+
+pub(crate) const NEPTUNE: Vsop87 = Vsop87 {
+    l: &[NEPTUNE_L0, NEPTUNE_L1, NEPTUNE_L2, NEPTUNE_L3, NEPTUNE_L4],
+    b: &[NEPTUNE_B0, NEPTUNE_B1, NEPTUNE_B2, NEPTUNE_B3, NEPTUNE_B4],
+    r: &[NEPTUNE_R0, NEPTUNE_R1, NEPTUNE_R2, NEPTUNE_R3],
+};
+
+const MERCURY_L0: Series = &[
+    (440250710., 0., 0.),
+    (40989415., 1.48302034, 26087.90314157),
+    (5046294., 4.4778549, 52175.8062831),
+    (855347., 1.165203, 78263.709425),
+    (165590., 4.119692, 104351.612566),
+    (34562., 0.77931, 130439.51571),
+    (7583., 3.7135, 156527.4188),
+    (3560., 1.512, 1109.3786),
+    (1803., 4.1033, 5661.332),
+    (1726., 0.3583, 182615.322),
+    (1590., 2.9951, 25028.5212),
+    (1365., 4.5992, 27197.2817),
+    (1017., 0.8803, 31749.2352),
+    (714., 1.541, 24978.525),
+    (644., 5.303, 21535.95),
+    (451., 6.05, 51116.424),
+    (404., 3.282, 208703.225),
+    (352., 5.242, 20426.571),
+    (345., 2.792, 15874.618),
+    (343., 5.765, 955.6),
+    (339., 5.863, 25558.212),
+    (325., 1.337, 53285.185),
+    (273., 2.495, 529.691),
+    (264., 3.917, 57837.138),
+    (260., 0.987, 4551.953),
+    (239., 0.113, 1059.382),
+    (235., 0.267, 11322.664),
+    (217., 0.66, 13521.751),
+    (209., 2.092, 47623.853),
+    (183., 2.629, 27043.503),
+    (182., 2.434, 25661.305),
+    (176., 4.536, 51066.428),
+    (173., 2.452, 24498.83),
+    (142., 3.36, 37410.567),
+    (138., 0.291, 10213.286),
+];
+const MERCURY_L1: Series = &[
+    (2608814706223., 0., 0.),
+    (1126008., 6.2170397, 26087.9031416),
+    (303471., 3.055655, 52175.806283),
+    (80538., 6.10455, 78263.70942),
+    (21245., 2.83532, 104351.61257),
+    (5592., 5.8268, 130439.5157),
+    (1472., 2.5185, 156527.4188),
+    (388., 5.48, 182615.322),
+    (352., 3.052, 1109.379),
+    (103., 2.149, 208703.225),
+    (94., 6.12, 27197.28),
+    (91., 0., 24978.52),
+    (52., 5.62, 5661.33),
+    (44., 4.57, 25028.52),
+    (28., 3.04, 51066.43),
+    (27., 5.09, 234791.13),
+];
+const MERCURY_L2: Series = &[
+    (53050., 0., 0.),
+    (16904., 4.69072, 26087.90314),
+    (7397., 1.3474, 52175.8063),
+    (3018., 4.4564, 78263.7094),
+    (1107., 1.2623, 104351.6126),
+    (378., 4.32, 130439.516),
+    (123., 1.069, 156527.419),
+    (39., 4.08, 182615.32),
+    (15., 4.63, 1109.38),
+    (12., 0.79, 208703.23),
+];
+const MERCURY_L3: Series = &[
+    (188., 0.035, 52175.806),
+    (142., 3.125, 26087.903),
+    (97., 3., 78263.71),
+    (44., 6.02, 104351.61),
+    (35., 0., 0.),
+    (18., 2.78, 130439.52),
+    (7., 5.82, 156527.42),
+    (3., 2.57, 182615.32),
+];
+const MERCURY_L4: Series = &[
+    (114., 3.1416, 0.),
+    (2., 2.03, 26087.9),
+    (2., 1.42, 78263.71),
+    (2., 4.5, 52175.81),
+    (1., 4.5, 104351.61),
+    (1., 1.27, 130439.52),
+];
+const MERCURY_L5: Series = &[
+    (1., 3.14, 0.),
+];
+const MERCURY_B0: Series = &[
+    (11737529., 1.98357499, 26087.90314157),
+    (2388077., 5.0373896, 52175.8062831),
+    (1222840., 3.1415927, 0.),
+    (543252., 1.796444, 78263.709425),
+    (129779., 4.832325, 104351.612566),
+    (31867., 1.58088, 130439.51571),
+    (7963., 4.6097, 156527.4188),
+    (2014., 1.3532, 182615.322),
+    (514., 4.378, 208703.225),
+    (209., 2.02, 24978.525),
+    (208., 4.918, 27197.282),
+    (132., 1.119, 234791.128),
+    (121., 1.813, 53285.185),
+    (100., 5.657, 20426.571),
+];
+const MERCURY_B1: Series = &[
+    (429151., 3.501698, 26087.903142),
+    (146234., 3.141593, 0.),
+    (22675., 0.01515, 52175.80628),
+    (10895., 0.4854, 78263.70942),
+    (6353., 3.4294, 104351.6126),
+    (2496., 0.1605, 130439.5157),
+    (860., 3.185, 156527.419),
+    (278., 6.21, 182615.322),
+    (86., 2.95, 208703.23),
+    (28., 0.29, 27197.28),
+    (26., 5.98, 234791.13),
+];
+const MERCURY_B2: Series = &[
+    (11831., 4.79066, 26087.90314),
+    (1914., 0., 0.),
+    (1045., 1.2122, 52175.8063),
+    (266., 4.434, 78263.709),
+    (170., 1.623, 104351.613),
+    (96., 4.8, 130439.52),
+    (45., 1.61, 156527.42),
+    (18., 4.67, 182615.32),
+    (7., 1.43, 208703.23),
+];
+const MERCURY_B3: Series = &[
+    (235., 0.354, 26087.903),
+    (161., 0., 0.),
+    (19., 4.36, 52175.81),
+    (6., 2.51, 78263.71),
+    (5., 6.14, 104351.61),
+    (3., 3.12, 130439.52),
+    (2., 6.27, 156527.42),
+];
+const MERCURY_B4: Series = &[
+    (4., 1.75, 26087.9),
+    (1., 3.14, 0.),
+];
+const MERCURY_R0: Series = &[
+    (39528272., 0., 0.),
+    (7834132., 6.1923372, 26087.9031416),
+    (795526., 2.959897, 52175.806283),
+    (121282., 6.010642, 78263.709425),
+    (21922., 2.7782, 104351.61257),
+    (4354., 5.8289, 130439.5157),
+    (918., 2.597, 156527.419),
+    (290., 1.424, 25028.521),
+    (260., 3.028, 27197.282),
+    (202., 5.647, 182615.322),
+    (201., 5.592, 31749.235),
+    (142., 6.253, 24978.525),
+    (100., 3.734, 21535.95),
+];
+const MERCURY_R1: Series = &[
+    (217348., 4.656172, 26087.903142),
+    (44142., 1.42386, 52175.80628),
+    (10094., 4.47466, 78263.70942),
+    (2433., 1.2423, 104351.6126),
+    (1624., 0., 0.),
+    (604., 4.293, 130439.516),
+    (153., 1.061, 156527.419),
+    (39., 4.11, 182615.32),
+];
+const MERCURY_R2: Series = &[
+    (3118., 3.0823, 26087.9031),
+    (1245., 6.1518, 52175.8063),
+    (425., 2.926, 78263.709),
+    (136., 5.98, 104351.613),
+    (42., 2.75, 130439.52),
+    (22., 3.14, 0.),
+    (13., 5.8, 156527.42),
+];
+const MERCURY_R3: Series = &[
+    (33., 1.68, 26087.9),
+    (24., 4.63, 52175.81),
+    (12., 1.39, 78263.71),
+    (5., 4.44, 104351.61),
+    (2., 1.21, 130439.52),
+];
+
+const VENUS_L0: Series = &[
+    (317614667., 0., 0.),
+    (1353968., 5.5931332, 10213.2855462),
+    (89892., 5.3065, 20426.57109),
+    (5477., 4.4163, 7860.4194),
+    (3456., 2.6996, 11790.6291),
+    (2372., 2.9938, 3930.2097),
+    (1664., 4.2502, 1577.3435),
+    (1438., 4.1575, 9683.5946),
+    (1317., 5.1867, 26.2983),
+    (1201., 6.1536, 30639.8566),
+    (769., 0.816, 9437.763),
+    (761., 1.95, 529.691),
+    (708., 1.065, 775.523),
+    (585., 3.998, 191.448),
+    (500., 4.123, 15720.839),
+    (429., 3.586, 19367.189),
+    (327., 5.677, 5507.553),
+    (326., 4.591, 10404.734),
+];
+const VENUS_L1: Series = &[
+    (1021352943053., 0., 0.),
+    (95708., 2.46424, 10213.28555),
+    (14445., 0.51625, 20426.57109),
+    (213., 1.795, 30639.857),
+    (174., 2.655, 26.298),
+    (152., 6.106, 1577.344),
+    (82., 5.7, 191.45),
+    (70., 2.68, 9437.76),
+    (52., 3.6, 775.52),
+    (38., 1.03, 529.69),
+    (30., 1.25, 5507.55),
+    (25., 6.11, 10404.73),
+];
+const VENUS_L2: Series = &[
+    (54127., 0., 0.),
+    (3891., 0.3451, 10213.2855),
+    (1338., 2.0201, 20426.5711),
+    (24., 2.05, 26.3),
+    (19., 3.54, 30639.86),
+];
+const VENUS_L3: Series = &[
+    (136., 4.804, 10213.286),
+    (78., 3.67, 20426.57),
+    (26., 0., 0.),
+];
+const VENUS_L4: Series = &[
+    (114., 3.1416, 0.),
+    (3., 5.21, 20426.57),
+    (2., 2.51, 10213.29),
+];
+const VENUS_L5: Series = &[
+    (1., 3.14, 0.),
+];
+const VENUS_B0: Series = &[
+    (5923638., 0.2670278, 10213.2855462),
+    (40108., 1.14737, 20426.57109),
+    (32815., 3.14159, 0.),
+    (1011., 1.0895, 30639.8566),
+    (149., 6.254, 18073.705),
+    (138., 0.86, 1577.344),
+    (130., 3.672, 9437.763),
+    (120., 3.705, 2352.866),
+    (108., 4.539, 22003.915),
+];
+const VENUS_B1: Series = &[
+    (513348., 1.803643, 10213.285546),
+    (4380., 3.3862, 20426.5711),
+    (199., 0., 0.),
+    (197., 2.53, 30639.857),
+];
+const VENUS_B2: Series = &[
+    (22378., 3.38509, 10213.28555),
+    (282., 0., 0.),
+    (173., 5.256, 20426.571),
+    (27., 3.87, 30639.86),
+];
+const VENUS_B3: Series = &[
+    (647., 4.992, 10213.286),
+    (20., 3.14, 0.),
+    (6., 0.77, 20426.57),
+    (3., 5.44, 30639.86),
+];
+const VENUS_B4: Series = &[
+    (14., 0.32, 10213.29),
+];
+const VENUS_R0: Series = &[
+    (72334821., 0., 0.),
+    (489824., 4.021518, 10213.285546),
+    (1658., 4.9021, 20426.5711),
+    (1632., 2.8455, 7860.4194),
+    (1378., 1.1285, 11790.6291),
+    (498., 2.587, 9683.595),
+    (374., 1.423, 3930.21),
+    (264., 5.529, 9437.763),
+    (237., 2.551, 15720.839),
+    (222., 2.013, 19367.189),
+    (126., 2.728, 1577.344),
+    (119., 3.02, 10404.734),
+];
+const VENUS_R1: Series = &[
+    (34551., 0.89199, 10213.28555),
+    (234., 1.772, 20426.571),
+    (234., 3.142, 0.),
+];
+const VENUS_R2: Series = &[
+    (1407., 5.0637, 10213.2855),
+    (16., 5.47, 20426.57),
+    (13., 0., 0.),
+];
+const VENUS_R3: Series = &[
+    (50., 3.22, 10213.29),
+];
+const VENUS_R4: Series = &[
+    (1., 0.92, 10213.29),
+];
+
+const EARTH_L0: Series = &[
+    (175347046., 0., 0.),
+    (3341656., 4.6692568, 6283.07585),
+    (34894., 4.6261, 12566.1517),
+    (3497., 2.7441, 5753.3849),
+    (3418., 2.8289, 3.5231),
+    (3136., 3.6277, 77713.7715),
+    (2676., 4.4181, 7860.4194),
+    (2343., 6.1352, 3930.2097),
+    (1324., 0.7425, 11506.7698),
+    (1273., 2.0371, 529.691),
+    (1199., 1.1096, 1577.3435),
+    (990., 5.233, 5884.927),
+    (902., 2.045, 26.298),
+    (857., 3.508, 398.149),
+    (780., 1.179, 5223.694),
+    (753., 2.533, 5507.553),
+    (505., 4.583, 18849.228),
+    (492., 4.205, 775.523),
+    (357., 2.92, 0.067),
+    (317., 5.849, 11790.629),
+    (284., 1.899, 796.298),
+    (271., 0.315, 10977.079),
+    (243., 0.345, 5486.778),
+    (206., 4.806, 2544.314),
+    (205., 1.869, 5573.143),
+    (202., 2.458, 6069.777),
+    (156., 0.833, 213.299),
+    (132., 3.411, 2942.463),
+    (126., 1.083, 20.775),
+    (115., 0.645, 0.98),
+    (103., 0.636, 4694.003),
+    (102., 0.976, 15720.839),
+    (102., 4.267, 7.114),
+    (99., 6.21, 2146.17),
+    (98., 0.68, 155.42),
+    (86., 5.98, 161000.69),
+    (85., 1.3, 6275.96),
+    (85., 3.67, 71430.7),
+    (80., 1.81, 17260.15),
+];
+const EARTH_L1: Series = &[
+    (628331966747., 0., 0.),
+    (206059., 2.678235, 6283.07585),
+    (4303., 2.6351, 12566.1517),
+    (425., 1.59, 3.523),
+    (119., 5.796, 26.298),
+    (109., 2.966, 1577.344),
+    (93., 2.59, 18849.23),
+    (72., 1.14, 529.69),
+    (68., 1.87, 398.15),
+    (67., 4.41, 5507.55),
+    (59., 2.89, 5223.69),
+    (56., 2.17, 155.42),
+    (45., 0.4, 796.3),
+    (36., 0.47, 775.52),
+    (29., 2.65, 7.11),
+    (21., 5.34, 0.98),
+    (19., 1.85, 5486.78),
+    (19., 4.97, 213.3),
+    (17., 2.99, 6275.96),
+    (16., 0.03, 2544.31),
+];
+const EARTH_L2: Series = &[
+    (52919., 0., 0.),
+    (8720., 1.0721, 6283.0758),
+    (309., 0.867, 12566.152),
+    (27., 0.05, 3.52),
+    (16., 5.19, 26.3),
+    (16., 3.68, 155.42),
+    (10., 0.76, 18849.23),
+    (9., 2.06, 77713.77),
+    (7., 0.83, 775.52),
+    (5., 4.66, 1577.34),
+];
+const EARTH_L3: Series = &[
+    (289., 5.844, 6283.076),
+    (35., 0., 0.),
+    (17., 5.49, 12566.15),
+    (3., 5.2, 155.42),
+    (1., 4.72, 3.52),
+];
+const EARTH_L4: Series = &[
+    (114., 3.142, 0.),
+    (8., 4.13, 6283.08),
+    (1., 3.84, 12566.15),
+];
+const EARTH_L5: Series = &[
+    (1., 3.14, 0.),
+];
+const EARTH_B0: Series = &[
+    (280., 3.199, 84334.662),
+    (102., 5.422, 5507.553),
+    (80., 3.88, 5223.69),
+    (44., 3.7, 2352.87),
+    (32., 4., 1577.34),
+];
+const EARTH_B1: Series = &[
+    (9., 3.9, 5507.55),
+    (6., 1.73, 5223.69),
+];
+const EARTH_R0: Series = &[
+    (100013989., 0., 0.),
+    (1670700., 3.0984635, 6283.07585),
+    (13956., 3.05525, 12566.1517),
+    (3084., 5.1985, 77713.7715),
+    (1628., 1.1739, 5753.3849),
+    (1576., 2.8469, 7860.4194),
+    (925., 5.453, 11506.77),
+    (542., 4.564, 3930.21),
+    (472., 3.661, 5884.927),
+    (346., 0.964, 5507.553),
+    (329., 5.9, 5223.694),
+    (307., 0.299, 5573.143),
+    (243., 4.273, 11790.629),
+    (212., 5.847, 1577.344),
+    (186., 5.022, 10977.079),
+    (175., 3.012, 18849.228),
+    (110., 5.055, 5486.778),
+    (98., 0.89, 6069.78),
+    (86., 5.69, 15720.84),
+    (86., 1.27, 161000.69),
+];
+const EARTH_R1: Series = &[
+    (103019., 1.10749, 6283.07585),
+    (1721., 1.0644, 12566.1517),
+    (702., 3.142, 0.),
+    (32., 1.02, 18849.23),
+    (31., 2.84, 5507.55),
+    (25., 1.32, 5223.69),
+    (18., 1.42, 1577.34),
+    (10., 5.91, 10977.08),
+    (9., 1.42, 6275.96),
+    (9., 0.27, 5486.78),
+];
+const EARTH_R2: Series = &[
+    (4359., 5.7846, 6283.0758),
+    (124., 5.579, 12566.152),
+    (12., 3.14, 0.),
+    (9., 3.63, 77713.77),
+    (6., 1.87, 5573.14),
+    (3., 5.47, 18849.23),
+];
+const EARTH_R3: Series = &[
+    (145., 4.273, 6283.076),
+    (7., 3.92, 12566.15),
+];
+const EARTH_R4: Series = &[
+    (4., 2.56, 6283.08),
+];
+
+const MARS_L0: Series = &[
+    (620347712., 0., 0.),
+    (18656368., 5.050371, 3340.6124267),
+    (1108217., 5.4009984, 6681.2248534),
+    (91798., 5.75479, 10021.83728),
+    (27745., 5.9705, 3.52312),
+    (12316., 0.84956, 2810.92146),
+    (10610., 2.93959, 2281.2305),
+    (8927., 4.157, 0.0173),
+    (8716., 6.1101, 13362.4497),
+    (7775., 3.3397, 5621.8429),
+    (6798., 0.3646, 398.149),
+    (4161., 0.2281, 2942.4634),
+    (3575., 1.6619, 2544.3144),
+    (3075., 0.857, 191.4483),
+    (2938., 6.0789, 0.0673),
+    (2628., 0.6481, 3337.0893),
+    (2580., 0.03, 3344.1355),
+    (2389., 5.039, 796.298),
+    (1799., 0.6563, 529.691),
+    (1546., 2.9158, 1751.5395),
+    (1528., 1.1498, 6151.5339),
+    (1286., 3.068, 2146.1654),
+    (1264., 3.6228, 5092.152),
+    (1025., 3.6933, 8962.4553),
+];
+const MARS_L1: Series = &[
+    (334085627474., 0., 0.),
+    (1458227., 3.6042605, 3340.6124267),
+    (164901., 3.926313, 6681.224853),
+    (19963., 4.26594, 10021.83728),
+    (3452., 4.7321, 3.5231),
+    (2485., 4.6128, 13362.4497),
+    (842., 4.459, 2281.23),
+    (538., 5.016, 398.149),
+    (521., 4.994, 3344.136),
+    (433., 2.561, 191.448),
+    (430., 5.316, 155.42),
+    (382., 3.539, 796.298),
+    (314., 4.963, 16703.062),
+    (283., 3.16, 2544.314),
+    (206., 4.569, 2146.165),
+    (169., 1.329, 3337.089),
+    (158., 4.185, 1751.54),
+    (134., 2.233, 0.98),
+    (134., 5.974, 1748.016),
+    (118., 6.024, 6151.534),
+];
+const MARS_L2: Series = &[
+    (58016., 2.04979, 3340.61243),
+    (54188., 0., 0.),
+    (13908., 2.45742, 6681.22485),
+    (2465., 2.8, 10021.8373),
+    (398., 3.141, 13362.45),
+    (222., 3.194, 3.523),
+    (121., 0.543, 155.42),
+    (62., 3.49, 16703.06),
+    (54., 3.54, 3344.14),
+    (34., 6., 2281.23),
+];
+const MARS_L3: Series = &[
+    (1482., 0.4443, 3340.6124),
+    (662., 0.885, 6681.225),
+    (188., 1.288, 10021.837),
+    (41., 1.65, 13362.45),
+    (26., 0., 0.),
+    (23., 2.05, 155.42),
+    (10., 1.58, 3.52),
+    (8., 2., 16703.06),
+];
+const MARS_L4: Series = &[
+    (114., 3.1416, 0.),
+    (29., 5.64, 6681.22),
+    (24., 5.14, 3340.61),
+    (11., 6.03, 10021.84),
+];
+const MARS_L5: Series = &[
+    (1., 3.14, 0.),
+];
+const MARS_B0: Series = &[
+    (3197135., 3.7683204, 3340.6124267),
+    (298033., 4.10617, 6681.224853),
+    (289105., 0., 0.),
+    (31366., 4.44651, 10021.83728),
+    (3484., 4.7881, 13362.4497),
+    (443., 5.026, 3344.136),
+    (443., 5.652, 3337.089),
+    (399., 5.131, 16703.062),
+    (293., 3.793, 2281.23),
+    (182., 6.136, 6151.534),
+];
+const MARS_B1: Series = &[
+    (350069., 5.368478, 3340.612427),
+    (14116., 3.14159, 0.),
+    (9671., 5.4788, 6681.2249),
+    (1472., 3.2021, 10021.8373),
+    (426., 3.408, 13362.45),
+    (102., 0.776, 3337.089),
+    (79., 3.72, 16703.06),
+];
+const MARS_B2: Series = &[
+    (16727., 0.60221, 3340.61243),
+    (4987., 3.1416, 0.),
+    (302., 3.559, 6681.225),
+    (26., 1.9, 13362.45),
+];
+const MARS_B3: Series = &[
+    (607., 1.981, 3340.612),
+    (43., 0., 0.),
+    (14., 1.8, 6681.22),
+];
+const MARS_B4: Series = &[
+    (13., 0., 0.),
+    (11., 3.46, 3340.61),
+];
+const MARS_R0: Series = &[
+    (153033488., 0., 0.),
+    (14184953., 3.47971284, 3340.6124267),
+    (660776., 3.817834, 6681.224853),
+    (46179., 4.15595, 10021.83728),
+    (8110., 5.5596, 2810.9215),
+    (7485., 1.7724, 5621.8429),
+    (5523., 1.3644, 2281.2305),
+    (3825., 4.4941, 13362.4497),
+    (2484., 4.9255, 2942.4634),
+    (2307., 0.0908, 2544.3144),
+    (1960., 4.7425, 3337.0893),
+    (1167., 2.1126, 5092.152),
+    (1103., 5.0091, 398.149),
+    (992., 5.839, 6151.534),
+    (899., 4.408, 529.691),
+];
+const MARS_R1: Series = &[
+    (1107433., 2.0325052, 3340.6124267),
+    (103176., 2.370718, 6681.224853),
+    (12877., 0., 0.),
+    (10816., 2.70888, 10021.83728),
+    (1195., 3.047, 13362.4497),
+    (439., 2.888, 2281.23),
+    (396., 3.423, 3344.136),
+];
+const MARS_R2: Series = &[
+    (44242., 0.47931, 3340.61243),
+    (8138., 0.87, 6681.2249),
+    (1275., 1.2259, 10021.8373),
+    (187., 1.573, 13362.45),
+    (52., 3.14, 0.),
+];
+const MARS_R3: Series = &[
+    (1113., 5.1499, 3340.6124),
+    (424., 5.613, 6681.225),
+    (100., 5.997, 10021.837),
+    (20., 0.08, 13362.45),
+];
+const MARS_R4: Series = &[
+    (20., 3.58, 3340.61),
+    (16., 4.05, 6681.22),
+];
+
+const JUPITER_L0: Series = &[
+    (59954691., 0., 0.),
+    (9695899., 5.0619179, 529.6909651),
+    (573610., 1.444062, 7.113547),
+    (306389., 5.417347, 1059.38193),
+    (97178., 4.14265, 632.7837),
+    (72903., 3.64043, 522.57742),
+    (64264., 3.41145, 103.09277),
+    (39806., 2.29377, 419.48464),
+    (38858., 1.27232, 316.39187),
+    (27965., 1.78455, 536.80451),
+    (13590., 5.77481, 1589.0729),
+    (8769., 3.63, 949.1756),
+    (8246., 3.5823, 206.1855),
+    (7368., 5.081, 735.8765),
+    (6263., 0.025, 213.2991),
+    (6114., 4.5132, 1162.4747),
+    (5305., 4.1863, 1052.2684),
+    (5305., 1.3067, 14.2271),
+    (4905., 1.3208, 110.2063),
+    (4647., 4.6996, 3.9322),
+    (3045., 4.3168, 426.5982),
+    (2610., 1.5667, 846.0828),
+    (2028., 1.0638, 3.1814),
+];
+const JUPITER_L1: Series = &[
+    (52993480757., 0., 0.),
+    (489741., 4.220667, 529.690965),
+    (228919., 6.026475, 7.113547),
+    (27655., 4.57266, 1059.38193),
+    (20721., 5.45939, 522.57742),
+    (12106., 0.16986, 536.80451),
+    (6068., 4.4242, 103.0928),
+    (5434., 3.9848, 419.4846),
+    (4238., 5.8901, 14.2271),
+    (2212., 5.2677, 206.1855),
+    (1746., 4.9267, 1589.0729),
+    (1296., 5.5513, 3.1814),
+    (1173., 5.8565, 1052.2684),
+    (1163., 0.5145, 3.9322),
+    (1099., 5.307, 515.4639),
+    (1007., 0.4648, 735.8765),
+    (1004., 3.1504, 426.5982),
+];
+const JUPITER_L2: Series = &[
+    (47234., 4.32148, 7.11355),
+    (38966., 0., 0.),
+    (30629., 2.93021, 529.69097),
+    (3189., 1.055, 522.5774),
+    (2729., 4.8455, 536.8045),
+    (2723., 3.4141, 1059.3819),
+    (1721., 4.1873, 14.2271),
+    (383., 5.768, 419.485),
+    (378., 0.76, 515.464),
+    (367., 6.055, 103.093),
+    (337., 3.786, 3.181),
+    (308., 0.694, 206.186),
+    (218., 3.814, 1589.073),
+    (199., 5.34, 1066.495),
+];
+const JUPITER_L3: Series = &[
+    (6502., 2.5986, 7.1135),
+    (1357., 1.3464, 529.691),
+    (471., 2.475, 14.227),
+    (417., 3.245, 536.805),
+    (353., 2.974, 522.577),
+    (155., 2.076, 1059.382),
+    (87., 2.51, 515.46),
+    (44., 0., 0.),
+];
+const JUPITER_L4: Series = &[
+    (669., 0.853, 7.114),
+    (114., 3.142, 0.),
+    (100., 0.743, 14.227),
+    (50., 1.65, 536.8),
+    (44., 5.82, 529.69),
+    (32., 4.86, 522.58),
+];
+const JUPITER_L5: Series = &[
+    (50., 5.26, 7.11),
+    (16., 5.25, 14.23),
+    (4., 0.01, 536.8),
+];
+const JUPITER_B0: Series = &[
+    (2268616., 3.5585261, 529.6909651),
+    (110090., 0., 0.),
+    (109972., 3.908093, 1059.38193),
+    (8101., 3.6051, 522.5774),
+    (6438., 0.3063, 536.8045),
+    (6044., 4.2588, 1589.0729),
+    (1107., 2.9853, 1162.4747),
+    (944., 1.675, 426.598),
+    (942., 2.936, 1052.268),
+    (894., 1.754, 7.114),
+];
+const JUPITER_B1: Series = &[
+    (177352., 5.701665, 529.690965),
+    (3230., 5.7794, 1059.3819),
+    (3081., 5.4746, 522.5774),
+    (2212., 4.7348, 536.8045),
+    (1694., 3.1416, 0.),
+    (346., 4.746, 1052.268),
+    (234., 5.189, 1066.495),
+    (196., 6.186, 7.114),
+    (150., 3.927, 1589.073),
+];
+const JUPITER_B2: Series = &[
+    (8094., 1.4632, 529.691),
+    (813., 3.1416, 0.),
+    (742., 0.957, 522.577),
+    (399., 2.899, 536.805),
+    (342., 1.447, 1059.382),
+];
+const JUPITER_B3: Series = &[
+    (252., 3.381, 529.691),
+    (122., 2.733, 522.577),
+    (49., 1.04, 536.8),
+    (11., 2.31, 1578.64),
+];
+const JUPITER_B4: Series = &[
+    (15., 4.53, 522.58),
+    (5., 4.47, 529.69),
+    (4., 5.44, 536.8),
+];
+const JUPITER_R0: Series = &[
+    (520887429., 0., 0.),
+    (25209327., 3.4910864, 529.6909651),
+    (610600., 3.841154, 1059.38193),
+    (282029., 2.574199, 632.783739),
+    (187647., 2.075904, 522.577418),
+    (86793., 0.71001, 419.48464),
+    (72063., 0.21466, 536.80451),
+    (65517., 5.97996, 316.39187),
+    (30135., 2.16132, 949.17561),
+    (29135., 1.67759, 103.09277),
+    (23947., 0.27458, 7.11355),
+    (23453., 3.54023, 735.87651),
+    (22284., 4.19363, 1589.0729),
+    (13033., 2.96043, 1162.4747),
+    (12749., 2.7155, 1052.26838),
+    (9703., 1.9067, 206.1855),
+    (9161., 4.4135, 213.2991),
+    (7895., 2.4791, 426.5982),
+    (7058., 2.1818, 1265.5675),
+    (6138., 6.2642, 846.0828),
+    (5477., 5.6573, 639.8973),
+    (4170., 2.0161, 515.4639),
+    (4137., 2.7222, 625.6702),
+    (3503., 0.5653, 1066.4955),
+];
+const JUPITER_R1: Series = &[
+    (1271802., 2.6493751, 529.6909651),
+    (61662., 3.00076, 1059.38193),
+    (53444., 3.89718, 522.57742),
+    (41390., 0., 0.),
+    (31185., 4.88277, 536.80451),
+    (11847., 2.4133, 419.48464),
+    (9166., 4.7598, 7.1135),
+    (3404., 3.3469, 1589.0729),
+    (3203., 5.2108, 735.8765),
+    (3176., 2.793, 103.0928),
+    (2806., 3.7422, 515.4639),
+    (2677., 4.3305, 1052.2684),
+    (2600., 3.6344, 206.1855),
+    (2412., 1.4695, 426.5982),
+    (2101., 3.9276, 639.8973),
+    (1646., 4.4163, 1066.4955),
+    (1641., 4.4163, 625.6702),
+    (1050., 3.1611, 213.2991),
+    (1025., 2.5543, 412.3711),
+    (806., 2.678, 632.784),
+];
+const JUPITER_R2: Series = &[
+    (79645., 1.35866, 529.69097),
+    (8252., 5.7777, 522.5774),
+    (7030., 3.2748, 536.8045),
+    (5314., 1.8384, 1059.3819),
+    (1861., 2.9768, 7.1135),
+    (964., 5.48, 515.464),
+    (836., 4.199, 419.485),
+    (498., 3.142, 0.),
+    (427., 2.228, 639.897),
+    (406., 3.783, 1066.495),
+];
+const JUPITER_R3: Series = &[
+    (3519., 6.058, 529.691),
+    (1073., 1.6732, 536.8045),
+    (916., 1.413, 522.577),
+    (342., 0.523, 1059.382),
+    (255., 1.196, 7.114),
+    (222., 0.952, 515.464),
+    (90., 3.14, 0.),
+];
+const JUPITER_R4: Series = &[
+    (129., 0.084, 536.805),
+    (113., 4.249, 529.691),
+    (83., 3.3, 522.58),
+    (38., 2.73, 515.46),
+    (27., 5.69, 7.11),
+];
+const JUPITER_R5: Series = &[
+    (11., 4.75, 536.8),
+];
+
+const SATURN_L0: Series = &[
+    (87401354., 0., 0.),
+    (11107660., 3.9620509, 213.29909544),
+    (1414151., 4.5858152, 7.113547),
+    (398379., 0.52112, 206.185548),
+    (350769., 3.303299, 426.598191),
+    (206816., 0.246584, 103.092774),
+    (79271., 3.84007, 220.41264),
+    (23990., 4.66977, 110.20632),
+    (16574., 0.43719, 419.48464),
+    (15820., 0.93809, 632.78374),
+    (15054., 2.7167, 639.89729),
+    (14907., 5.76903, 316.39187),
+    (14610., 1.56519, 3.93215),
+    (13160., 4.44891, 14.22709),
+    (13005., 5.98119, 11.0457),
+    (10725., 3.1294, 202.2534),
+    (6126., 1.7633, 277.035),
+    (5863., 0.2366, 529.691),
+    (5228., 4.2078, 3.1814),
+    (5020., 3.1779, 433.7117),
+    (4593., 0.6198, 199.072),
+    (4006., 2.2448, 63.7359),
+    (3874., 3.2228, 138.5175),
+    (3269., 0.7749, 949.1756),
+    (2954., 0.9828, 95.9792),
+    (2461., 2.0316, 735.8765),
+];
+const SATURN_L1: Series = &[
+    (21354295596., 0., 0.),
+    (1296855., 1.8282054, 213.2990954),
+    (564348., 2.885001, 7.113547),
+    (107679., 2.277699, 206.185548),
+    (98323., 1.0807, 426.59819),
+    (40255., 2.04128, 220.41264),
+    (19942., 1.27955, 103.09277),
+    (10512., 2.7488, 14.22709),
+    (6939., 0.4049, 639.8973),
+    (4803., 2.4419, 419.4846),
+    (4056., 2.9217, 110.2063),
+    (3769., 3.6497, 3.9322),
+    (3385., 2.4169, 3.1814),
+    (3302., 1.2626, 433.7117),
+    (3071., 2.3274, 199.072),
+];
+const SATURN_L2: Series = &[
+    (116441., 1.179879, 7.113547),
+    (91921., 0.07425, 213.2991),
+    (90592., 0., 0.),
+    (15277., 4.06492, 206.18555),
+    (10631., 0.25778, 220.41264),
+    (10605., 5.40964, 426.59819),
+    (4265., 1.046, 14.2271),
+    (1216., 2.9186, 103.0928),
+    (1165., 4.6094, 639.8973),
+    (1082., 5.6913, 433.7117),
+    (1045., 4.0421, 199.072),
+    (1020., 0.6337, 3.1814),
+];
+const SATURN_L3: Series = &[
+    (16039., 5.73945, 7.11355),
+    (4250., 4.5854, 213.2991),
+    (1907., 4.7608, 220.4126),
+    (1466., 5.9133, 206.1855),
+    (1162., 5.6197, 14.2271),
+    (1067., 3.6082, 426.5982),
+    (239., 3.861, 433.712),
+    (237., 5.768, 199.072),
+    (166., 5.116, 3.181),
+];
+const SATURN_L4: Series = &[
+    (1662., 3.9983, 7.1135),
+    (257., 2.984, 220.413),
+    (236., 3.902, 14.227),
+    (149., 2.741, 213.299),
+    (114., 3.142, 0.),
+    (110., 1.515, 206.186),
+];
+const SATURN_L5: Series = &[
+    (124., 2.259, 7.114),
+    (34., 2.16, 14.23),
+    (28., 1.2, 220.41),
+    (6., 1.22, 227.53),
+];
+const SATURN_B0: Series = &[
+    (4330678., 3.6028443, 213.2990954),
+    (240348., 2.852385, 426.598191),
+    (84746., 0., 0.),
+    (34116., 0.57297, 206.18555),
+    (30863., 3.48442, 220.41264),
+    (14734., 2.11847, 639.89729),
+    (9917., 5.79, 419.4846),
+    (6994., 4.736, 7.1135),
+    (4808., 5.4331, 316.3919),
+    (4788., 4.9651, 110.2063),
+];
+const SATURN_B1: Series = &[
+    (397555., 5.3329, 213.299095),
+    (49479., 3.14159, 0.),
+    (18572., 6.09919, 426.59819),
+    (14801., 2.30586, 206.18555),
+    (9644., 1.6967, 220.4126),
+    (3757., 1.2543, 419.4846),
+    (2717., 5.9117, 639.8973),
+];
+const SATURN_B2: Series = &[
+    (20630., 0.50482, 213.2991),
+    (3720., 3.9983, 206.1855),
+    (1627., 6.1819, 220.4126),
+    (1346., 0., 0.),
+    (706., 3.039, 419.485),
+    (365., 5.099, 426.598),
+];
+const SATURN_B3: Series = &[
+    (666., 1.99, 213.299),
+    (632., 5.698, 206.186),
+    (398., 0., 0.),
+    (188., 4.338, 220.413),
+];
+const SATURN_B4: Series = &[
+    (80., 1.12, 206.19),
+    (32., 3.12, 213.3),
+];
+const SATURN_R0: Series = &[
+    (955758136., 0., 0.),
+    (52921382., 2.3922622, 213.2990954),
+    (1873680., 5.2354961, 206.1855484),
+    (1464664., 1.6476305, 426.5981909),
+    (821891., 5.9352, 316.39187),
+    (547507., 5.015326, 103.092774),
+    (371684., 2.271148, 220.412642),
+    (361778., 3.139043, 7.113547),
+    (140618., 5.704067, 632.783739),
+    (108975., 3.293136, 110.206321),
+    (69007., 5.941, 419.48464),
+    (61053., 0.94038, 639.89729),
+    (48913., 1.55733, 202.2534),
+    (34144., 0.19519, 277.03499),
+    (32402., 5.47085, 949.17561),
+    (20937., 0.46349, 735.87651),
+    (20839., 1.52103, 433.71174),
+    (20747., 5.33256, 199.072),
+    (15298., 3.0594, 529.691),
+    (14296., 2.6043, 323.5054),
+];
+const SATURN_R1: Series = &[
+    (6182981., 0.2584352, 213.2990954),
+    (506578., 0.711147, 206.185548),
+    (341394., 5.796358, 426.598191),
+    (188491., 0.472157, 220.412642),
+    (186262., 3.141593, 0.),
+    (143891., 1.407449, 7.113547),
+    (49621., 6.01744, 103.09277),
+    (20928., 5.09246, 639.89729),
+    (19953., 1.1756, 419.48464),
+    (18840., 1.6082, 110.20632),
+    (13877., 0.75886, 199.072),
+    (12893., 5.9433, 433.71174),
+    (5397., 1.2885, 14.2271),
+];
+const SATURN_R2: Series = &[
+    (436902., 4.786717, 213.299095),
+    (71923., 2.5007, 206.18555),
+    (49767., 4.97168, 220.41264),
+    (43221., 3.8694, 426.59819),
+    (29646., 5.9631, 7.11355),
+    (4721., 2.4753, 199.072),
+    (4142., 4.1067, 433.7117),
+    (3789., 3.0977, 639.8973),
+    (2964., 1.3721, 103.0928),
+    (2556., 2.8507, 419.4846),
+];
+const SATURN_R3: Series = &[
+    (20315., 3.02187, 213.2991),
+    (8924., 3.1914, 220.4126),
+    (6909., 4.3517, 206.1855),
+    (4087., 4.2241, 7.1135),
+    (3879., 2.0106, 426.5982),
+    (1071., 4.2036, 199.072),
+];
+const SATURN_R4: Series = &[
+    (1202., 1.415, 220.4126),
+    (708., 1.162, 213.299),
+    (516., 6.24, 206.186),
+    (427., 2.469, 7.114),
+    (268., 0.187, 426.598),
+];
+const SATURN_R5: Series = &[
+    (129., 5.913, 220.413),
+    (32., 0.69, 7.11),
+];
+
+const URANUS_L0: Series = &[
+    (548129294., 0., 0.),
+    (9260408., 0.8910642, 74.7815986),
+    (1504248., 3.6271926, 1.4844727),
+    (365982., 1.899622, 73.297126),
+    (272328., 3.358237, 149.563197),
+    (70328., 5.39254, 63.7359),
+    (68893., 6.09292, 76.26607),
+    (61999., 2.26952, 2.96895),
+    (61951., 2.85099, 11.0457),
+    (26469., 3.14152, 71.81265),
+    (25711., 6.1138, 454.90937),
+    (21079., 4.36059, 148.07872),
+    (17819., 1.74437, 36.64856),
+    (14613., 4.73732, 3.93215),
+    (11163., 5.82682, 224.3448),
+    (10998., 0.48865, 138.5175),
+    (9527., 2.9552, 35.1641),
+    (7546., 5.2363, 109.9457),
+    (4220., 3.2333, 70.8494),
+    (4052., 2.2775, 151.0477),
+    (3490., 5.4831, 146.5943),
+    (3355., 1.0655, 4.4534),
+    (3144., 4.752, 77.7505),
+    (2927., 4.629, 9.5612),
+    (2922., 5.3524, 85.8273),
+    (2273., 4.366, 70.3282),
+];
+const URANUS_L1: Series = &[
+    (7502543122., 0., 0.),
+    (154458., 5.242017, 74.781599),
+    (24456., 1.71256, 1.48447),
+    (9258., 0.4284, 11.0457),
+    (8266., 1.5022, 63.7359),
+    (7842., 1.3198, 149.5632),
+    (3899., 0.4648, 3.9322),
+    (2284., 4.1737, 76.2661),
+    (1927., 0.5301, 2.9689),
+    (1233., 1.5863, 70.8494),
+    (791., 5.436, 3.181),
+    (767., 1.996, 73.297),
+];
+const URANUS_L2: Series = &[
+    (53033., 0., 0.),
+    (2358., 2.2601, 74.7816),
+    (769., 4.526, 11.046),
+    (552., 3.258, 63.736),
+    (542., 2.276, 3.932),
+    (529., 4.923, 1.484),
+    (258., 3.691, 3.181),
+    (239., 5.858, 149.563),
+];
+const URANUS_L3: Series = &[
+    (121., 0.024, 74.782),
+    (68., 4.12, 3.93),
+    (53., 2.39, 11.05),
+    (46., 0., 0.),
+];
+const URANUS_L4: Series = &[
+    (114., 3.142, 0.),
+    (6., 4.58, 74.78),
+];
+const URANUS_B0: Series = &[
+    (1346278., 2.6187781, 74.7815986),
+    (62341., 5.08111, 149.5632),
+    (61601., 3.14159, 0.),
+    (9964., 1.616, 76.2661),
+    (9926., 0.5763, 73.2971),
+];
+const URANUS_B1: Series = &[
+    (206366., 4.123943, 74.781599),
+    (8563., 0.3382, 149.5632),
+    (1726., 2.1219, 73.2971),
+    (1374., 0., 0.),
+    (1369., 3.0686, 76.2661),
+];
+const URANUS_B2: Series = &[
+    (9212., 5.8004, 74.7816),
+    (557., 0., 0.),
+    (286., 2.177, 149.563),
+];
+const URANUS_B3: Series = &[
+    (268., 1.251, 74.782),
+    (11., 3.14, 0.),
+];
+const URANUS_B4: Series = &[
+    (6., 2.85, 74.78),
+];
+const URANUS_R0: Series = &[
+    (1921264848., 0., 0.),
+    (88784984., 5.60377527, 74.78159857),
+    (3440836., 0.328361, 73.2971259),
+    (2055653., 1.7829517, 149.5631971),
+    (649322., 4.522473, 76.266071),
+    (602248., 3.860038, 63.735898),
+    (496404., 1.401399, 454.909367),
+    (338526., 1.580027, 138.517497),
+    (243508., 1.570866, 71.812653),
+    (190522., 1.998094, 1.484473),
+    (161858., 2.791379, 148.078724),
+    (143706., 1.383686, 11.0457),
+    (93192., 0.17437, 36.64856),
+    (89806., 3.66105, 109.94569),
+    (71424., 4.24509, 224.3448),
+    (46677., 1.39977, 35.16409),
+    (39026., 3.36235, 277.03499),
+    (39010., 1.66971, 70.84945),
+    (36755., 3.88649, 146.59425),
+    (30349., 0.701, 151.04767),
+    (29156., 3.18056, 77.75054),
+    (25786., 3.78538, 85.8273),
+    (25620., 5.25656, 380.12777),
+    (22637., 0.72519, 529.69097),
+    (20473., 2.7964, 70.32818),
+    (20472., 1.55589, 202.2534),
+    (17901., 0.55455, 2.96895),
+    (15503., 5.35405, 38.13304),
+    (14702., 4.90434, 108.46122),
+    (12897., 2.62154, 111.43016),
+    (12328., 5.96039, 127.4718),
+    (11959., 1.75044, 984.60033),
+    (11853., 0.99343, 52.6902),
+    (11696., 3.29826, 3.93215),
+    (11495., 0.43774, 65.22037),
+    (10793., 1.42105, 213.2991),
+];
+const URANUS_R1: Series = &[
+    (1479896., 3.6720571, 74.7815986),
+    (71212., 6.22601, 63.7359),
+    (68627., 6.13411, 149.5632),
+    (24060., 3.14159, 0.),
+    (21468., 2.60177, 76.26607),
+    (20857., 5.24625, 11.0457),
+    (11405., 0.01848, 70.84945),
+    (7497., 0.4236, 73.2971),
+    (4244., 1.4169, 85.8273),
+    (3927., 3.1551, 71.8127),
+];
+const URANUS_R2: Series = &[
+    (22440., 0.69953, 74.7816),
+    (4727., 1.699, 63.7359),
+    (1682., 4.6483, 70.8494),
+    (1650., 3.0966, 11.0457),
+    (1434., 3.5212, 149.5632),
+    (770., 0., 0.),
+    (500., 6.172, 76.266),
+];
+const URANUS_R3: Series = &[
+    (1164., 4.7345, 74.7816),
+    (212., 3.343, 63.736),
+    (196., 2.98, 70.849),
+    (105., 0.958, 11.046),
+];
+const URANUS_R4: Series = &[
+    (53., 3.01, 74.78),
+    (10., 1.91, 56.62),
+];
+
+const NEPTUNE_L0: Series = &[
+    (531188633., 0., 0.),
+    (1798476., 2.9010127, 38.1330356),
+    (1019728., 0.4858092, 1.4844727),
+    (124532., 4.830081, 36.648563),
+    (42064., 5.41055, 2.96895),
+    (37715., 6.09222, 35.16409),
+    (33785., 1.24489, 76.26607),
+    (16483., 0.00008, 491.55793),
+    (9199., 4.9375, 39.6175),
+    (8994., 0.2746, 175.1661),
+    (4216., 1.9871, 73.2971),
+    (3365., 1.0359, 33.6796),
+    (2285., 4.2061, 4.4534),
+    (1434., 2.7834, 74.7816),
+];
+const NEPTUNE_L1: Series = &[
+    (3837687717., 0., 0.),
+    (16604., 4.86319, 1.48447),
+    (15807., 2.27923, 38.13304),
+    (3335., 3.682, 76.2661),
+    (1306., 3.6732, 2.9689),
+    (605., 1.505, 35.164),
+    (179., 3.453, 39.618),
+    (107., 2.451, 4.453),
+    (106., 2.755, 33.68),
+];
+const NEPTUNE_L2: Series = &[
+    (53893., 0., 0.),
+    (296., 1.855, 1.484),
+    (281., 1.191, 38.133),
+    (270., 5.721, 76.266),
+];
+const NEPTUNE_L3: Series = &[
+    (31., 0., 0.),
+    (15., 1.35, 76.27),
+    (12., 6.04, 1.48),
+    (12., 6.11, 38.13),
+];
+const NEPTUNE_L4: Series = &[
+    (114., 3.142, 0.),
+];
+const NEPTUNE_B0: Series = &[
+    (3088623., 1.4410437, 38.1330356),
+    (27780., 5.91272, 76.26607),
+    (27624., 0., 0.),
+    (15448., 3.50877, 39.61751),
+    (15355., 2.52124, 36.64856),
+    (2000., 1.51, 74.7816),
+];
+const NEPTUNE_B1: Series = &[
+    (227279., 3.807931, 38.133036),
+    (1803., 1.9758, 76.2661),
+    (1433., 3.1416, 0.),
+    (1386., 4.8256, 36.6486),
+    (1073., 6.0805, 39.6175),
+];
+const NEPTUNE_B2: Series = &[
+    (9691., 5.5712, 38.133),
+    (79., 3.63, 76.27),
+    (72., 0.45, 36.65),
+    (59., 3.14, 0.),
+];
+const NEPTUNE_B3: Series = &[
+    (273., 1.017, 38.133),
+    (2., 0., 0.),
+];
+const NEPTUNE_B4: Series = &[
+    (6., 2.67, 38.13),
+];
+const NEPTUNE_R0: Series = &[
+    (3007013206., 0., 0.),
+    (27062259., 1.32999459, 38.13303564),
+    (1691764., 3.2518614, 36.6485629),
+    (807831., 5.185928, 1.484473),
+    (537761., 4.521139, 35.16409),
+    (495726., 1.571057, 491.557929),
+    (274572., 1.845523, 175.16606),
+    (135134., 3.372206, 39.617508),
+    (121802., 5.797544, 76.266071),
+    (100895., 0.377027, 73.297126),
+    (69792., 3.79617, 2.96895),
+    (46688., 5.74938, 33.67962),
+    (24594., 0.50802, 109.94569),
+    (16939., 1.59422, 71.81265),
+    (14230., 1.07786, 74.7816),
+    (12012., 1.92062, 1021.24889),
+];
+const NEPTUNE_R1: Series = &[
+    (236339., 0.70498, 38.133036),
+    (13220., 3.32015, 1.48447),
+    (8622., 6.2163, 35.1641),
+    (2702., 1.8814, 39.6175),
+    (2155., 2.0943, 2.9689),
+    (2153., 5.1687, 76.2661),
+    (1603., 0., 0.),
+    (1464., 1.1842, 33.6796),
+    (1136., 3.9189, 36.6486),
+];
+const NEPTUNE_R2: Series = &[
+    (4247., 5.8991, 38.133),
+    (218., 0.346, 1.484),
+    (163., 2.239, 491.558),
+];
+const NEPTUNE_R3: Series = &[
+    (166., 4.552, 38.133),
+];
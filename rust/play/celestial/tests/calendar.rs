@@ -0,0 +1,218 @@
+use std::convert::TryFrom;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+use proptest::prelude::*;
+
+use celestial::{
+    calendar::{
+        day_of_week, easter, julian_easter, Calendar, CalendarDate, Gregorian, Hebrew, Islamic,
+        IsoWeek, IsoWeekDate, Julian, GREGORIAN_REFORM,
+    },
+    error::Error,
+    Day, JulianDay,
+};
+
+/// Julian day number of 0001 January 1 of the proleptic Gregorian calendar,
+/// chrono's day 1 of the common era.
+const CE: i64 = 1_721_426;
+
+fn date(year: i64, month: u8, day: u8) -> CalendarDate {
+    CalendarDate::new(year, month, day)
+}
+
+fn gregorian(year: i64, month: u8, day: u8) -> i64 {
+    Gregorian::default()
+        .day_number(&date(year, month, day))
+        .unwrap()
+}
+
+#[test]
+fn julian_and_gregorian() {
+    // Meeus, Astronomical Algorithms, chapter 7
+    let calendar = Gregorian::default();
+    assert_eq!(gregorian(333, 1, 27), 1_842_713);
+    assert_eq!(gregorian(-4712, 1, 1), 0);
+    assert_eq!(gregorian(2000, 1, 1), 2_451_545);
+    assert_eq!(calendar.date(GREGORIAN_REFORM - 1), date(1582, 10, 4));
+    assert_eq!(calendar.date(GREGORIAN_REFORM), date(1582, 10, 15));
+    assert!(matches!(
+        calendar.day_number(&date(1582, 10, 10)),
+        Err(Error::InvalidDate(_))
+    ));
+
+    // Julian leap years keep February 29 until the reform
+    assert!(calendar.day_number(&date(1500, 2, 29)).is_ok());
+    assert!(calendar.day_number(&date(1700, 2, 29)).is_err());
+    assert!(Julian.day_number(&date(1900, 2, 29)).is_ok());
+    assert!(Gregorian::proleptic()
+        .day_number(&date(1500, 2, 29))
+        .is_err());
+    assert!(calendar.day_number(&date(2021, 13, 1)).is_err());
+    assert!(calendar.day_number(&date(2021, 4, 31)).is_err());
+
+    // Great Britain went from Wednesday 2 to Thursday 14 September 1752
+    let britain = Gregorian::with_reform(2_361_222);
+    assert_eq!(britain.date(2_361_221), date(1752, 9, 2));
+    assert_eq!(britain.date(2_361_222), date(1752, 9, 14));
+    assert_eq!(
+        britain.convert(&date(1752, 9, 2), &calendar).unwrap(),
+        date(1752, 9, 13)
+    );
+    assert!(britain.day_number(&date(1752, 9, 3)).is_err());
+
+    let jd = calendar.julian_day(&date(1957, 10, 4)).unwrap();
+    assert_eq!(jd.to_f64(), 2_436_115.5);
+    assert_eq!(calendar.date_of(jd + 0.81), date(1957, 10, 4));
+    assert_eq!(calendar.date_of(jd + 1.), date(1957, 10, 5));
+    assert_eq!(date(-44, 3, 15).to_string(), "-044-03-15");
+}
+
+#[test]
+fn hebrew() {
+    // Rosh Hashanah, Hanukkah and Passover of the leap year 5784
+    let rosh_hashanah = date(5784, 7, 1);
+    assert_eq!(
+        Hebrew.day_number(&rosh_hashanah).unwrap(),
+        gregorian(2023, 9, 16)
+    );
+    assert_eq!(Hebrew.date(gregorian(2023, 12, 8)), date(5784, 9, 25));
+    assert_eq!(Hebrew.date(gregorian(2024, 4, 23)), date(5784, 1, 15));
+    assert_eq!(Hebrew.date(gregorian(2024, 3, 24)), date(5784, 13, 14));
+    assert_eq!(Hebrew.date(gregorian(2024, 10, 3)), date(5785, 7, 1));
+
+    assert!(Hebrew::is_leap_year(5784));
+    assert!(!Hebrew::is_leap_year(5785));
+    assert_eq!(Hebrew::days(5784), 383);
+    assert_eq!(Hebrew::months(5785), 12);
+    assert!(Hebrew.day_number(&date(5785, 13, 1)).is_err());
+    assert!(Hebrew.day_number(&date(5784, 2, 30)).is_err());
+
+    assert_eq!(Hebrew.date(347_998), date(1, 7, 1));
+}
+
+#[test]
+fn islamic() {
+    assert_eq!(
+        Islamic.convert(&date(1, 1, 1), &Julian).unwrap(),
+        date(622, 7, 16)
+    );
+    assert_eq!(
+        Islamic.day_number(&date(1444, 9, 1)).unwrap(),
+        gregorian(2023, 3, 23)
+    );
+    // A day after the crescent was seen
+    assert_eq!(Islamic.date(gregorian(2024, 7, 8)), date(1446, 1, 1));
+
+    assert!(Islamic::is_leap_year(1445));
+    assert!(Islamic.day_number(&date(1445, 12, 30)).is_ok());
+    assert!(Islamic.day_number(&date(1444, 12, 30)).is_err());
+    assert!(Islamic.day_number(&date(1444, 2, 30)).is_err());
+}
+
+#[test]
+fn iso_weeks() {
+    let week = |year, week, weekday| IsoWeekDate {
+        year,
+        week,
+        weekday,
+    };
+    assert_eq!(
+        IsoWeek.date(gregorian(2008, 12, 29)),
+        week(2009, 1, Day::Monday)
+    );
+    assert_eq!(
+        IsoWeek.date(gregorian(2010, 1, 3)),
+        week(2009, 53, Day::Sunday)
+    );
+    assert_eq!(
+        IsoWeek.date(gregorian(2005, 1, 1)),
+        week(2004, 53, Day::Saturday)
+    );
+    assert_eq!(week(2004, 53, Day::Saturday).to_string(), "2004-W53-6");
+
+    assert_eq!((IsoWeek::weeks(2009), IsoWeek::weeks(2010)), (53, 52));
+    assert!(IsoWeek.day_number(&week(2010, 53, Day::Monday)).is_err());
+    assert!(IsoWeek.day_number(&week(2010, 0, Day::Monday)).is_err());
+}
+
+#[test]
+fn computus() {
+    // Meeus, Astronomical Algorithms, chapter 8
+    for &(year, month, day) in &[
+        (1818, 3, 22),
+        (1954, 4, 18),
+        (1991, 3, 31),
+        (1992, 4, 19),
+        (1993, 4, 11),
+        (2000, 4, 23),
+        (2285, 3, 22),
+    ] {
+        assert_eq!(easter(year), date(year, month, day));
+    }
+    for &year in &[179, 711, 1243] {
+        assert_eq!(julian_easter(year), date(year, 4, 12));
+    }
+
+    // The Orthodox Easter of 2024
+    let orthodox = Julian
+        .convert(&julian_easter(2024), &Gregorian::default())
+        .unwrap();
+    assert_eq!(orthodox, date(2024, 5, 5));
+}
+
+#[test]
+fn days_of_the_week() {
+    assert_eq!(day_of_week(JulianDay::J2000), Day::Saturday);
+    assert_eq!(day_of_week(JulianDay::from(2_434_923.5)), Day::Wednesday);
+    assert_eq!(Day::try_from(4).unwrap(), Day::Thursday);
+    assert!(matches!(Day::try_from(7), Err(Error::InvalidDate(_))));
+}
+
+proptest! {
+    #[test]
+    fn round_trips(day_number in -2_000_000_i64..6_000_000) {
+        for calendar in &[Gregorian::default(), Gregorian::proleptic()] {
+            let date = calendar.date(day_number);
+            prop_assert_eq!(calendar.day_number(&date).unwrap(), day_number);
+        }
+        let date = Julian.date(day_number);
+        prop_assert_eq!(Julian.day_number(&date).unwrap(), day_number);
+        let date = Hebrew.date(day_number);
+        prop_assert_eq!(Hebrew.day_number(&date).unwrap(), day_number);
+        let date = Islamic.date(day_number);
+        prop_assert_eq!(Islamic.day_number(&date).unwrap(), day_number);
+        let date = IsoWeek.date(day_number);
+        prop_assert_eq!(IsoWeek.day_number(&date).unwrap(), day_number);
+    }
+
+    #[test]
+    fn agrees_with_chrono(day_number in -2_000_000_i64..6_000_000) {
+        let naive = NaiveDate::from_num_days_from_ce((day_number - CE + 1) as i32);
+        let date = Gregorian::proleptic().date(day_number);
+        prop_assert_eq!(
+            (date.year, date.month as u32, date.day as u32),
+            (naive.year() as i64, naive.month(), naive.day())
+        );
+
+        let week = IsoWeek.date(day_number);
+        let iso = naive.iso_week();
+        prop_assert_eq!((week.year, week.week as u32), (iso.year() as i64, iso.week()));
+        prop_assert_eq!(iso_weekday(week.weekday), naive.weekday());
+
+        let midnight = JulianDay::new(day_number, -0.5);
+        prop_assert_eq!(day_of_week(midnight), week.weekday);
+        prop_assert_eq!(day_of_week(midnight + 0.999), week.weekday);
+    }
+}
+
+fn iso_weekday(day: Day) -> Weekday {
+    match day {
+        Day::Monday => Weekday::Mon,
+        Day::Tuesday => Weekday::Tue,
+        Day::Wednesday => Weekday::Wed,
+        Day::Thursday => Weekday::Thu,
+        Day::Friday => Weekday::Fri,
+        Day::Saturday => Weekday::Sat,
+        Day::Sunday => Weekday::Sun,
+    }
+}
@@ -0,0 +1,170 @@
+use std::{env, fs};
+
+use chrono::{TimeZone, Utc};
+use float_eq::assert_float_eq;
+
+use celestial::{
+    catalog::{Catalog, Star},
+    coords::Equatorial,
+    degrees,
+    error::Error,
+    hours,
+    observer::Observer,
+    JulianDay,
+};
+
+/// A few lines in the fixed-width layout of BSC5, with the columns this
+/// crate doesn't read left blank, and an entry dropped from the catalog.
+const BSC5: &str = "  92
+ 424  1Alp UMi                                                             023148.7+891551             2.02                    F7:Ib-IIv SB         +0.038-0.015
+2061 58Alp Ori                                                             055510.3+072425             0.50                    M1-M2Ia-Iab          +0.027+0.011
+2326   Alp Car                                                             062357.1-524145            -0.72                    F0II                 +0.019+0.023
+2491  9Alp CMa                                                             064508.9-164258            -1.46                    A1Vm                 -0.553-1.205
+5340 16Alp Boo                                                             141539.7+191057            -0.04                    K1.5IIIFe-0.5        -1.093-1.999
+7001  3Alp Lyr                                                             183656.3+384701             0.03                    A0Va                 +0.201+0.287
+";
+
+const HIPPARCOS: &str = "\
+HIP,HR,Name,RAdeg,DEdeg,pmRA,pmDE,Vmag,SpType
+91262,7001,Vega,279.23410832,38.78299311,201.02,287.46,0.03,A0Vvar
+32349,2491,Sirius,101.28854105,-16.71314306,-546.01,-1223.08,-1.44,A0m...
+69673,5340,Arcturus,213.91811403,19.18726997,-1093.45,-1999.40,-0.05,K2IIIp
+12345,,,,,,,,
+";
+
+fn bsc5() -> Catalog {
+    Catalog::from_bsc5(BSC5).unwrap()
+}
+
+#[test]
+fn bright_star_catalogue() {
+    let catalog = bsc5();
+    assert_eq!(catalog.stars().len(), 6);
+
+    let vega = catalog.by_hr(7001).unwrap();
+    assert_eq!(vega.name.as_deref(), Some("3Alp Lyr"));
+    assert_float_eq!(vega.position.ra_hours(), 18.615_639, abs <= 1e-6);
+    assert_float_eq!(vega.position.dec_degrees(), 38.783_611, abs <= 1e-6);
+    assert_eq!(
+        (vega.magnitude, vega.pm_ra, vega.pm_dec),
+        (0.03, 0.201, 0.287)
+    );
+    assert_eq!(vega.spectral_class, "A0Va");
+    assert_eq!(vega.epoch, JulianDay::J2000);
+
+    let canopus = catalog.by_name("alp car").unwrap();
+    assert_float_eq!(canopus.position.dec_degrees(), -52.695_833, abs <= 1e-6);
+
+    assert_eq!(catalog.by_name("Alp Lyr"), Some(vega));
+    assert_eq!(catalog.by_name("3 alp lyr"), Some(vega));
+    assert_eq!(catalog.by_name("Bet Lyr"), None);
+    assert_eq!(catalog.by_hr(92), None);
+}
+
+#[test]
+fn hipparcos_csv() {
+    let catalog: Catalog = HIPPARCOS.parse().unwrap();
+    assert_eq!(catalog.stars().len(), 3);
+
+    let sirius = catalog.by_hip(32_349).unwrap();
+    assert_eq!(catalog.by_name("sirius"), Some(sirius));
+    assert_eq!(catalog.by_hr(2491), Some(sirius));
+    assert_float_eq!(sirius.position.ra_hours(), 6.752_569, abs <= 1e-6);
+    assert_float_eq!(sirius.pm_dec, -1.223_08, abs <= 1e-9);
+
+    // Carried from 1991.25 to 2000, it lands on the BSC5 place
+    let at_j2000 = sirius.position_at(JulianDay::J2000);
+    let bsc5 = bsc5().by_hr(2491).unwrap().position;
+    assert!(at_j2000.separation(&bsc5) < degrees(1. / 3600.));
+}
+
+#[test]
+fn proper_motion() {
+    // θ Persei, Meeus example 21.b, moving 0.034 25 s a year in right
+    // ascension
+    let star = Star {
+        hr: Some(875),
+        hip: None,
+        name: None,
+        position: Equatorial::new(degrees(41.049_942).to_hours(), degrees(49.228_467)),
+        epoch: JulianDay::J2000,
+        pm_ra: 0.034_25 * 15. * 49.228_467_f64.to_radians().cos(),
+        pm_dec: -0.0895,
+        magnitude: 4.12,
+        spectral_class: "F7V".to_string(),
+    };
+    let position = star.position_at(JulianDay::from(2_462_088.69));
+    assert_float_eq!(position.ra.to_degrees().value, 41.054_063, abs <= 1e-5);
+    assert_float_eq!(position.dec_degrees(), 49.227_750, abs <= 1e-5);
+
+    // Arcturus moves over a degree in two thousand years
+    let arcturus = bsc5().by_hr(5340).unwrap().clone();
+    let moved = arcturus.position_at(JulianDay::from(Utc.ymd(4000, 1, 1)));
+    assert_float_eq!(
+        moved.dec_degrees() - arcturus.position.dec_degrees(),
+        -1.111,
+        abs <= 0.001
+    );
+}
+
+#[test]
+fn cone_search() {
+    let catalog = bsc5();
+    let betelgeuse = catalog.by_hr(2061).unwrap();
+
+    let found = catalog.cone(betelgeuse.position, degrees(30.), JulianDay::J2000);
+    let hr: Vec<_> = found.iter().map(|star| star.hr.unwrap()).collect();
+    assert_eq!(hr, [2061, 2491]);
+
+    assert_float_eq!(
+        found[1]
+            .position
+            .separation(&betelgeuse.position)
+            .to_degrees()
+            .value,
+        27.104,
+        abs <= 0.001
+    );
+    assert!(catalog
+        .cone(
+            Equatorial::new(hours(0.), degrees(-90.)),
+            degrees(30.),
+            JulianDay::J2000
+        )
+        .is_empty());
+}
+
+#[test]
+fn brightest_visible() {
+    // A January evening in London: Sirius low in the south, Vega skimming
+    // the northern horizon and Arcturus not yet up
+    let london = Observer::new(51.5074, -0.1278);
+    let dt = Utc.ymd(2021, 1, 15).and_hms(22, 0, 0);
+    let catalog = bsc5();
+
+    let visible = catalog.brightest_visible(3, dt, &london, degrees(10.));
+    let hr: Vec<_> = visible.iter().map(|(star, _)| star.hr.unwrap()).collect();
+    assert_eq!(hr, [2491, 2061, 424]);
+    assert!(visible[0].1.altitude < degrees(25.));
+    assert_float_eq!(visible[2].1.altitude.to_degrees().value, 51.5, abs <= 1.);
+
+    let all = catalog.brightest_visible(10, dt, &london, degrees(0.));
+    assert_eq!(all.len(), 4);
+}
+
+#[test]
+fn bad_catalogs() {
+    let bad = BSC5.replace("+0.287", "+0.2x7");
+    match Catalog::from_bsc5(&bad) {
+        Err(Error::Parse(message)) => assert!(message.contains("line 7")),
+        other => panic!("expected a parse error, not {:?}", other),
+    }
+    assert!("HIP,Vmag\n1,2.0".parse::<Catalog>().is_err());
+    assert!("".parse::<Catalog>().is_err());
+    assert!(Catalog::from_file("/nonexistent/catalog").is_err());
+
+    let path = env::temp_dir().join("celestial-catalog.csv");
+    fs::write(&path, HIPPARCOS).unwrap();
+    assert_eq!(Catalog::from_file(&path).unwrap().stars().len(), 3);
+    fs::remove_file(&path).unwrap();
+}
@@ -1,6 +1,71 @@
-use celestial::HourAngle;
+use chrono::{TimeZone, Utc};
+use float_eq::assert_float_eq;
+
+use celestial::{
+    coords::{obliquity, Ecliptic, Equatorial, Galactic, Horizon},
+    degrees, hours,
+    observer::Observer,
+    Angle, JulianDay,
+};
 
 #[test]
 fn ch4_q1() {
-    let _star = HourAngle(15., 30., 15.);
+    let star = Angle::from_hms_tuple((15., 30., 15.));
+    assert_float_eq!(star.to_hours().value, 15.504_167, abs <= 1e-6);
+    assert_eq!(star.display_hms().to_string(), "15h 30m 15s");
+    assert_eq!("15h 30m 15s".parse::<Angle>().unwrap(), star);
+}
+
+#[test]
+fn mean_obliquity() {
+    // Meeus, Astronomical Algorithms, example 22.a: 23°26'27.407"
+    let eps = obliquity(JulianDay::from(Utc.ymd(1987, 4, 10)));
+    assert_float_eq!(eps, degrees(23.440_946), abs <= degrees(1e-6));
+}
+
+#[test]
+fn equatorial_to_ecliptic() {
+    // Meeus example 13.a: Pollux
+    let pollux = Equatorial::new(degrees(116.328_942).to_hours(), degrees(28.026_183));
+    let ecliptic = pollux.to_ecliptic(degrees(23.439_291_1));
+    assert_float_eq!(ecliptic.lon_degrees(), 113.215_630, abs <= 1e-5);
+    assert_float_eq!(ecliptic.lat_degrees(), 6.684_170, abs <= 1e-5);
+
+    let back = Ecliptic::new(ecliptic.lon, ecliptic.lat).to_equatorial(degrees(23.439_291_1));
+    assert_float_eq!(back.ra.to_degrees().value, 116.328_942, abs <= 1e-5);
+    assert_float_eq!(back.dec_degrees(), 28.026_183, abs <= 1e-5);
+}
+
+#[test]
+fn equatorial_to_horizon() {
+    // Meeus example 13.b: Venus from the US Naval Observatory, with azimuth
+    // measured from north rather than south
+    let time = Utc.ymd(1987, 4, 10).and_hms(19, 21, 0);
+    let usno = Observer::new(38.921_389, -77.065_556);
+    let venus = Equatorial::new(degrees(347.319_337_5).to_hours(), degrees(-6.719_892));
+
+    let horizon = venus.to_horizon(time, &usno);
+    assert_float_eq!(horizon.azimuth.to_degrees().value, 248.0337, abs <= 0.01);
+    assert_float_eq!(horizon.altitude.to_degrees().value, 15.1249, abs <= 0.01);
+
+    let back = Horizon {
+        azimuth: horizon.azimuth,
+        altitude: horizon.altitude,
+    }
+    .to_equatorial(time, &usno);
+    assert_float_eq!(back.ra_hours(), venus.ra_hours(), abs <= 1e-9);
+    assert_float_eq!(back.dec_degrees(), venus.dec_degrees(), abs <= 1e-9);
+}
+
+#[test]
+fn equatorial_to_galactic() {
+    // Sagittarius A*, 17h45m40.04s -29°00'28.1"
+    let sgr_a = Equatorial::new(hours(17.761_122), degrees(-29.007_806));
+    let galactic = sgr_a.to_galactic();
+    assert_float_eq!(galactic.l.to_degrees().value, 359.9442, abs <= 1e-3);
+    assert_float_eq!(galactic.b.to_degrees().value, -0.0462, abs <= 1e-3);
+
+    let back = Galactic::new(galactic.l, galactic.b).to_equatorial();
+    assert_float_eq!(back.ra_hours(), sgr_a.ra_hours(), abs <= 1e-9);
+    assert_float_eq!(back.dec_degrees(), sgr_a.dec_degrees(), abs <= 1e-9);
 }
@@ -0,0 +1,152 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use float_eq::assert_float_eq;
+
+use celestial::{
+    planets::{earth, solve_kepler, Configuration, Planet, Theory},
+    JulianDay,
+};
+
+const PLANETS: [Planet; 7] = [
+    Planet::Mercury,
+    Planet::Venus,
+    Planet::Mars,
+    Planet::Jupiter,
+    Planet::Saturn,
+    Planet::Uranus,
+    Planet::Neptune,
+];
+
+fn assert_near(actual: DateTime<Utc>, expected: DateTime<Utc>, tolerance: Duration) {
+    let error = (actual - expected).num_seconds().abs();
+    assert!(
+        error <= tolerance.num_seconds(),
+        "{} is {}s from {}",
+        actual,
+        error,
+        expected
+    );
+}
+
+#[test]
+fn kepler_equation() {
+    // Meeus, Astronomical Algorithms, example 30.a
+    let e = solve_kepler(5_f64.to_radians(), 0.1);
+    assert_float_eq!(e.to_degrees(), 5.554_589, abs <= 1e-6);
+
+    // Nearly parabolic orbits, where Newton's method struggles
+    for &(m, ecc) in &[(0.2_f64, 0.99), (1., 0.999_9), (-179., 0.95), (725., 0.5)] {
+        let m = m.to_radians();
+        let e = solve_kepler(m, ecc);
+        assert_float_eq!(e - ecc * e.sin(), m, abs <= 1e-12);
+    }
+}
+
+#[test]
+fn venus_heliocentric() {
+    // Meeus example 32.a: 1992 December 20.0 TD
+    let venus = Planet::Venus.heliocentric(JulianDay::from(2_448_976.5), Theory::Vsop87);
+    assert_float_eq!(venus.lon, 26.114_28, abs <= 1e-3);
+    assert_float_eq!(venus.lat, -2.620_70, abs <= 1e-3);
+    assert_float_eq!(venus.radius, 0.724_603, abs <= 1e-5);
+}
+
+#[test]
+fn venus_geocentric() {
+    // Meeus example 33.a
+    let venus = Planet::Venus.geocentric(JulianDay::from(2_448_976.5), Theory::Vsop87);
+    assert_float_eq!(venus.ecliptic.lon_degrees(), 313.081_02, abs <= 1e-3);
+    assert_float_eq!(venus.ecliptic.lat_degrees(), -2.084_74, abs <= 1e-3);
+    assert_float_eq!(venus.distance, 0.910_947, abs <= 1e-5);
+    assert_float_eq!(
+        venus.equatorial.ra.to_degrees().value,
+        316.172_91,
+        abs <= 1e-3
+    );
+    assert_float_eq!(venus.equatorial.dec_degrees(), -18.888_01, abs <= 1e-3);
+
+    // The same moment with the 1984 Astronomical Almanac formula, as in
+    // Meeus example 41.c
+    assert_float_eq!(venus.magnitude, -4.2, abs <= 0.05);
+}
+
+/// The mean elements leave out the great inequality of Jupiter and Saturn,
+/// so they can differ from VSOP87 by a few tenths of a degree.
+#[test]
+fn theories_agree() {
+    for year in [1850, 1900, 1950, 2000, 2021, 2050] {
+        let jd = JulianDay::from(Utc.ymd(year, 7, 1));
+        let (kepler, vsop) = (earth(jd, Theory::Kepler), earth(jd, Theory::Vsop87));
+        assert_float_eq!(kepler.lon, vsop.lon, abs <= 0.02);
+        assert_float_eq!(kepler.radius, vsop.radius, abs <= 1e-4);
+
+        for planet in PLANETS.iter() {
+            let kepler = planet.heliocentric(jd, Theory::Kepler);
+            let vsop = planet.heliocentric(jd, Theory::Vsop87);
+            let lon = (kepler.lon - vsop.lon + 180.).rem_euclid(360.) - 180.;
+            assert!(lon.abs() < 0.2, "{:?} {}: {}", planet, year, lon);
+            assert!(
+                (kepler.lat - vsop.lat).abs() < 0.03,
+                "{:?} {}",
+                planet,
+                year
+            );
+            assert!(
+                (kepler.radius - vsop.radius).abs() < 0.002 * vsop.radius,
+                "{:?} {}",
+                planet,
+                year
+            );
+        }
+    }
+}
+
+#[test]
+fn elongation_and_phase() {
+    let jd = JulianDay::from(Utc.ymd(2021, 6, 1));
+    for planet in PLANETS.iter() {
+        let position = planet.geocentric(jd, Theory::Vsop87);
+        assert!(position.elongation >= 0. && position.elongation <= 180.);
+        if planet.is_inferior() {
+            assert!(position.elongation < 48.);
+        }
+        assert!(position.phase_angle < 180.);
+    }
+
+    let jupiter = Planet::Jupiter.geocentric(jd, Theory::Vsop87);
+    assert!(jupiter.magnitude < -2. && jupiter.magnitude > -3.);
+}
+
+#[test]
+fn conjunctions_and_oppositions() {
+    // Mercury crossed the Sun on 2019 November 11, at inferior conjunction
+    // at 15h22m UT
+    let conjunction = Planet::Mercury
+        .next(
+            Configuration::Conjunction,
+            Utc.ymd(2019, 11, 1).and_hms(0, 0, 0),
+        )
+        .unwrap();
+    assert_near(
+        conjunction,
+        Utc.ymd(2019, 11, 11).and_hms(15, 22, 0),
+        Duration::minutes(5),
+    );
+
+    // Mars was at opposition on 2020 October 13, at 23h26m UT
+    let opposition = Planet::Mars
+        .next(
+            Configuration::Opposition,
+            Utc.ymd(2020, 1, 1).and_hms(0, 0, 0),
+        )
+        .unwrap();
+    assert_near(
+        opposition,
+        Utc.ymd(2020, 10, 13).and_hms(23, 26, 0),
+        Duration::minutes(10),
+    );
+
+    assert_eq!(
+        Planet::Venus.next(Configuration::Opposition, Utc::now()),
+        None
+    );
+}
@@ -0,0 +1,133 @@
+use std::{env, fs, path::PathBuf};
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use celestial::{
+    catalog::Catalog,
+    chart::{Chart, Constellations, Projection},
+    degrees,
+    error::Error,
+    observer::Observer,
+};
+
+fn data(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/data")
+        .join(name)
+}
+
+/// Greenwich on the evening of the March equinox in 2021, with Orion in the
+/// west, the Plough overhead and Mars near a half Moon.
+fn evening() -> (Catalog, Constellations, DateTime<Utc>, Observer) {
+    (
+        Catalog::from_file(data("bright_stars.csv")).unwrap(),
+        Constellations::from_file(data("constellationship.fab")).unwrap(),
+        Utc.ymd(2021, 3, 20).and_hms(21, 0, 0),
+        Observer::new(51.4769, -0.0005),
+    )
+}
+
+/// Compare a chart with the file it should match byte for byte.  Set
+/// `UPDATE_GOLDEN` to write the file afresh after a deliberate change, and
+/// look at the result before committing it.
+fn assert_golden(svg: &str, name: &str) {
+    let path = data(name);
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, svg).unwrap();
+    }
+    let golden = fs::read_to_string(&path).unwrap();
+    assert!(
+        svg == golden,
+        "{} differs; run with UPDATE_GOLDEN=1 to see how",
+        name
+    );
+}
+
+#[test]
+fn constellation_lines() {
+    let constellations = Constellations::from_file(data("constellationship.fab")).unwrap();
+    let figures = constellations.figures();
+    assert_eq!(figures.len(), 3);
+    assert_eq!(figures[0].abbreviation, "Ori");
+    assert_eq!(figures[0].lines.len(), 8);
+    assert_eq!(figures[2].lines, [(36_850, 37_826)]);
+
+    for bad in &["Gem 2 36850 37826", "Gem one 36850 37826", "Gem 1 36850 x"] {
+        assert!(
+            matches!(bad.parse::<Constellations>(), Err(Error::Parse(_))),
+            "{}",
+            bad
+        );
+    }
+}
+
+#[test]
+fn stereographic() {
+    let (catalog, constellations, dt, greenwich) = evening();
+    let svg = Chart::default()
+        .render(&catalog, &constellations, dt, &greenwich)
+        .unwrap();
+    assert_golden(&svg, "chart-stereographic.svg");
+
+    // The same every time
+    assert_eq!(
+        Chart::default()
+            .render(&catalog, &constellations, dt, &greenwich)
+            .unwrap(),
+        svg
+    );
+
+    // Canopus never rises at Greenwich
+    let stars = svg.matches(r#"class="star""#).count();
+    assert_eq!(stars, catalog.stars().len() - 1);
+    assert_eq!(svg.matches(r#"class="figure""#).count(), 16);
+    for text in &[">N<", ">E<", ">S<", ">W<", ">Mars<", ">Moon<"] {
+        assert!(svg.contains(text), "{}", text);
+    }
+    assert!(!svg.contains(">Jupiter<"));
+    assert!(svg.contains("2021-03-20 21:00 UTC"));
+}
+
+#[test]
+fn orthographic() {
+    let (catalog, constellations, dt, greenwich) = evening();
+    let chart = Chart {
+        projection: Projection::Orthographic,
+        size: 600.,
+        magnitude_limit: 2.,
+        grid: None,
+    };
+    let svg = chart
+        .render(&catalog, &constellations, dt, &greenwich)
+        .unwrap();
+    assert_golden(&svg, "chart-orthographic.svg");
+
+    assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="600""#));
+    assert!(!svg.contains(r#"class="grid""#));
+    let bright = catalog
+        .stars()
+        .iter()
+        .filter(|star| star.magnitude <= 2. && star.hr != Some(2326))
+        .count();
+    assert_eq!(svg.matches(r#"class="star""#).count(), bright);
+}
+
+#[test]
+fn grid_steps() {
+    let (catalog, constellations, dt, greenwich) = evening();
+    for &(ra, dec) in &[(0., 15.), (30., -15.), (f64::NAN, 15.)] {
+        let chart = Chart {
+            grid: Some((degrees(ra), degrees(dec))),
+            ..Chart::default()
+        };
+        assert!(
+            matches!(
+                chart.render(&catalog, &constellations, dt, &greenwich),
+                Err(Error::Parse(_))
+            ),
+            "{} {}",
+            ra,
+            dec
+        );
+    }
+}
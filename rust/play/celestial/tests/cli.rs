@@ -0,0 +1,247 @@
+use chrono::{TimeZone, Utc};
+use chrono_tz::{Europe::London, Tz};
+use float_eq::assert_float_eq;
+use serde_json::Value;
+use structopt::StructOpt;
+
+use celestial::{
+    cli::{run, Opt, When},
+    degrees,
+    error::Error,
+    sidereal::SiderealTime,
+    JulianDay,
+};
+
+const GREENWICH: &str = "51.4769N, 0.0005W, 46m";
+
+fn celestial(args: &[&str]) -> celestial::error::Result<String> {
+    let opt = Opt::from_iter_safe(std::iter::once("celestial").chain(args.iter().copied()))
+        .expect("arguments parse");
+    run(&opt)
+}
+
+fn json(args: &[&str]) -> Value {
+    let output = celestial(&[args, &["--json"]].concat()).unwrap();
+    serde_json::from_str(&output).unwrap()
+}
+
+#[test]
+fn moments() {
+    let when = |s: &str| s.parse::<When>().unwrap();
+    let utc = Utc.ymd(2021, 3, 20).and_hms(21, 0, 0);
+    for s in &[
+        "2021-03-20T21:00:00Z",
+        "2021-03-20 21:00Z",
+        "2021-03-20T22:00:00+01:00",
+        "2021-03-20T16:00-0500",
+    ] {
+        assert_eq!(when(s).resolve(&Tz::UTC).unwrap(), utc, "{}", s);
+    }
+    assert_eq!(when("2021-03-20T21:00:00").resolve(&London).unwrap(), utc);
+    assert_eq!(
+        when("2021-07-20 21:00").resolve(&London).unwrap(),
+        Utc.ymd(2021, 7, 20).and_hms(20, 0, 0)
+    );
+    assert_eq!(
+        when("2021-07-20").resolve(&London).unwrap(),
+        Utc.ymd(2021, 7, 19).and_hms(23, 0, 0)
+    );
+    assert_eq!(
+        when("2021-07-20T00:30+01:00").date(&Tz::UTC).unwrap(),
+        Utc.ymd(2021, 7, 19)
+    );
+    assert_eq!(when("now"), When::Now);
+
+    // The clocks went forward at one in the morning
+    assert!(matches!(
+        when("2021-03-28T01:30").resolve(&London),
+        Err(Error::InvalidDate(_))
+    ));
+    for bad in &["2021-03-20T25:00", "20 March 2021", "2021-03"] {
+        assert!(
+            matches!(bad.parse::<When>(), Err(Error::Parse(_))),
+            "{}",
+            bad
+        );
+    }
+}
+
+#[test]
+fn julian_days() {
+    let days = json(&["jd", "2000-01-01T12:00:00Z"]);
+    assert_eq!(days["jd"], 2_451_545.);
+    assert_eq!(days["mjd"], 51_544.5);
+    assert_float_eq!(
+        days["jd_tt"].as_f64().unwrap(),
+        2_451_545. + 64.184 / 86_400.,
+        abs <= 1e-7
+    );
+
+    let text = celestial(&["jd", "2000-01-01T13:00:00+01:00"]).unwrap();
+    assert!(text.contains("JD      2451545.000000"), "{}", text);
+}
+
+#[test]
+fn sidereal_times() {
+    let dt = Utc.ymd(2021, 3, 20).and_hms(22, 0, 0);
+    let gast = SiderealTime::apparent(JulianDay::from(dt));
+    let times = json(&["lst", "--lon", "77.0656W", "--at", "2021-03-20T22:00Z"]);
+    assert_float_eq!(times["gast"].as_f64().unwrap(), gast.hours(), abs <= 1e-9);
+    assert_float_eq!(
+        times["lst"].as_f64().unwrap(),
+        gast.local(degrees(-77.0656)).hours(),
+        abs <= 1e-9
+    );
+    assert_eq!(
+        json(&["lst", "--lon", "-77.0656", "--at", "2021-03-20T22:00Z"])["lst"],
+        times["lst"]
+    );
+
+    // The longitude can come from the site
+    assert_eq!(
+        json(&[
+            "--site",
+            "38.9214N 77.0656W",
+            "lst",
+            "--at",
+            "2021-03-20T22:00Z"
+        ])["lst"],
+        times["lst"]
+    );
+    assert!(matches!(
+        celestial(&["lst", "--at", "2021-03-20T22:00Z"]),
+        Err(Error::Parse(_))
+    ));
+}
+
+#[test]
+fn positions() {
+    let at = ["--at", "2021-03-20T21:00Z"];
+    // The March equinox
+    let sun = json(&["sun", "--at", "2021-03-20T09:37Z"]);
+    assert_float_eq!(sun["dec"].as_f64().unwrap(), 0., abs <= 0.01);
+    assert!(sun.get("altitude").is_none());
+
+    // Mars stood with the Pleiades and Aldebaran in the west
+    let mars = json(&[&["planet", "mars", "--site", GREENWICH], &at[..]].concat());
+    assert_float_eq!(mars["ra"].as_f64().unwrap(), 4.53, abs <= 0.01);
+    assert!((30. ..40.).contains(&mars["altitude"].as_f64().unwrap()));
+    assert!((250. ..280.).contains(&mars["azimuth"].as_f64().unwrap()));
+    assert_eq!(mars["distance_unit"], "AU");
+
+    // The Moon's parallax moves it by most of a degree
+    let moon = |site: &[&str]| json(&[&["moon"], &at[..], site].concat());
+    let (geocentric, topocentric) = (moon(&[]), moon(&["--site", GREENWICH]));
+    let shift = geocentric["dec"].as_f64().unwrap() - topocentric["dec"].as_f64().unwrap();
+    assert!((0.3..1.).contains(&shift), "{}", shift);
+    assert_float_eq!(
+        topocentric["illuminated"].as_f64().unwrap(),
+        0.43,
+        abs <= 0.01
+    );
+
+    assert!(Opt::from_iter_safe(&["celestial", "planet", "pluto"]).is_err());
+}
+
+#[test]
+fn rise_and_set() {
+    let sun = json(&[
+        "rise-set",
+        "sun",
+        "--site",
+        "-33.8688, 151.2093",
+        "--tz",
+        "Australia/Sydney",
+        "--date",
+        "2021-06-21",
+    ]);
+    assert_eq!(sun["horizon"], "crosses");
+    assert_eq!(sun["rise"], "2021-06-21T07:00:01+10:00");
+    assert!(sun["set"].as_str().unwrap().starts_with("2021-06-21T16:53"));
+
+    // The Sun never sets at midsummer in Svalbard
+    let sun = json(&[
+        "rise-set",
+        "sun",
+        "--site",
+        "78.22N 15.65E",
+        "--date",
+        "2021-06-21",
+    ]);
+    assert_eq!(sun["horizon"], "circumpolar");
+    assert_eq!(sun["rise"], Value::Null);
+}
+
+#[test]
+fn tonight() {
+    let site = ["--site", GREENWICH, "--tz", "Europe/London"];
+    let plan = json(&[&["tonight", "--date", "2021-03-20"], &site[..]].concat());
+    assert_eq!(plan["date"], "2021-03-20");
+    assert!(plan["sunset"]
+        .as_str()
+        .unwrap()
+        .starts_with("2021-03-20T18:1"));
+    assert!(plan["sunrise"]
+        .as_str()
+        .unwrap()
+        .starts_with("2021-03-21T05:5"));
+    assert_eq!(plan["moon"]["waxing"], true);
+
+    let planets: Vec<_> = plan["planets"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|planet| planet["name"].as_str().unwrap())
+        .collect();
+    assert!(planets.contains(&"Mars"));
+    assert!(!planets.contains(&"Venus"));
+
+    let text = celestial(&[&["tonight", "--date", "2021-03-20"], &site[..]].concat()).unwrap();
+    assert!(text.starts_with("Night of 2021-03-20"));
+    assert!(text.contains("Sunset            18:1"), "{}", text);
+
+    assert!(matches!(celestial(&["tonight"]), Err(Error::Parse(_))));
+
+    // Cuba's clocks skip the midnight after 2021 March 13
+    let havana = ["--site", "23.1N, 82.4W", "--tz", "America/Havana"];
+    let plan = json(&[&["tonight", "--date", "2021-03-13"], &havana[..]].concat());
+    assert!(plan["sunrise"]
+        .as_str()
+        .unwrap()
+        .starts_with("2021-03-14T07:"));
+}
+
+#[test]
+fn chart() {
+    let data = |name: &str| format!("{}/tests/data/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let (stars, lines) = (data("bright_stars.csv"), data("constellationship.fab"));
+    let args = [
+        "chart",
+        "--catalog",
+        &stars,
+        "--lines",
+        &lines,
+        "--at",
+        "2021-03-20T21:00Z",
+        "--site",
+        GREENWICH,
+    ];
+    let svg = celestial(&args).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains(">Mars<"));
+    assert!(svg.contains(r#"class="grid""#));
+    assert!(svg.contains(r#"class="figure""#));
+
+    let bare = celestial(&[&args[..3], &args[5..], &["--no-grid"]].concat()).unwrap();
+    assert!(!bare.contains(r#"class="grid""#));
+    assert!(!bare.contains(r#"class="figure""#));
+
+    assert!(matches!(
+        celestial(&["chart", "--catalog", &stars]),
+        Err(Error::Parse(_))
+    ));
+    assert!(matches!(
+        celestial(&["chart", "--catalog", "nowhere.csv", "--site", GREENWICH]),
+        Err(Error::StdIo(_))
+    ));
+}
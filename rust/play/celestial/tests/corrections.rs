@@ -0,0 +1,113 @@
+use chrono::{TimeZone, Utc};
+use float_eq::assert_float_eq;
+
+use celestial::{
+    coords::Equatorial,
+    corrections::{aberrate, nutate, precess, Atmosphere, Reduction, J2000},
+    degrees, hours,
+    observer::Observer,
+    JulianDay,
+};
+
+/// θ Persei at J2000.0, moved by its proper motion to 2028 November 13.19 TD,
+/// as in Meeus, Astronomical Algorithms, example 21.b.
+fn theta_persei() -> Equatorial {
+    let years = (JulianDay::from(2_462_088.69) - J2000) / 365.25;
+    Equatorial::new(
+        degrees(41.049_942 + 0.034_25 * 15. / 3600. * years).to_hours(),
+        degrees(49.228_467 - 0.0895 / 3600. * years),
+    )
+}
+
+#[test]
+fn precession() {
+    let jd = JulianDay::from(2_462_088.69);
+    let star = precess(theta_persei(), J2000, jd);
+    assert_float_eq!(star.ra.to_degrees().value, 41.547_214, abs <= 1e-5);
+    assert_float_eq!(star.dec_degrees(), 49.348_483, abs <= 1e-5);
+
+    let back = precess(star, jd, J2000);
+    assert_float_eq!(
+        (back.ra_hours(), back.dec_degrees()),
+        (theta_persei().ra_hours(), theta_persei().dec_degrees()),
+        abs <= (1e-9, 1e-8)
+    );
+}
+
+#[test]
+fn apparent_place() {
+    // Meeus example 23.a: 2h46m14.390s +49°21'07.45"
+    let jd = JulianDay::from(2_462_088.69);
+    let mean = precess(theta_persei(), J2000, jd);
+    let star = aberrate(nutate(mean, jd), jd);
+    assert_float_eq!(star.ra.to_degrees().value, 41.559_996, abs <= 1.5e-4);
+    assert_float_eq!(star.dec_degrees(), 49.352_069, abs <= 1.5e-4);
+
+    let reduction = Reduction::new(J2000);
+    assert_eq!(reduction.apparent(theta_persei(), jd), star);
+}
+
+#[test]
+fn refraction() {
+    let standard = Atmosphere::default();
+
+    // About 34' at the horizon and none at the zenith
+    assert_float_eq!(
+        standard.refraction_from_apparent(0.) * 60.,
+        34.5,
+        abs <= 0.5
+    );
+    assert_float_eq!(standard.refraction_from_true(90.), 0., abs <= 1e-6);
+    assert_float_eq!(standard.refraction_from_apparent(90.), 0., abs <= 1e-6);
+    assert_float_eq!(
+        standard.refraction_from_apparent(45.) * 60.,
+        1.,
+        abs <= 0.05
+    );
+
+    // The two formulae undo each other to a few arcseconds
+    for altitude in (0..=90).map(f64::from) {
+        let apparent = altitude + standard.refraction_from_true(altitude);
+        let back = apparent - standard.refraction_from_apparent(apparent);
+        assert_float_eq!(back, altitude, abs <= 0.002);
+    }
+
+    // Cold, dense air bends light more
+    let winter = Atmosphere::new(-20., 1040.);
+    assert!(winter.refraction_from_true(5.) > standard.refraction_from_true(5.));
+}
+
+#[test]
+fn observed_place() {
+    let observer = Observer::new(51.48, 0.);
+    let dt = Utc.ymd(2021, 3, 1).and_hms(22, 0, 0);
+    let polaris = Equatorial::new(hours(2.530_301), degrees(89.264_109));
+
+    // Precession alone has moved Polaris several arcminutes since 2000
+    let geometric = Reduction {
+        refraction: false,
+        ..Reduction::default()
+    };
+    let observed = geometric.observed(polaris, dt, &observer);
+    let naive = polaris.to_horizon(dt, &observer);
+    assert!(
+        (observed.altitude - naive.altitude)
+            .to_degrees()
+            .value
+            .abs()
+            > 0.05
+    );
+
+    // Refraction lifts it by under an arcminute at this altitude
+    let refracted = Reduction::default().observed(polaris, dt, &observer);
+    assert_float_eq!(
+        refracted.azimuth.to_degrees().value,
+        observed.azimuth.to_degrees().value,
+        abs <= 1e-9
+    );
+    assert_float_eq!(
+        (refracted.altitude - observed.altitude).to_minutes().value,
+        0.8,
+        abs <= 0.1
+    );
+}
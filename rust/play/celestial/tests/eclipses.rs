@@ -0,0 +1,268 @@
+use std::convert::TryFrom;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use float_eq::assert_float_eq;
+
+use celestial::{
+    degrees,
+    eclipses::{
+        lunar_eclipses, solar_eclipses, LunarEclipse, LunarEclipseKind, SolarEclipse,
+        SolarEclipseKind,
+    },
+    observer::Observer,
+    timescale::{JulianDate, Tt},
+};
+
+use LunarEclipseKind::{Partial as P, Penumbral as N, Total as T};
+use SolarEclipseKind::{Annular as A, Hybrid as H, Partial, Total};
+
+fn assert_near(actual: DateTime<Utc>, expected: DateTime<Utc>, tolerance: Duration) {
+    let error = (actual - expected).num_seconds().abs();
+    assert!(
+        error <= tolerance.num_seconds(),
+        "{} is {}s from {}",
+        actual,
+        error,
+        expected
+    );
+}
+
+/// A moment on TT, as the canons give greatest eclipse, as if it were UTC.
+fn on_tt(dt: DateTime<Utc>) -> DateTime<Utc> {
+    let tt = JulianDate::<celestial::timescale::Utc>::from(dt).to::<Tt>();
+    DateTime::try_from(tt.jd).unwrap()
+}
+
+fn years(from: i32, to: i32) -> std::ops::Range<DateTime<Utc>> {
+    Utc.ymd(from, 1, 1).and_hms(0, 0, 0)..Utc.ymd(to + 1, 1, 1).and_hms(0, 0, 0)
+}
+
+fn solar(y: i32, m: u32, d: u32) -> SolarEclipse {
+    let day = Utc.ymd(y, m, d);
+    solar_eclipses(day.and_hms(0, 0, 0)..day.succ().and_hms(0, 0, 0))
+        .next()
+        .unwrap()
+}
+
+fn lunar(y: i32, m: u32, d: u32) -> LunarEclipse {
+    let day = Utc.ymd(y, m, d);
+    lunar_eclipses(day.and_hms(0, 0, 0)..day.succ().and_hms(0, 0, 0))
+        .next()
+        .unwrap()
+}
+
+#[test]
+fn solar_canon() {
+    // Espenak and Meeus, Five Millennium Canon of Solar Eclipses
+    #[rustfmt::skip]
+    let canon = [
+        (2001, 6, 21, Total), (2001, 12, 14, A), (2002, 6, 10, A), (2002, 12, 4, Total),
+        (2003, 5, 31, A), (2003, 11, 23, Total), (2004, 4, 19, Partial), (2004, 10, 14, Partial),
+        (2005, 4, 8, H), (2005, 10, 3, A), (2006, 3, 29, Total), (2006, 9, 22, A),
+        (2007, 3, 19, Partial), (2007, 9, 11, Partial), (2008, 2, 7, A), (2008, 8, 1, Total),
+        (2009, 1, 26, A), (2009, 7, 22, Total), (2010, 1, 15, A), (2010, 7, 11, Total),
+        (2011, 1, 4, Partial), (2011, 6, 1, Partial), (2011, 7, 1, Partial), (2011, 11, 25, Partial),
+        (2012, 5, 20, A), (2012, 11, 13, Total), (2013, 5, 10, A), (2013, 11, 3, H),
+        (2014, 4, 29, A), (2014, 10, 23, Partial), (2015, 3, 20, Total), (2015, 9, 13, Partial),
+        (2016, 3, 9, Total), (2016, 9, 1, A), (2017, 2, 26, A), (2017, 8, 21, Total),
+        (2018, 2, 15, Partial), (2018, 7, 13, Partial), (2018, 8, 11, Partial), (2019, 1, 6, Partial),
+        (2019, 7, 2, Total), (2019, 12, 26, A), (2020, 6, 21, A), (2020, 12, 14, Total),
+        (2021, 6, 10, A), (2021, 12, 4, Total), (2022, 4, 30, Partial), (2022, 10, 25, Partial),
+        (2023, 4, 20, H), (2023, 10, 14, A), (2024, 4, 8, Total), (2024, 10, 2, A),
+        (2025, 3, 29, Partial), (2025, 9, 21, Partial),
+    ];
+    let found: Vec<_> = solar_eclipses(years(2001, 2025)).collect();
+
+    assert_eq!(found.len(), canon.len());
+    for (eclipse, &(y, m, d, kind)) in found.iter().zip(canon.iter()) {
+        assert_eq!(eclipse.maximum.date(), Utc.ymd(y, m, d));
+        assert_eq!(eclipse.kind, kind, "{}", eclipse.maximum);
+    }
+
+    // The annular eclipse of 2014 grazed Antarctica with its axis off the
+    // Earth
+    assert!(!solar(2014, 4, 29).central);
+    assert!(solar(2003, 5, 31).central);
+}
+
+#[test]
+fn solar_elements() {
+    // Greatest eclipse on TD, gamma and magnitude from the canon
+    let canon = [
+        (solar(2017, 8, 21), (18, 26, 40), 0.4367, 1.0306),
+        (solar(2023, 4, 20), (4, 17, 56), -0.3952, 1.0132),
+        (solar(2023, 10, 14), (18, 0, 41), 0.3753, 0.9520),
+        (solar(2024, 4, 8), (18, 18, 29), 0.3431, 1.0566),
+        (solar(2011, 1, 4), (8, 51, 42), 1.0627, 0.8576),
+        (solar(2022, 4, 30), (20, 42, 36), -1.1901, 0.6396),
+    ];
+    for (eclipse, (h, m, s), gamma, magnitude) in canon.iter() {
+        let greatest = eclipse.maximum.date().and_hms(*h, *m, *s);
+        assert_near(on_tt(eclipse.maximum), greatest, Duration::minutes(1));
+        assert_float_eq!(eclipse.gamma, *gamma, abs <= 0.005);
+        assert_float_eq!(eclipse.magnitude, *magnitude, abs <= 0.005);
+    }
+}
+
+#[test]
+fn lunar_canon() {
+    // Espenak and Meeus, Five Millennium Canon of Lunar Eclipses
+    #[rustfmt::skip]
+    let canon = [
+        (2001, 1, 9, T), (2001, 7, 5, P), (2001, 12, 30, N), (2002, 5, 26, N),
+        (2002, 6, 24, N), (2002, 11, 20, N), (2003, 5, 16, T), (2003, 11, 9, T),
+        (2004, 5, 4, T), (2004, 10, 28, T), (2005, 4, 24, N), (2005, 10, 17, P),
+        (2006, 3, 14, N), (2006, 9, 7, P), (2007, 3, 3, T), (2007, 8, 28, T),
+        (2008, 2, 21, T), (2008, 8, 16, P), (2009, 2, 9, N), (2009, 7, 7, N),
+        (2009, 8, 6, N), (2009, 12, 31, P), (2010, 6, 26, P), (2010, 12, 21, T),
+        (2011, 6, 15, T), (2011, 12, 10, T), (2012, 6, 4, P), (2012, 11, 28, N),
+        (2013, 4, 25, P), (2013, 5, 25, N), (2013, 10, 18, N), (2014, 4, 15, T),
+        (2014, 10, 8, T), (2015, 4, 4, T), (2015, 9, 28, T), (2016, 3, 23, N),
+        (2016, 9, 16, N), (2017, 2, 11, N), (2017, 8, 7, P), (2018, 1, 31, T),
+        (2018, 7, 27, T), (2019, 1, 21, T), (2019, 7, 16, P), (2020, 1, 10, N),
+        (2020, 6, 5, N), (2020, 7, 5, N), (2020, 11, 30, N), (2021, 5, 26, T),
+        (2021, 11, 19, P), (2022, 5, 16, T), (2022, 11, 8, T), (2023, 5, 5, N),
+        (2023, 10, 28, P), (2024, 3, 25, N), (2024, 9, 18, P), (2025, 3, 14, T),
+        (2025, 9, 7, T),
+    ];
+    let found: Vec<_> = lunar_eclipses(years(2001, 2025)).collect();
+
+    assert_eq!(found.len(), canon.len());
+    for (eclipse, &(y, m, d, kind)) in found.iter().zip(canon.iter()) {
+        assert_eq!(eclipse.maximum.date(), Utc.ymd(y, m, d));
+        assert_eq!(eclipse.kind, kind, "{}", eclipse.maximum);
+        assert_eq!(eclipse.partial.is_some(), kind != N);
+        assert_eq!(eclipse.total.is_some(), kind == T);
+    }
+}
+
+#[test]
+fn lunar_elements() {
+    // Greatest eclipse on TD, gamma and penumbral and umbral magnitudes
+    let canon = [
+        (lunar(2018, 7, 27), (20, 22, 54), 0.1168, 2.6805, 1.6087),
+        (lunar(2021, 11, 19), (9, 4, 6), -0.4552, 2.0733, 0.9742),
+        (lunar(2023, 5, 5), (17, 24, 5), -1.0350, 0.9646, -0.0456),
+        (lunar(2015, 4, 4), (12, 1, 24), 0.4460, 2.0786, 1.0008),
+    ];
+    for (eclipse, (h, m, s), gamma, penumbral, umbral) in canon.iter() {
+        let greatest = eclipse.maximum.date().and_hms(*h, *m, *s);
+        assert_near(on_tt(eclipse.maximum), greatest, Duration::minutes(1));
+        assert_float_eq!(eclipse.gamma, *gamma, abs <= 0.005);
+        assert_float_eq!(eclipse.penumbral_magnitude, *penumbral, abs <= 0.01);
+        assert_float_eq!(eclipse.umbral_magnitude, *umbral, abs <= 0.01);
+    }
+
+    // Contacts of 2022 November 8, in UT
+    let eclipse = lunar(2022, 11, 8);
+    let day = Utc.ymd(2022, 11, 8);
+    let (total, partial) = (eclipse.total.unwrap(), eclipse.partial.unwrap());
+    let contacts = [
+        (eclipse.penumbral.0, day.and_hms(8, 2, 17)),
+        (partial.0, day.and_hms(9, 9, 12)),
+        (total.0, day.and_hms(10, 16, 39)),
+        (total.1, day.and_hms(11, 41, 45)),
+        (partial.1, day.and_hms(12, 49, 9)),
+        (eclipse.penumbral.1, day.and_hms(13, 56, 9)),
+    ];
+    for (actual, expected) in contacts.iter() {
+        assert_near(*actual, *expected, Duration::minutes(1));
+    }
+
+    // Setting in New York, risen in the east over Tokyo
+    let new_york = eclipse.horizon(&Observer::new(40.7128, -74.006));
+    assert!((degrees(0.)..degrees(15.)).contains(&new_york.altitude));
+    assert!((degrees(240.)..degrees(300.)).contains(&new_york.azimuth));
+    let tokyo = eclipse.horizon(&Observer::new(35.6762, 139.6503));
+    assert!((degrees(20.)..degrees(45.)).contains(&tokyo.altitude));
+    assert!((degrees(60.)..degrees(120.)).contains(&tokyo.azimuth));
+}
+
+#[test]
+fn local_circumstances() {
+    // Totality at Carbondale, Illinois and at Dallas, Texas
+    let carbondale = solar(2017, 8, 21)
+        .local(&Observer::new(37.7273, -89.2168))
+        .unwrap();
+    let day = Utc.ymd(2017, 8, 21);
+    assert_near(
+        carbondale.first_contact,
+        day.and_hms(16, 52, 30),
+        Duration::minutes(1),
+    );
+    assert_near(
+        carbondale.second_contact.unwrap(),
+        day.and_hms(18, 20, 8),
+        Duration::minutes(1),
+    );
+    assert_near(
+        carbondale.third_contact.unwrap(),
+        day.and_hms(18, 22, 49),
+        Duration::minutes(1),
+    );
+    assert_near(
+        carbondale.fourth_contact,
+        day.and_hms(19, 47, 30),
+        Duration::minutes(1),
+    );
+    assert_eq!(carbondale.obscuration, 1.);
+    assert!(carbondale.magnitude > 1.);
+    assert_float_eq!(
+        carbondale.horizon.altitude.to_degrees().value,
+        64.,
+        abs <= 1.
+    );
+
+    let dallas = solar(2024, 4, 8)
+        .local(&Observer::new(32.7767, -96.797))
+        .unwrap();
+    let day = Utc.ymd(2024, 4, 8);
+    assert_near(
+        dallas.second_contact.unwrap(),
+        day.and_hms(18, 40, 43),
+        Duration::minutes(1),
+    );
+    assert_near(
+        dallas.third_contact.unwrap(),
+        day.and_hms(18, 44, 35),
+        Duration::minutes(1),
+    );
+
+    // Partial in London, about 85% of the Sun covered
+    let london = solar(2015, 3, 20)
+        .local(&Observer::new(51.5074, -0.1278))
+        .unwrap();
+    let day = Utc.ymd(2015, 3, 20);
+    assert_near(
+        london.first_contact,
+        day.and_hms(8, 25, 0),
+        Duration::minutes(1),
+    );
+    assert_near(london.maximum, day.and_hms(9, 31, 0), Duration::minutes(1));
+    assert_near(
+        london.fourth_contact,
+        day.and_hms(10, 41, 0),
+        Duration::minutes(1),
+    );
+    assert_eq!((london.second_contact, london.third_contact), (None, None));
+    assert_float_eq!(london.obscuration, 0.85, abs <= 0.02);
+    assert!(london.obscuration < london.magnitude);
+
+    // An annular eclipse leaves a ring of the Sun uncovered
+    let san_antonio = solar(2023, 10, 14)
+        .local(&Observer::new(29.4241, -98.4936))
+        .unwrap();
+    assert!(san_antonio.second_contact.is_some());
+    assert!(san_antonio.magnitude < 1.);
+    assert_float_eq!(
+        san_antonio.obscuration,
+        san_antonio.magnitude.powi(2),
+        abs <= 1e-9
+    );
+
+    // Not seen at all from the other side of the world
+    assert_eq!(
+        solar(2017, 8, 21).local(&Observer::new(-33.8688, 151.2093)),
+        None
+    );
+}
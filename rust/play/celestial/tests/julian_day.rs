@@ -0,0 +1,159 @@
+use std::convert::TryFrom;
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use float_eq::assert_float_eq;
+use proptest::prelude::*;
+
+use celestial::{error::Error, DateUtil, Day, JulianDay};
+
+/// Any instant from 4000 BC to AD 4000, to the nanosecond.  Chrono's dates
+/// for 1582 October 5 to 14 are left out, since the Julian calendar dates
+/// they stand for land after the reform.
+fn datetime() -> impl Strategy<Value = DateTime<Utc>> {
+    let start = Utc.ymd(-3999, 1, 1).and_hms(0, 0, 0).timestamp();
+    let end = Utc.ymd(4000, 1, 1).and_hms(0, 0, 0).timestamp();
+
+    (start..end, 0..1_000_000_000_u32)
+        .prop_map(|(seconds, nanos)| Utc.timestamp(seconds, nanos))
+        .prop_filter("dropped by the reform", |dt| {
+            !(Utc.ymd(1582, 10, 5)..Utc.ymd(1582, 10, 15)).contains(&dt.date())
+        })
+}
+
+fn duration() -> impl Strategy<Value = Duration> {
+    (-100_000_i64..100_000, 0..86_400_000_000_000_i64)
+        .prop_map(|(days, nanos)| Duration::days(days) + Duration::nanoseconds(nanos))
+}
+
+#[test]
+fn calendar_dates() {
+    // Meeus, Astronomical Algorithms, chapter 7
+    for &((y, m, d), (h, min, sec), jd) in &[
+        ((1957, 10, 4), (19, 26, 24), 2_436_116.31),
+        ((2000, 1, 1), (12, 0, 0), 2_451_545.),
+        ((1987, 6, 19), (12, 0, 0), 2_446_966.),
+        ((1600, 12, 31), (0, 0, 0), 2_305_812.5),
+        ((837, 4, 10), (7, 12, 0), 2_026_871.8),
+        ((333, 1, 27), (12, 0, 0), 1_842_713.),
+        ((1, 1, 1), (0, 0, 0), 1_721_423.5),
+        ((-1000, 7, 12), (12, 0, 0), 1_356_001.),
+        ((-4712, 1, 1), (12, 0, 0), 0.),
+    ] {
+        let dt = Utc.ymd(y, m, d).and_hms(h, min, sec);
+        assert_float_eq!(JulianDay::from(dt).to_f64(), jd, abs <= 1e-9);
+        let back = DateTime::try_from(JulianDay::from(jd)).unwrap();
+        assert!((back - dt).num_microseconds().unwrap().abs() < 100);
+    }
+}
+
+#[test]
+fn julian_leap_days() {
+    // 1500 was a leap year in the Julian calendar but has no February 29 in
+    // chrono's proleptic Gregorian one
+    let feb_28 = JulianDay::from(Utc.ymd(1500, 2, 28));
+    assert!(matches!(
+        DateTime::<Utc>::try_from(feb_28 + 1.),
+        Err(Error::InvalidDate(_))
+    ));
+    assert_eq!(
+        DateTime::try_from(feb_28 + 2.).unwrap(),
+        Utc.ymd(1500, 3, 1).and_hms(0, 0, 0)
+    );
+}
+
+#[test]
+fn out_of_range() {
+    for &jd in &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1e300, -1e12] {
+        assert!(DateTime::<Utc>::try_from(JulianDay::from(jd)).is_err());
+    }
+}
+
+#[test]
+fn two_parts() {
+    let jd = JulianDay::new(2_451_545, -0.25);
+    assert_eq!((jd.day(), jd.fraction()), (2_451_544, 0.75));
+    assert_eq!(JulianDay::from(2_451_544.75), jd);
+    assert_eq!(JulianDay::J2000 - jd, 0.25);
+    assert!(jd < JulianDay::J2000);
+
+    // A nanosecond is lost in a single f64 but kept in two parts
+    let dt = Utc.ymd(2021, 6, 1).and_hms_nano(3, 4, 5, 6);
+    let jd = JulianDay::from(dt);
+    assert_eq!(DateTime::try_from(jd).unwrap(), dt);
+    assert_ne!(
+        DateTime::try_from(JulianDay::from(jd.to_f64())).unwrap(),
+        dt
+    );
+}
+
+proptest! {
+    #[test]
+    fn round_trips_to_the_nanosecond(dt in datetime()) {
+        let jd = JulianDay::from(dt);
+        prop_assert!((0. ..1.).contains(&jd.fraction()));
+        prop_assert_eq!(DateTime::try_from(jd).unwrap(), dt);
+    }
+
+    #[test]
+    fn julian_days_round_trip(day in -1_000_000_i64..3_000_000, fraction in 0. ..1.) {
+        let jd = JulianDay::new(day, fraction);
+        match DateTime::<Utc>::try_from(jd) {
+            // Half a nanosecond, and a little for the rounding of the fraction
+            Ok(dt) => prop_assert!((JulianDay::from(dt) - jd).abs() * 86_400e9 < 0.6),
+            // Only a Julian leap day that Gregorian lacks, the day after a
+            // February 28 in a century year
+            Err(_) => {
+                let before = DateTime::<Utc>::try_from(jd - 1.).unwrap();
+                prop_assert_eq!((before.month(), before.day()), (2, 28));
+                prop_assert!(before.year() % 100 == 0 && before.year() % 400 != 0);
+                prop_assert!(day < 2_299_161);
+            }
+        }
+    }
+
+    #[test]
+    fn proleptic_julian(dt in datetime()) {
+        // Before the reform every fourth year is a leap year
+        prop_assume!(dt.year() < 1578 && !(dt.month() == 2 && dt.day() == 29));
+        let later = dt.with_year(dt.year() + 4).unwrap();
+        prop_assert_eq!(JulianDay::from(later) - JulianDay::from(dt), 1_461.);
+    }
+
+    #[test]
+    fn proleptic_gregorian(dt in datetime()) {
+        // After it the calendar repeats every 400 years, and agrees with
+        // chrono's count of days
+        prop_assume!(dt.date() >= Utc.ymd(1582, 10, 15));
+        let later = dt.with_year(dt.year() + 400).unwrap();
+        prop_assert_eq!(JulianDay::from(later) - JulianDay::from(dt), 146_097.);
+
+        let since = dt - Utc.ymd(2000, 1, 1).and_hms(12, 0, 0);
+        let nanos = (since - Duration::days(since.num_days())).num_nanoseconds().unwrap();
+        let days = since.num_days() as f64 + nanos as f64 / 86_400e9;
+        prop_assert!(((JulianDay::from(dt) - JulianDay::J2000) - days).abs() < 1e-9);
+    }
+
+    #[test]
+    fn adds_durations(dt in datetime(), duration in duration()) {
+        let reform = Utc.ymd(1582, 10, 15);
+        prop_assume!(dt.date() >= reform && (dt + duration).date() >= reform);
+        let jd = JulianDay::from(dt);
+        prop_assert_eq!(DateTime::try_from(jd + duration).unwrap(), dt + duration);
+        prop_assert_eq!(DateTime::try_from(jd + duration - duration).unwrap(), dt);
+    }
+
+    #[test]
+    fn days_of_the_week(dt in datetime()) {
+        prop_assume!(dt.date() >= Utc.ymd(1582, 10, 15));
+        let day = Day::try_from(dt.weekday().num_days_from_sunday() as u8).unwrap();
+        prop_assert_eq!(dt.date().day_of_week(), day);
+    }
+
+    #[test]
+    fn never_panics(jd in any::<f64>(), days in any::<f64>()) {
+        let jd = JulianDay::from(jd);
+        let _ = DateTime::<Utc>::try_from(jd);
+        let _ = DateTime::<Utc>::try_from(jd + days);
+        let _ = DateTime::<Utc>::try_from(jd - Duration::max_value());
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{DateTime, Duration, TimeZone, Utc, MAX_DATETIME};
+use float_eq::assert_float_eq;
+
+use celestial::{
+    moon::{illuminated_fraction, libration, lunar_phases, phase_angle, LunarPosition, Phase},
+    JulianDay,
+};
+
+fn assert_near(actual: DateTime<Utc>, expected: DateTime<Utc>, tolerance: Duration) {
+    let error = (actual - expected).num_seconds().abs();
+    assert!(
+        error <= tolerance.num_seconds(),
+        "{} is {}s from {}",
+        actual,
+        error,
+        expected
+    );
+}
+
+#[test]
+fn lunar_position() {
+    // Meeus, Astronomical Algorithms, example 47.a: 1992 April 12.0 TD
+    let moon = LunarPosition::from_julian_day(JulianDay::from(2_448_724.5));
+    assert_float_eq!(moon.ecliptic.lon_degrees(), 133.162_655, abs <= 0.003);
+    assert_float_eq!(moon.ecliptic.lat_degrees(), -3.229_126, abs <= 0.001);
+    assert_float_eq!(moon.distance, 368_409.7, abs <= 10.);
+    assert_float_eq!(moon.parallax, 0.991_990, abs <= 1e-4);
+    assert_float_eq!(moon.apparent_longitude, 133.167_265, abs <= 0.003);
+    assert_float_eq!(
+        moon.equatorial.ra.to_degrees().value,
+        134.688_470,
+        abs <= 0.003
+    );
+    assert_float_eq!(moon.equatorial.dec_degrees(), 13.768_368, abs <= 0.001);
+}
+
+#[test]
+fn position_from_utc() {
+    // 1992 April 12.0 TD was 58.184 s earlier on UTC
+    let utc = Utc.ymd(1992, 4, 11).and_hms_milli(23, 59, 1, 816);
+    let moon = LunarPosition::from(utc);
+    let td = LunarPosition::from_julian_day(JulianDay::from(2_448_724.5));
+    assert_float_eq!(
+        moon.ecliptic.lon_degrees(),
+        td.ecliptic.lon_degrees(),
+        abs <= 1e-6
+    );
+
+    // Taking UTC for TT puts the Moon half a minute of arc behind
+    let behind = LunarPosition::from_julian_day(JulianDay::from(utc));
+    assert_float_eq!(
+        moon.ecliptic.lon_degrees() - behind.ecliptic.lon_degrees(),
+        0.0087,
+        abs <= 0.001
+    );
+}
+
+#[test]
+fn illumination() {
+    // Meeus example 48.a
+    let time = Utc.ymd(1992, 4, 12).and_hms(0, 0, 0);
+    assert_float_eq!(phase_angle(time), 69.075_6, abs <= 0.01);
+    assert_float_eq!(illuminated_fraction(time), 0.678_6, abs <= 1e-3);
+
+    // Full moon of 2021 June 24
+    let full = Utc.ymd(2021, 6, 24).and_hms(18, 40, 0);
+    assert!(illuminated_fraction(full) > 0.999);
+}
+
+#[test]
+fn optical_libration() {
+    // Meeus example 53.a
+    let libration = libration(Utc.ymd(1992, 4, 12).and_hms(0, 0, 0));
+    assert_float_eq!(libration.longitude, -1.206, abs <= 0.005);
+    assert_float_eq!(libration.latitude, 4.194, abs <= 0.005);
+}
+
+#[test]
+fn new_moon_1977() {
+    // Meeus example 49.a: 1977 February 18, 3h37m42s TD
+    let start = Utc.ymd(1977, 2, 14).and_hms(0, 0, 0);
+    let (phase, time) = lunar_phases(start..).next().unwrap();
+    assert_eq!(phase, Phase::NewMoon);
+    assert_near(
+        time,
+        Utc.ymd(1977, 2, 18).and_hms(3, 37, 42),
+        Duration::minutes(2),
+    );
+}
+
+#[test]
+fn phases_of_2021() {
+    // Times in UT, which is about a minute behind the dynamical time used
+    let expected = [
+        (Phase::NewMoon, Utc.ymd(2021, 6, 10).and_hms(10, 53, 0)),
+        (Phase::FirstQuarter, Utc.ymd(2021, 6, 18).and_hms(3, 54, 0)),
+        (Phase::FullMoon, Utc.ymd(2021, 6, 24).and_hms(18, 40, 0)),
+        (Phase::LastQuarter, Utc.ymd(2021, 7, 1).and_hms(21, 11, 0)),
+    ];
+    let phases: Vec<(Phase, DateTime<Utc>)> =
+        lunar_phases(Utc.ymd(2021, 6, 9).and_hms(0, 0, 0)..Utc.ymd(2021, 7, 2).and_hms(0, 0, 0))
+            .collect();
+
+    assert_eq!(phases.len(), expected.len());
+    for ((phase, time), (expected_phase, expected_time)) in phases.iter().zip(expected.iter()) {
+        assert_eq!(phase, expected_phase);
+        assert_near(*time, *expected_time, Duration::minutes(3));
+    }
+}
+
+#[test]
+fn phases_in_a_year() {
+    let start = Utc.ymd(2021, 1, 1).and_hms(0, 0, 0);
+    let end = Utc.ymd(2022, 1, 1).and_hms(0, 0, 0);
+    let phases: Vec<(Phase, DateTime<Utc>)> = lunar_phases(start..end).collect();
+    let count = |phase| phases.iter().filter(|x| x.0 == phase).count();
+
+    assert_eq!(count(Phase::NewMoon), 12);
+    assert_eq!(count(Phase::FullMoon), 12);
+    assert_eq!(count(Phase::LastQuarter), 13);
+    assert_eq!(phases.len(), 49);
+    assert!(phases.windows(2).all(|x| x[0].1 < x[1].1));
+}
+
+#[test]
+fn phases_at_the_end_of_time() {
+    // The new moon on Julian 1500 February 29 is skipped, not the rest
+    let start = Utc.ymd(1500, 2, 20).and_hms(0, 0, 0);
+    let end = Utc.ymd(1500, 3, 20).and_hms(0, 0, 0);
+    let phases: Vec<Phase> = lunar_phases(start..end).map(|x| x.0).collect();
+    assert_eq!(
+        phases,
+        [Phase::LastQuarter, Phase::FirstQuarter, Phase::FullMoon]
+    );
+
+    // Phases stop where chrono's dates do
+    let start = MAX_DATETIME - Duration::days(60);
+    let phases: Vec<(Phase, DateTime<Utc>)> = lunar_phases(start..).collect();
+    assert!((7..=9).contains(&phases.len()));
+}
@@ -0,0 +1,171 @@
+use chrono::{TimeZone, Utc};
+use chrono_tz::{Australia::Sydney, Europe::London};
+use float_eq::assert_float_eq;
+
+use celestial::{
+    coords::Equatorial,
+    degrees,
+    error::Error,
+    hours,
+    moon::LunarPosition,
+    observer::Observer,
+    rise_set::{rise_transit_set, Sun},
+    sidereal::SiderealTime,
+    JulianDay,
+};
+
+/// Palomar Observatory, from Meeus, Astronomical Algorithms, chapter 11.
+fn palomar() -> Observer {
+    Observer::new(33.356_111, -116.8625).with_elevation(1_706.)
+}
+
+#[test]
+fn geocentric_place() {
+    // Meeus, example 11.a
+    let (rho_sin, rho_cos) = palomar().geocentric();
+    assert_float_eq!(rho_sin, 0.546_861, abs <= 1e-6);
+    assert_float_eq!(rho_cos, 0.836_339, abs <= 1e-6);
+
+    let equator = Observer::new(0., 0.);
+    assert_eq!(equator.geocentric_latitude(), 0.);
+    assert_float_eq!(equator.geocentric_distance(), 6_378.137, abs <= 1e-9);
+    let pole = Observer::new(90., 0.);
+    assert_float_eq!(pole.geocentric_distance(), 6_356.752, abs <= 1e-3);
+    assert_float_eq!(
+        Observer::new(45., 0.).geocentric_latitude(),
+        44.8076,
+        abs <= 1e-4
+    );
+
+    // Turning with the Earth, the site comes round to the equinox at a
+    // sidereal time equal to minus its longitude
+    let site = palomar();
+    let fixed = site.earth_fixed();
+    let [x, y, _] = site.position(SiderealTime::from_hours(116.8625 / 15.));
+    assert_float_eq!(y, 0., abs <= 1e-9);
+    assert_float_eq!(x, fixed[0].hypot(fixed[1]), abs <= 1e-9);
+}
+
+#[test]
+fn diurnal_parallax() {
+    // Meeus, example 40.a: Mars from Palomar on 2003 August 28 at 3:17 UT
+    let jd = JulianDay::from(Utc.ymd(2003, 8, 28).and_hms(3, 17, 0));
+    let mars = Equatorial::new(degrees(339.530_208).to_hours(), degrees(-15.771_083));
+    let parallax = ((8.794_f64 / 3600.).to_radians().sin() / 0.372_76)
+        .asin()
+        .to_degrees();
+
+    let topocentric = palomar().topocentric(mars, parallax, jd);
+    assert_float_eq!(
+        topocentric.ra.to_seconds().value / 15.,
+        (22. + 38. / 60. + 8.54 / 3600.) * 3600.,
+        abs <= 0.05
+    );
+    assert_float_eq!(topocentric.dec_degrees(), -15.775, abs <= 0.1 / 3600.);
+
+    // The Moon drops by nearly its parallax towards the horizon
+    let jd = JulianDay::from(Utc.ymd(2021, 3, 1).and_hms(22, 0, 0));
+    let moon = LunarPosition::from_julian_day(jd);
+    let london = Observer::new(51.5074, -0.1278);
+    let geocentric = london.horizon(moon.equatorial, jd);
+    let topocentric = london.horizon(london.topocentric(moon.equatorial, moon.parallax, jd), jd);
+    assert_float_eq!(
+        (geocentric.altitude - topocentric.altitude)
+            .to_degrees()
+            .value,
+        moon.parallax * geocentric.altitude.to_radians().value.cos(),
+        abs <= 0.01
+    );
+}
+
+#[test]
+fn horizon_agrees_with_coords() {
+    // Both reckon from apparent sidereal time
+    let dt = Utc.ymd(2021, 3, 1).and_hms(22, 0, 0);
+    let london = Observer::new(51.5074, -0.1278);
+    let sirius = Equatorial::new(hours(6.752_5), degrees(-16.716));
+    let observer = london.horizon(sirius, JulianDay::from(dt));
+    let coords = sirius.to_horizon(dt, &london);
+    assert_float_eq!(
+        observer.azimuth.to_degrees().value,
+        coords.azimuth.to_degrees().value,
+        abs <= 1e-9
+    );
+    assert_float_eq!(
+        observer.altitude.to_degrees().value,
+        coords.altitude.to_degrees().value,
+        abs <= 1e-9
+    );
+}
+
+#[test]
+fn horizon_dip() {
+    // From a thousand metres the Sun rises several minutes early
+    let date = London.ymd(2021, 3, 20);
+    let sea_level = Observer::new(51.5074, -0.1278);
+    let hill = sea_level.with_elevation(1_000.);
+    assert_float_eq!(hill.dip(), 1.094, abs <= 1e-3);
+
+    let low = rise_transit_set(&Sun::Disc, &sea_level, date);
+    let high = rise_transit_set(&Sun::Disc, &hill, date);
+    let earlier = (*low.rise().unwrap() - *high.rise().unwrap()).num_seconds();
+    let later = (*high.set().unwrap() - *low.set().unwrap()).num_seconds();
+    assert!((240..600).contains(&earlier));
+    assert!((240..600).contains(&later));
+
+    // Twilight is reckoned from the true horizon
+    let civil = |observer| rise_transit_set(&Sun::CivilTwilight, observer, date);
+    assert_eq!(civil(&sea_level).rise(), civil(&hill).rise());
+}
+
+#[test]
+fn parses_hemispheres() {
+    let greenwich: Observer = "51.4769N, 0.0005W, 46m".parse().unwrap();
+    assert_eq!(
+        (greenwich.latitude, greenwich.longitude, greenwich.elevation),
+        (51.4769, -0.0005, 46.)
+    );
+    let usno: Observer = "38.9214n 77.0656w".parse().unwrap();
+    assert_eq!((usno.latitude, usno.longitude), (38.9214, -77.0656));
+    let sydney: Observer = "-33.8688, 151.2093".parse().unwrap();
+    assert_eq!((sydney.latitude, sydney.longitude), (-33.8688, 151.2093));
+
+    for bad in [
+        "51.4769",
+        "0.0005W 51.4769N",
+        "-10N 20E",
+        "91N 0E",
+        "0N 181E",
+        "10N 20E high",
+        "1 2 3 4",
+    ] {
+        assert!(
+            matches!(bad.parse::<Observer>(), Err(Error::Parse(_))),
+            "{}",
+            bad
+        );
+    }
+}
+
+#[test]
+fn serde() {
+    let sydney = Observer::new(-33.8688, 151.2093)
+        .with_elevation(58.)
+        .with_atmosphere(25., 1015.)
+        .with_timezone(Sydney);
+    let json = serde_json::to_string(&sydney).unwrap();
+    assert!(json.contains("\"tz\":\"Australia/Sydney\""));
+    assert_eq!(serde_json::from_str::<Observer>(&json).unwrap(), sydney);
+
+    // Anything but the place can be left out
+    let bare: Observer = serde_json::from_str(r#"{"latitude": 51.5, "longitude": -0.13}"#).unwrap();
+    assert_eq!(bare, Observer::new(51.5, -0.13));
+    assert_eq!(bare.atmosphere().pressure, 1010.);
+    assert!(serde_json::from_str::<Observer>(r#"{"latitude": 51.5}"#).is_err());
+
+    let dt = Utc.ymd(2021, 6, 21).and_hms(12, 0, 0);
+    assert_eq!(
+        sydney.local_time(&dt).to_string(),
+        "2021-06-21 22:00:00 AEST"
+    );
+}
@@ -0,0 +1,147 @@
+use chrono::{DateTime, TimeZone, Utc};
+use chrono_tz::{Europe::London, Europe::Oslo, Tz};
+
+use celestial::{
+    coords::{local_sidereal_time, Equatorial, HourAngleCoords},
+    degrees, hours,
+    observer::Observer,
+    rise_set::{rise_transit_set, Body, Moon, RiseTransitSet, Star, Sun},
+    timescale::{JulianDate, Tt},
+    Angle,
+};
+
+fn london() -> Observer {
+    Observer::new(51.5074, -0.1278)
+}
+
+fn tromso() -> Observer {
+    Observer::new(69.6492, 18.9553)
+}
+
+fn assert_near(actual: Option<&DateTime<Tz>>, expected: DateTime<Tz>, minutes: i64) {
+    let actual = actual.expect("no event");
+    let error = (*actual - expected).num_seconds().abs();
+    assert!(
+        error <= minutes * 60,
+        "{} is {}s from {}",
+        actual,
+        error,
+        expected
+    );
+}
+
+/// Altitude of a body's centre at a moment.
+fn altitude<B: Body>(body: &B, observer: &Observer, dt: &DateTime<Tz>) -> Angle {
+    let position = body.equatorial(JulianDate::<Tt>::from_datetime(*dt));
+    let lst = local_sidereal_time(*dt, observer).to_hours().value;
+    HourAngleCoords {
+        hour_angle: hours((lst - position.ra_hours()).rem_euclid(24.)),
+        dec: position.dec,
+    }
+    .to_horizon(degrees(observer.latitude))
+    .altitude
+}
+
+#[test]
+fn sunrise_and_sunset() {
+    // London, midsummer 2021: sunrise 04:43, noon 13:02, sunset 21:21 BST
+    let date = London.ymd(2021, 6, 21);
+    let events = rise_transit_set(&Sun::Disc, &london(), date);
+    assert_near(events.rise(), London.ymd(2021, 6, 21).and_hms(4, 43, 30), 1);
+    assert_near(
+        events.transit(),
+        London.ymd(2021, 6, 21).and_hms(13, 2, 10),
+        1,
+    );
+    assert_near(events.set(), London.ymd(2021, 6, 21).and_hms(21, 21, 30), 1);
+}
+
+#[test]
+fn events_are_on_the_horizon() {
+    let date = London.ymd(2021, 3, 1);
+    for body in [Sun::Disc, Sun::CivilTwilight, Sun::AstronomicalTwilight] {
+        let events = rise_transit_set(&body, &london(), date);
+        for time in [events.rise(), events.set()] {
+            let time = time.unwrap();
+            let h0 = body.standard_altitude(JulianDate::from_datetime(*time), &london());
+            assert!(
+                (altitude(&body, &london(), time) - h0)
+                    .to_degrees()
+                    .value
+                    .abs()
+                    < 0.01
+            );
+        }
+    }
+
+    for day in 1..=28 {
+        let date = London.ymd(2021, 2, day);
+        let events = rise_transit_set(&Moon, &london(), date);
+        for time in [events.rise(), events.set()].iter().flatten() {
+            let h0 = Moon.standard_altitude(JulianDate::from_datetime(**time), &london());
+            assert!(
+                (altitude(&Moon, &london(), time) - h0)
+                    .to_degrees()
+                    .value
+                    .abs()
+                    < 0.01
+            );
+            assert_eq!(time.date(), date);
+        }
+    }
+}
+
+#[test]
+fn moon_skips_a_rising() {
+    let days: Vec<RiseTransitSet<Tz>> = (1..=31)
+        .map(|day| rise_transit_set(&Moon, &london(), London.ymd(2021, 1, day)))
+        .collect();
+    let rises = days.iter().filter(|x| x.rise().is_some()).count();
+    let sets = days.iter().filter(|x| x.set().is_some()).count();
+
+    // The Moon rises about 50 minutes later each day
+    assert_eq!(rises, 30);
+    assert_eq!(sets, 30);
+}
+
+#[test]
+fn midnight_sun_and_polar_night() {
+    let summer = rise_transit_set(&Sun::Disc, &tromso(), Oslo.ymd(2021, 6, 21));
+    assert!(matches!(summer, RiseTransitSet::Circumpolar { .. }));
+
+    let winter = rise_transit_set(&Sun::Disc, &tromso(), Oslo.ymd(2021, 12, 21));
+    assert!(matches!(winter, RiseTransitSet::NeverRises { .. }));
+    assert!(winter.transit().is_some());
+
+    // There is still civil twilight around noon
+    let twilight = rise_transit_set(&Sun::CivilTwilight, &tromso(), Oslo.ymd(2021, 12, 21));
+    assert!(twilight.rise().unwrap() < twilight.set().unwrap());
+}
+
+#[test]
+fn fixed_stars() {
+    let polaris = Star(Equatorial::new(hours(2.530), degrees(89.264)));
+    let canopus = Star(Equatorial::new(hours(6.399), degrees(-52.696)));
+    let sirius = Star(Equatorial::new(hours(6.752), degrees(-16.716)));
+    let date = Utc.ymd(2021, 4, 1);
+
+    assert!(matches!(
+        rise_transit_set(&polaris, &london(), date),
+        RiseTransitSet::Circumpolar { .. }
+    ));
+    assert!(matches!(
+        rise_transit_set(&canopus, &london(), date),
+        RiseTransitSet::NeverRises { .. }
+    ));
+
+    let events = rise_transit_set(&sirius, &london(), date);
+    let (rise, transit, set) = (
+        *events.rise().unwrap(),
+        *events.transit().unwrap(),
+        *events.set().unwrap(),
+    );
+    let lst = local_sidereal_time(transit, &london()).to_hours().value;
+    assert!((lst - sirius.0.ra_hours()).abs() < 0.001);
+    assert!(rise < transit && transit < set);
+    assert!(((transit - rise) - (set - transit)).num_seconds().abs() <= 2);
+}
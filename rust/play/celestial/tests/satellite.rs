@@ -0,0 +1,263 @@
+use std::convert::TryFrom;
+
+use chrono::{DateTime, Duration, TimeZone, Timelike, Utc};
+use float_eq::assert_float_eq;
+
+use celestial::{
+    degrees,
+    error::Error,
+    observer::Observer,
+    satellite::{Sgp4, Teme, Tle},
+    sidereal::SiderealTime,
+    sun::SolarPosition,
+    JulianDay,
+};
+
+/// The test element sets of Spacetrack Report #3 and of Vallado et al.
+const SGP4_TLES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/sgp4.tle");
+
+const ISS: &str = "ISS (ZARYA)
+1 25544U 98067A   21015.53472222  .00001264  00000-0  31067-4 0  9993
+2 25544  51.6460 314.2834 0000707 251.7406 232.7848 15.49206282264768
+";
+
+fn propagator(catalog_number: u32) -> Sgp4 {
+    let tles = Tle::from_file(SGP4_TLES).unwrap();
+    let tle = tles
+        .iter()
+        .find(|tle| tle.catalog_number == catalog_number)
+        .unwrap();
+    Sgp4::new(tle).unwrap()
+}
+
+fn assert_state(state: Teme, position: [f64; 3], velocity: [f64; 3], km: f64, km_s: f64) {
+    for i in 0..3 {
+        assert_float_eq!(state.position[i], position[i], abs <= km);
+        assert_float_eq!(state.velocity[i], velocity[i], abs <= km_s);
+    }
+}
+
+#[test]
+fn element_sets() {
+    let tle = propagator(5).tle().clone();
+    assert_eq!(tle.name, None);
+    assert_float_eq!(tle.eccentricity, 0.1859667, abs <= 1e-12);
+    assert_float_eq!(tle.mean_motion, 10.82419157, abs <= 1e-12);
+    assert_float_eq!(tle.ndot, 2.3e-7, abs <= 1e-15);
+    assert_float_eq!(tle.bstar, 2.8098e-5, abs <= 1e-15);
+    let epoch = Utc.ymd(2000, 6, 27).and_hms_micro(18, 50, 19, 733_568);
+    let difference = DateTime::try_from(tle.epoch).unwrap() - epoch;
+    assert!(difference.num_microseconds().unwrap().abs() < 10);
+
+    let iss: Tle = ISS.parse().unwrap();
+    assert_eq!(iss.name.as_deref(), Some("ISS (ZARYA)"));
+    assert_eq!(iss.catalog_number, 25_544);
+    assert_float_eq!(iss.inclination, 51.646, abs <= 1e-12);
+    assert_eq!(iss.nddot, 0.);
+
+    assert!(matches!(
+        ISS.replace("51.6460", "51.6x60").parse::<Tle>(),
+        Err(Error::Parse(_))
+    ));
+    assert!(ISS
+        .lines()
+        .take(2)
+        .collect::<String>()
+        .parse::<Tle>()
+        .is_err());
+    assert!(Tle::from_file("/nonexistent/tle").is_err());
+    assert_eq!(Tle::from_file(SGP4_TLES).unwrap().len(), 3);
+}
+
+#[test]
+fn vallado_near_earth() {
+    // Vanguard 1, from the verification output of Vallado et al. (2006)
+    let sgp4 = propagator(5);
+    assert_state(
+        sgp4.propagate(0.).unwrap(),
+        [7022.46529266, -1400.08296755, 0.03995155],
+        [1.893841015, 6.405893759, 4.534807250],
+        1e-6,
+        1e-9,
+    );
+    assert_state(
+        sgp4.propagate(360.).unwrap(),
+        [-7154.03120202, -3783.17682504, -3536.19412294],
+        [4.741887409, -4.151817765, -2.093935425],
+        1e-6,
+        1e-9,
+    );
+}
+
+#[test]
+fn spacetrack_report_sgp4() {
+    // The report worked in single precision, good to tens of metres
+    let sgp4 = propagator(88_888);
+    let expected = [
+        (
+            0.,
+            [2328.97048951, -5995.22076416, 1719.97067261],
+            [2.91207230, -0.98341546, -7.09081703],
+        ),
+        (
+            360.,
+            [2456.10705566, -6071.93853760, 1222.89727783],
+            [2.67938992, -0.44829041, -7.22879231],
+        ),
+        (
+            720.,
+            [2567.56195068, -6112.50384522, 713.96397400],
+            [2.44024599, 0.09810869, -7.31995916],
+        ),
+        (
+            1080.,
+            [2663.09078980, -6115.48229980, 196.39640427],
+            [2.19611494, 0.65241340, -7.36282371],
+        ),
+        (
+            1440.,
+            [2742.55133057, -6079.67144775, -326.38095856],
+            [1.94850853, 1.21106635, -7.35619083],
+        ),
+    ];
+    for (minutes, position, velocity) in expected {
+        assert_state(
+            sgp4.propagate(minutes).unwrap(),
+            position,
+            velocity,
+            0.05,
+            1e-4,
+        );
+    }
+}
+
+#[test]
+fn spacetrack_report_sdp4() {
+    // A twelve-hour orbit, with the lunar and solar terms
+    let sdp4 = propagator(11_801);
+    assert_state(
+        sdp4.propagate(0.).unwrap(),
+        [7473.37102491, 428.94748312, 5828.74846783],
+        [5.107155391, 6.444680305, -0.186133297],
+        1e-6,
+        1e-9,
+    );
+    let expected = [
+        (
+            360.,
+            [-3305.22537232, 32410.86328125, -24697.17675781],
+            [-1.30113538, -1.15131518, -0.28333528],
+        ),
+        (
+            720.,
+            [14271.28759766, 24110.46411133, -4725.76837158],
+            [-0.32050445, 2.67984074, -2.08405289],
+        ),
+        (
+            1080.,
+            [-9990.05883044, 22717.35522461, -23616.89062500],
+            [-1.01667246, -2.29026759, 0.72892364],
+        ),
+        (
+            1440.,
+            [9787.86975097, 33753.34667969, -15030.81176758],
+            [-1.09425966, 0.92358845, -1.52230928],
+        ),
+    ];
+    for (minutes, position, velocity) in expected {
+        assert_state(
+            sdp4.propagate(minutes).unwrap(),
+            position,
+            velocity,
+            0.05,
+            1e-4,
+        );
+    }
+}
+
+#[test]
+fn decays() {
+    // Given a thousand times the drag, the orbit of 88888 soon falls in
+    let mut tle = propagator(88_888).tle().clone();
+    tle.bstar *= 1000.;
+    let sgp4 = Sgp4::new(&tle).unwrap();
+    assert!(sgp4.propagate(0.).is_ok());
+    assert!(matches!(sgp4.propagate(1000.), Err(Error::Propagation(_))));
+}
+
+#[test]
+fn overhead() {
+    // Standing under the satellite puts it near the zenith, off by the few
+    // kilometres between straight down and the plumb line on an oblate
+    // Earth
+    let sgp4 = Sgp4::new(&ISS.parse().unwrap()).unwrap();
+    let dt = Utc.ymd(2021, 1, 15).and_hms(18, 0, 0);
+    let [x, y, z] = sgp4.at(dt).unwrap().position;
+    let longitude = y.atan2(x).to_degrees() - SiderealTime::from(dt).degrees();
+    let latitude = z.atan2(x.hypot(y)).to_degrees();
+    let observer = Observer::new(latitude, (longitude + 540.) % 360. - 180.);
+
+    let seen = sgp4.observe(dt, &observer).unwrap();
+    assert!(seen.horizon.altitude > degrees(85.));
+    assert_float_eq!(seen.range, 420., abs <= 20.);
+}
+
+#[test]
+fn earth_shadow() {
+    let jd = JulianDay::from(Utc.ymd(2021, 1, 15).and_hms(18, 0, 0));
+    let sun = SolarPosition::from_julian_day(jd).equatorial;
+    let (ra, dec) = (sun.ra.to_radians().value, sun.dec.to_radians().value);
+    let towards = [dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin()];
+    let across = [-towards[1], towards[0]].map(|x| x / dec.cos());
+    let at = |distance: f64, sideways: f64| Teme {
+        position: [
+            distance * towards[0] + sideways * across[0],
+            distance * towards[1] + sideways * across[1],
+            distance * towards[2],
+        ],
+        velocity: [0.; 3],
+    };
+
+    assert!(at(7000., 0.).is_sunlit(jd));
+    assert!(!at(-7000., 0.).is_sunlit(jd));
+    assert!(!at(-7000., 6000.).is_sunlit(jd));
+    assert!(at(-7000., 6500.).is_sunlit(jd));
+}
+
+#[test]
+fn iss_passes() {
+    let sgp4 = Sgp4::new(&ISS.parse().unwrap()).unwrap();
+    let london = Observer::new(51.5074, -0.1278);
+    let from = Utc.ymd(2021, 1, 15).and_hms(12, 0, 0);
+    let to = Utc.ymd(2021, 1, 17).and_hms(12, 0, 0);
+
+    let passes = sgp4.passes(&london, from, to, degrees(10.)).unwrap();
+    assert!(!passes.is_empty());
+    for pass in &passes {
+        assert!(pass.rise.time < pass.culmination.time);
+        assert!(pass.culmination.time < pass.set.time);
+        assert!((pass.set.time - pass.rise.time).num_minutes() < 10);
+        for event in [&pass.rise, &pass.set] {
+            assert!((degrees(10.)..degrees(10.5)).contains(&event.horizon.altitude));
+        }
+        assert!(pass.max_elevation() >= pass.rise.horizon.altitude);
+
+        // No higher point between
+        let mut t = pass.rise.time;
+        while t < pass.set.time {
+            let altitude = sgp4.observe(t, &london).unwrap().horizon.altitude;
+            assert!(altitude <= pass.max_elevation() + degrees(1e-3));
+            t = t + Duration::seconds(10);
+        }
+
+        // Only seen in the evening or before dawn
+        let hour = pass.culmination.time.hour();
+        if pass.visible {
+            assert!(!(8..16).contains(&hour));
+        }
+        if (10..15).contains(&hour) {
+            assert!(!pass.visible);
+        }
+    }
+    assert!(passes.iter().any(|pass| pass.visible));
+}
@@ -0,0 +1,109 @@
+use chrono::{Duration, LocalResult, TimeZone, Utc};
+use chrono_tz::America::Havana;
+use float_eq::assert_float_eq;
+
+use celestial::{
+    degrees,
+    sidereal::{earth_rotation_angle, equation_of_equinoxes, SiderealTime},
+    timescale::{JulianDate, Ut1},
+    JulianDay,
+};
+
+#[test]
+fn meeus_12a() {
+    // 1987 April 10, 0h UT
+    let jd = JulianDay::from(2_446_895.5);
+    assert_eq!(format!("{:.4}", SiderealTime::mean(jd)), "13:10:46.3668");
+
+    // Within the accuracy of the four-term nutation
+    let apparent = SiderealTime::apparent(jd);
+    let expected = SiderealTime::from_hms(13, 10, 46.1351);
+    assert_float_eq!(
+        apparent.hours() * 3600.,
+        expected.hours() * 3600.,
+        abs <= 0.01
+    );
+    assert_float_eq!(equation_of_equinoxes(jd) * 3600., -0.2317, abs <= 0.01);
+}
+
+#[test]
+fn meeus_12b() {
+    // 1987 April 10, 19h 21m UT
+    let st = SiderealTime::from(Utc.ymd(1987, 4, 10).and_hms(19, 21, 0));
+    assert_eq!(format!("{:.4}", st), "08:34:57.0896");
+    assert_float_eq!(st.degrees(), 128.737_873_4, abs <= 1e-6);
+}
+
+#[test]
+fn iau_2006() {
+    // Earth rotation angle at J2000.0 UT1 (IERS Conventions)
+    assert_float_eq!(
+        earth_rotation_angle(JulianDay::J2000),
+        280.460_618_2,
+        abs <= 1e-6
+    );
+
+    // The 1982 and 2006 expressions agree to a few milliseconds this century
+    for year in [1987, 2000, 2021, 2050] {
+        let dt = Utc.ymd(year, 4, 10).and_hms(19, 21, 0);
+        let ut1 = JulianDate::<Ut1>::new(JulianDay::from(dt));
+        let difference = |a: SiderealTime, b: SiderealTime| (a.hours() - b.hours()) * 3600.;
+        assert!(difference(SiderealTime::mean_2006(ut1), SiderealTime::mean(ut1.jd)).abs() < 0.01);
+        assert!(
+            difference(
+                SiderealTime::apparent_2006(ut1),
+                SiderealTime::apparent(ut1.jd)
+            )
+            .abs()
+                < 0.01
+        );
+    }
+}
+
+#[test]
+fn wraps_into_a_day() {
+    assert_eq!(SiderealTime::from_hours(-1.).hours(), 23.);
+    assert_eq!(SiderealTime::from_hours(49.5).hours(), 1.5);
+    assert_eq!(SiderealTime::from_hours(-1e-17).hours(), 0.);
+    assert_eq!(SiderealTime::from_degrees(-90.).to_string(), "18:00:00");
+    assert_eq!(SiderealTime::from_hms(23, 59, 59.9).to_string(), "00:00:00");
+    assert_eq!(
+        format!("{:.1}", SiderealTime::from_hms(23, 59, 59.9)),
+        "23:59:59.9"
+    );
+
+    let (h, m, s) = SiderealTime::from_hours(13.5).local(degrees(-45.)).hms();
+    assert_eq!((h, m), (10, 30));
+    assert_float_eq!(s, 0., abs <= 1e-9);
+    assert_eq!(
+        SiderealTime::from_hours(1.).greenwich(degrees(30.)).hours(),
+        23.
+    );
+}
+
+#[test]
+fn to_universal_time() {
+    let date = Utc.ymd(1987, 4, 10);
+    let dt = date.and_hms(19, 21, 0);
+    let ut = SiderealTime::from(dt).to_ut(date).single().unwrap();
+    assert!((ut - dt).num_microseconds().unwrap().abs() < 10);
+
+    // The sidereal time just after midnight comes round again before the
+    // day is out
+    let dt = date.and_hms(0, 1, 0);
+    match SiderealTime::from(dt).to_ut(date) {
+        LocalResult::Ambiguous(first, second) => {
+            assert!((first - dt).num_microseconds().unwrap().abs() < 10);
+            let day = Duration::nanoseconds((86_400e9 / 1.002_737_909_350_795) as i64);
+            assert!((second - first - day).num_milliseconds().abs() < 1);
+        }
+        other => panic!("expected two times, not {:?}", other),
+    }
+
+    // Cuba's clocks went from midnight to 1 am on 2021 March 14, leaving a
+    // day of 23 hours
+    let date = Havana.ymd(2021, 3, 14);
+    let dt = Havana.ymd(2021, 3, 14).and_hms(1, 30, 0);
+    let ut = SiderealTime::from(dt).to_ut(date).single().unwrap();
+    assert!((ut - dt).num_microseconds().unwrap().abs() < 10);
+}
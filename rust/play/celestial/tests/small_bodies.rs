@@ -0,0 +1,268 @@
+use chrono::{Datelike, Duration, TimeZone, Utc};
+use float_eq::assert_float_eq;
+
+use celestial::{
+    coords::Equatorial,
+    corrections::{precess, J2000},
+    degrees,
+    error::Error,
+    planets::solve_kepler,
+    small_bodies::{parse_elements, solve_universal, Magnitude, Orbit, SmallBody},
+    JulianDay,
+};
+
+/// Comet Encke at its 1990 return, Meeus, Astronomical Algorithms, example
+/// 33.b, in the MPC's comet format.
+const ENCKE: &str = "0002P         1990 10 28.5450 0.330886   0.850220  186.2335  334.7501   11.9452  19901028   9.8  6.0  2P/Encke                                                 MPC 12345";
+
+/// The same orbit in MPCORB's format, with the mean anomaly at 1990
+/// October 10.0.
+const ENCKE_MPCORB: &str = "x0002    9.80  0.15 J90AA 354.43332  186.23352  334.75006   11.94524  0.8502196 0.30017125    2.2091404                                                               Encke as an asteroid";
+
+const MPCORB: &str = "MINOR PLANET CENTER ORBIT DATABASE (MPCORB)
+
+Des'n     H     G   Epoch     M        Peri.      Node       Incl.       e            n           a        Reference #Obs #Opp    Arc    rms  Perts   Computer
+----------------------------------------------------------------------------------------------------------------------------------------------------------------
+00001    3.34  0.12 K2155 162.68631   73.73161   80.28698   10.58862  0.0775571 0.21406009    2.7676569  0 E2021-06   7330  51                                        (1) Ceres
+K21A00A       0.15  K2155  12.00000   40.00000  120.00000    5.00000  0.2000000 0.30000000    2.2000000
+";
+
+const OUMUAMUA: &str = "     I2017U1  2017 09 09.4894 0.255240   1.201133  241.8105   24.5969  122.7417  20171123  22.1  4.0  1I/'Oumuamua";
+
+#[test]
+fn universal_anomaly() {
+    // An ellipse, two orbits and a bit after perihelion
+    let (a, e): (f64, f64) = (2.5, 0.6);
+    let q = a * (1. - e);
+    let motion = 0.017_202_098_95 / a.powf(1.5);
+    let ecc = solve_kepler(2., e);
+    let days = (ecc - e * ecc.sin() + 4. * std::f64::consts::PI) / motion;
+    assert_float_eq!(solve_universal(q, e, days), a.sqrt() * ecc, abs <= 1e-10);
+    assert_float_eq!(solve_universal(q, e, -days), -a.sqrt() * ecc, abs <= 1e-10);
+
+    // A parabola a quarter of the way round, by Barker's equation
+    let q: f64 = 1.5;
+    let days = 4. / 3. * (2. * q.powi(3)).sqrt() / 0.017_202_098_95;
+    assert_float_eq!(solve_universal(q, 1., days), (2. * q).sqrt(), abs <= 1e-10);
+
+    // A hyperbola, far out
+    let (a, e): (f64, f64) = (-0.8, 3.);
+    let q = a * (1. - e);
+    let anomaly = 5_f64;
+    let days = (e * anomaly.sinh() - anomaly) * (-a).powf(1.5) / 0.017_202_098_95;
+    assert_float_eq!(
+        solve_universal(q, e, days),
+        (-a).sqrt() * anomaly,
+        abs <= 1e-9
+    );
+}
+
+#[test]
+fn distance_from_the_sun() {
+    let orbit = |eccentricity: f64| Orbit {
+        perihelion_distance: 1.2,
+        eccentricity,
+        inclination: 30.,
+        node: 40.,
+        argument_of_perihelion: 50.,
+        perihelion_time: J2000,
+    };
+    for &e in &[0., 0.5, 0.999, 1., 1.001, 2.] {
+        let orbit = orbit(e);
+        assert_float_eq!(orbit.heliocentric(J2000).radius, 1.2, abs <= 1e-12);
+
+        // Symmetrical either side of perihelion
+        let (before, after) = (
+            orbit.heliocentric(J2000 - 100.),
+            orbit.heliocentric(J2000 + 100.),
+        );
+        assert_float_eq!(before.radius, after.radius, abs <= 1e-10);
+        assert!(after.radius > 1.2);
+    }
+
+    let ellipse = orbit(0.5);
+    let period = ellipse.period().unwrap();
+    assert_float_eq!(period, 2.4_f64.powf(1.5) * 365.256_9, rmax <= 1e-4);
+    assert_float_eq!(
+        ellipse.heliocentric(J2000 + period / 2.).radius,
+        3.6,
+        abs <= 1e-9
+    );
+    assert_eq!(orbit(1.).period(), None);
+}
+
+#[test]
+fn comet_encke() {
+    let encke: SmallBody = ENCKE.parse().unwrap();
+    assert_eq!(encke.designation, "2P/Encke");
+    assert_eq!(encke.magnitude, Some(Magnitude::Comet { h: 9.8, k: 6. }));
+    assert_float_eq!(
+        encke.orbit.semi_major_axis().unwrap(),
+        2.209_140,
+        abs <= 1e-5
+    );
+
+    // Meeus gives 10h34m14.2s +19°09'31" on the equinox of J2000.0, which
+    // precession takes to the date before nutation and aberration
+    let jd = JulianDay::from(2_448_170.5);
+    let position = encke.geocentric(jd);
+    assert_float_eq!(position.distance, 0.824_28, abs <= 1e-5);
+    let meeus = precess(
+        Equatorial::new(
+            "10h 34m 14.2s".parse().unwrap(),
+            "19° 09' 31\"".parse().unwrap(),
+        ),
+        J2000,
+        jd,
+    );
+    assert!(position.equatorial.separation(&meeus) < degrees(0.01));
+
+    // In MPCORB's form it is the same orbit
+    let asteroid: SmallBody = ENCKE_MPCORB.parse().unwrap();
+    assert_float_eq!(
+        asteroid.orbit.perihelion_distance,
+        encke.orbit.perihelion_distance,
+        abs <= 1e-6
+    );
+    assert!(
+        asteroid
+            .geocentric(jd)
+            .equatorial
+            .separation(&position.equatorial)
+            < degrees(1e-4)
+    );
+}
+
+#[test]
+fn mpcorb() {
+    let bodies = parse_elements(MPCORB).unwrap();
+    assert_eq!(bodies.len(), 2);
+
+    let ceres = &bodies[0];
+    assert_eq!(ceres.designation, "(1) Ceres");
+    assert_eq!(
+        ceres.magnitude,
+        Some(Magnitude::Asteroid { h: 3.34, g: 0.12 })
+    );
+    assert_float_eq!(ceres.orbit.eccentricity, 0.077_557_1, abs <= 1e-9);
+    assert_float_eq!(
+        ceres.orbit.perihelion_distance,
+        2.767_656_9 * (1. - 0.077_557_1),
+        abs <= 1e-9
+    );
+    assert_float_eq!(ceres.orbit.period().unwrap() / 365.25, 4.6, abs <= 0.01);
+
+    // The epoch, 2021 May 5.0, is where the mean anomaly puts it
+    let epoch = JulianDay::from(Utc.ymd(2021, 5, 5));
+    let since = ((epoch - ceres.orbit.perihelion_time) * 0.214_060_09).rem_euclid(360.);
+    assert_float_eq!(since, 162.686_31, abs <= 1e-3);
+
+    // No name, and no absolute magnitude
+    assert_eq!(bodies[1].designation, "K21A00A");
+    assert_eq!(bodies[1].magnitude, None);
+    assert!(bodies[1].geocentric(epoch).magnitude.is_nan());
+
+    let bad = ENCKE_MPCORB.replace("0.8502196", "0.85x2196");
+    let error = parse_elements(&format!("{}\n{}", ENCKE_MPCORB, bad)).unwrap_err();
+    assert!(
+        matches!(&error, Error::Parse(message) if message.ends_with("on line 2")),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn interstellar() {
+    let oumuamua: SmallBody = OUMUAMUA.parse().unwrap();
+    assert_eq!(oumuamua.designation, "1I/'Oumuamua");
+    assert_eq!(oumuamua.orbit.semi_major_axis(), None);
+
+    // Close to the Earth in the middle of October 2017, and leaving fast
+    let closest = JulianDay::from(Utc.ymd(2017, 10, 14));
+    let position = oumuamua.geocentric(closest);
+    assert!((0.15..0.17).contains(&position.distance));
+    let later = oumuamua.geocentric(closest + 365.);
+    assert!(later.radius > 5.);
+}
+
+#[test]
+fn magnitudes() {
+    let ceres = Magnitude::Asteroid { h: 3.34, g: 0.12 };
+    assert_float_eq!(ceres.at(1., 1., 0.), 3.34, abs <= 1e-12);
+    assert_float_eq!(
+        ceres.at(2.8, 1.8, 0.),
+        3.34 + 5. * (2.8_f64 * 1.8).log10(),
+        abs <= 1e-12
+    );
+    assert!(ceres.at(2.8, 1.8, 20.) > ceres.at(2.8, 1.8, 0.) + 0.5);
+
+    let comet = Magnitude::Comet { h: 9.8, k: 6. };
+    assert_float_eq!(
+        comet.at(0.5, 1., 90.),
+        9.8 - 6. * 2_f64.log10(),
+        abs <= 1e-12
+    );
+}
+
+#[test]
+fn ephemeris() {
+    let encke: SmallBody = ENCKE.parse().unwrap();
+    let from = Utc.ymd(1990, 10, 1).and_hms(0, 0, 0);
+    let to = Utc.ymd(1990, 10, 11).and_hms(0, 0, 0);
+
+    let rows = encke.ephemeris(from, to, Duration::days(5)).unwrap();
+    let times: Vec<_> = rows.iter().map(|(dt, _)| dt.day()).collect();
+    assert_eq!(times, [1, 6, 11]);
+    // Positions are for TT, 57.184 s ahead of UTC in 1990
+    let tt = JulianDay::from(2_448_170.5) + 57.184 / 86_400.;
+    let expected = encke.geocentric(tt);
+    assert_float_eq!(
+        rows[1].1.equatorial.ra_hours(),
+        expected.equatorial.ra_hours(),
+        abs <= 1e-9
+    );
+    assert_float_eq!(rows[1].1.distance, expected.distance, abs <= 1e-12);
+    assert!(matches!(
+        encke.ephemeris(from, to, Duration::zero()),
+        Err(Error::InvalidDate(_))
+    ));
+
+    let mut table = Vec::new();
+    encke
+        .write_ephemeris(&mut table, from, to, Duration::days(5))
+        .unwrap();
+    let table = String::from_utf8(table).unwrap();
+    let lines: Vec<_> = table.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], "2P/Encke");
+    assert!(lines[1].starts_with("Date (UTC)"));
+    assert!(
+        lines[3].starts_with("1990-10-06 00:00  10h 33m"),
+        "{}",
+        table
+    );
+    assert!(lines[3].contains("0.82429"), "{}", table);
+}
+
+#[test]
+fn impossible_orbits() {
+    // An MPCORB line with e >= 1 has no mean anomaly, and a comet needs a
+    // perihelion outside the Sun
+    let open = ENCKE_MPCORB.replace("0.8502196", "1.0000000");
+    let inside = ENCKE.replace("0.330886", "-0.33088");
+    for (bad, expected) in &[
+        (open, "no ellipse with a = 2.2091404 and e = 1 on line 2"),
+        (
+            inside,
+            "no orbit with q = -0.33088 and e = 0.85022 on line 2",
+        ),
+    ] {
+        let error = parse_elements(&format!("{}\n{}", ENCKE, bad)).unwrap_err();
+        assert!(
+            matches!(&error, Error::Parse(message) if message == expected),
+            "{}",
+            error
+        );
+    }
+    assert!(Orbit::from_mean_anomaly(-2.2, 0.8, 12., 335., 186., 354., J2000).is_err());
+}
@@ -0,0 +1,81 @@
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use float_eq::assert_float_eq;
+
+use celestial::{
+    sun::{equation_of_time, seasons, Season, SolarPosition},
+    JulianDay,
+};
+
+fn assert_near(actual: DateTime<Utc>, expected: DateTime<Utc>, tolerance: Duration) {
+    let error = (actual - expected).num_seconds().abs();
+    assert!(
+        error <= tolerance.num_seconds(),
+        "{} is {}s from {}",
+        actual,
+        error,
+        expected
+    );
+}
+
+#[test]
+fn solar_position() {
+    // Meeus, Astronomical Algorithms, example 25.a: 1992 October 13.0 TD
+    let sun = SolarPosition::from_julian_day(JulianDay::from(2_448_908.5));
+    assert_float_eq!(sun.true_longitude, 199.909_88, abs <= 1e-4);
+    assert_float_eq!(sun.apparent_longitude, 199.908_95, abs <= 1e-4);
+    assert_float_eq!(sun.distance, 0.997_66, abs <= 1e-5);
+    assert_float_eq!(
+        sun.equatorial.ra.to_degrees().value,
+        198.380_83,
+        abs <= 1e-4
+    );
+    assert_float_eq!(sun.equatorial.dec_degrees(), -7.785_07, abs <= 1e-4);
+}
+
+#[test]
+fn equation_of_time_1992() {
+    // Meeus example 28.b: 13m42.6s on 1992 October 13.0 TD
+    let e = equation_of_time(Utc.ymd(1992, 10, 13).and_hms(0, 0, 0));
+    assert_float_eq!(e.num_milliseconds() as f64 / 1000., 822.6, abs <= 1.);
+}
+
+#[test]
+fn equation_of_time_extremes() {
+    // The sundial is about 14 minutes slow in February and 16 fast in November
+    let february = equation_of_time(Utc.ymd(2021, 2, 11).and_hms(12, 0, 0));
+    let november = equation_of_time(Utc.ymd(2021, 11, 3).and_hms(12, 0, 0));
+    assert_float_eq!(february.num_seconds() as f64 / 60., -14.2, abs <= 0.1);
+    assert_float_eq!(november.num_seconds() as f64 / 60., 16.4, abs <= 0.1);
+}
+
+#[test]
+fn seasons_2021() {
+    // US Naval Observatory, Earth's Seasons 2021 (UT)
+    let expected = [
+        Utc.ymd(2021, 3, 20).and_hms(9, 37, 0),
+        Utc.ymd(2021, 6, 21).and_hms(3, 32, 0),
+        Utc.ymd(2021, 9, 22).and_hms(19, 21, 0),
+        Utc.ymd(2021, 12, 21).and_hms(15, 59, 0),
+    ];
+    for ((_, start), expected) in seasons(2021).unwrap().iter().zip(expected.iter()) {
+        assert_near(*start, *expected, Duration::minutes(10));
+    }
+}
+
+#[test]
+fn season_longitudes() {
+    let june = Season::JuneSolstice.start(1962).unwrap();
+    let sun = SolarPosition::from(june);
+    assert_float_eq!(sun.apparent_longitude, 90., abs <= 1e-4);
+    assert_float_eq!(sun.equatorial.dec_degrees(), 23.44, abs <= 0.01);
+
+    // Meeus example 27.a: 1962 June 21, 21h25m08s TD
+    assert_near(
+        june,
+        Utc.ymd(1962, 6, 21).and_hms(21, 25, 8),
+        Duration::minutes(5),
+    );
+
+    // Beyond chrono's range
+    assert!(Season::MarchEquinox.start(i32::MAX).is_none());
+}
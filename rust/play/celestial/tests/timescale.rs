@@ -0,0 +1,141 @@
+use std::{convert::TryFrom, env, fs};
+
+use chrono::{DateTime, TimeZone};
+use float_eq::assert_float_eq;
+
+use celestial::{
+    timescale::{delta_t, JulianDate, LeapSeconds, Tai, Tdb, Tt, Ut1, Utc},
+    JulianDay,
+};
+
+/// The end of an IERS `leap-seconds.list`, with the same last few entries
+/// as the built-in table.
+const LEAP_SECONDS_LIST: &str = "\
+#	Updated through IERS Bulletin C62
+#	File expires on:  28 June 2022
+#
+#$	 3835722800
+#@	 3865363200
+#
+3550089600	35	# 1 Jul 2012
+3644697600	36	# 1 Jul 2015
+3692217600	37	# 1 Jan 2017
+#h	16edd0f0 3f9fe8c0 b5e30af2 3f7e7d3a 8e5d5a2b
+";
+
+fn seconds(a: JulianDay, b: JulianDay) -> f64 {
+    (a - b) * 86_400.
+}
+
+#[test]
+fn leap_seconds() {
+    let table = LeapSeconds::builtin();
+    assert_eq!(table.tai_minus_utc(41_316.5), None);
+    assert_eq!(table.tai_minus_utc(41_317.), Some(10.));
+    assert_eq!(table.tai_minus_utc(51_544.5), Some(32.));
+    assert_eq!(table.tai_minus_utc(57_753.999), Some(36.));
+    assert_eq!(table.tai_minus_utc(57_754.), Some(37.));
+
+    // TT runs 69.184 s ahead of UTC today
+    let utc = JulianDate::<Utc>::from(chrono::Utc.ymd(2021, 6, 1).and_hms(0, 0, 0));
+    let tt = utc.to::<Tt>();
+    assert_float_eq!(seconds(tt.jd, utc.jd), 69.184, abs <= 1e-4);
+    assert_float_eq!(seconds(utc.to::<Tai>().jd, utc.jd), 37., abs <= 1e-4);
+}
+
+#[test]
+fn across_a_leap_second() {
+    // A minute either side of the leap second at the end of 2016 is two
+    // minutes and a second apart on TAI
+    let before = JulianDate::<Utc>::from(chrono::Utc.ymd(2016, 12, 31).and_hms(23, 59, 0));
+    let after = JulianDate::<Utc>::from(chrono::Utc.ymd(2017, 1, 1).and_hms(0, 1, 0));
+    let tai = (before.to::<Tai>(), after.to::<Tai>());
+    assert_float_eq!(seconds(tai.1.jd, tai.0.jd), 121., abs <= 1e-4);
+
+    // And both come back to the same UTC
+    assert_float_eq!(seconds(tai.0.to::<Utc>().jd, before.jd), 0., abs <= 1e-4);
+    assert_float_eq!(seconds(tai.1.to::<Utc>().jd, after.jd), 0., abs <= 1e-4);
+
+    let back = DateTime::<chrono::Utc>::try_from(tai.1.to::<Utc>()).unwrap();
+    assert_eq!(back, chrono::Utc.ymd(2017, 1, 1).and_hms(0, 1, 0));
+}
+
+#[test]
+fn round_trips() {
+    for year in [1800, 1950, 1972, 2000, 2021, 2100] {
+        let utc = JulianDate::<Utc>::from(chrono::Utc.ymd(year, 3, 1).and_hms(12, 0, 0));
+        let tt = utc.to::<Tt>();
+        assert_float_eq!(seconds(tt.to::<Utc>().jd, utc.jd), 0., abs <= 1e-4);
+        assert_float_eq!(
+            seconds(tt.to::<Ut1>().to::<Tt>().jd, tt.jd),
+            0.,
+            abs <= 1e-3
+        );
+        assert_float_eq!(
+            seconds(tt.to::<Tdb>().to::<Tt>().jd, tt.jd),
+            0.,
+            abs <= 1e-4
+        );
+
+        // TDB stays within two milliseconds of TT
+        assert!(seconds(tt.to::<Tdb>().jd, tt.jd).abs() < 0.002);
+    }
+}
+
+#[test]
+fn universal_time() {
+    // ΔT from the Espenak and Meeus polynomials
+    assert_float_eq!(delta_t(JulianDay::from(2_451_545.)), 63.86, abs <= 0.01);
+    assert_float_eq!(
+        delta_t(JulianDay::from(chrono::Utc.ymd(1900, 1, 1))),
+        -2.79,
+        abs <= 0.01
+    );
+
+    // Before 1972 UTC is UT1
+    let utc = JulianDate::<Utc>::from(chrono::Utc.ymd(1950, 1, 1).and_hms(0, 0, 0));
+    assert_float_eq!(seconds(utc.to::<Ut1>().jd, utc.jd), 0., abs <= 1e-3);
+
+    // Since then they have stayed within a couple of seconds, given ΔT is a
+    // fit
+    let utc = JulianDate::<Utc>::from(chrono::Utc.ymd(1990, 1, 1).and_hms(0, 0, 0));
+    assert!(seconds(utc.to::<Ut1>().jd, utc.jd).abs() < 2.);
+}
+
+#[test]
+fn modified_julian_day() {
+    let tt = JulianDate::<Tt>::new(JulianDay::from(2_451_545.));
+    assert_float_eq!(tt.mjd(), 51_544.5, abs <= 1e-9);
+    assert_eq!(JulianDate::<Tt>::from_mjd(51_544.5), tt);
+    assert_eq!(tt.to_string(), "2451545 TT");
+
+    let dt = chrono::Utc.ymd(2021, 6, 1).and_hms(0, 0, 0);
+    assert_float_eq!(JulianDate::<Utc>::from(dt).mjd(), 59_366., abs <= 1e-9);
+    assert_eq!(
+        JulianDate::<Tt>::from_datetime(dt),
+        JulianDate::from(dt).to()
+    );
+}
+
+#[test]
+fn leap_seconds_file() {
+    let table: LeapSeconds = LEAP_SECONDS_LIST.parse().unwrap();
+    assert_float_eq!(table.expires().unwrap().mjd(), 59_758., abs <= 1e-9);
+    assert_eq!(table.tai_minus_utc(57_203.), Some(35.));
+    assert_eq!(table.tai_minus_utc(59_000.), Some(37.));
+
+    let utc = JulianDate::<Utc>::from(chrono::Utc.ymd(2016, 6, 1).and_hms(0, 0, 0));
+    assert_eq!(
+        utc.to_with::<Tt>(&table),
+        utc.to_with::<Tt>(LeapSeconds::builtin())
+    );
+
+    let path = env::temp_dir().join("celestial-leap-seconds.list");
+    fs::write(&path, LEAP_SECONDS_LIST).unwrap();
+    assert_eq!(LeapSeconds::from_file(&path).unwrap(), table);
+    fs::remove_file(&path).unwrap();
+
+    assert!("3692217600 thirty-seven".parse::<LeapSeconds>().is_err());
+    assert!("# nothing here".parse::<LeapSeconds>().is_err());
+    assert!(LeapSeconds::from_file("/nonexistent/leap-seconds.list").is_err());
+}
@@ -1,8 +1,9 @@
+use std::{error::Error, fmt, str::FromStr};
+
 use crate::prelude::*;
 
 unit! {
-    Angle {
-        // TODO: need to be dimensionless
+    Angle: ANGLE {
         Degree => (degrees, "°", 1_f64.to_radians()),
         Hour => (hours, "h", 15_f64.to_radians()),
         Minute => (minutes, "'", (1_f64 / 60_f64).to_radians()),
@@ -21,6 +22,11 @@ impl Value<Angle> {
             unit: Angle::Dms(1.),
         }
     }
+
+    /// Hours, minutes and seconds of right ascension or hour angle.
+    pub fn from_hms_tuple(hms: (f64, f64, f64)) -> Self {
+        hours(hms.0 + hms.1 / 60. + hms.2 / 3600.)
+    }
 }
 
 impl Value<Angle> {
@@ -34,4 +40,175 @@ impl Value<Angle> {
         let s = minutes(m_rem).to_seconds().value;
         (d_trunc, minutes(m_trunc).value, seconds(s).value)
     }
+
+    pub fn to_hms_tuple(self) -> (f64, f64, f64) {
+        let h = self.to_hours().value;
+        let m = h.fract() * 60.;
+        (h.trunc(), m.trunc(), m.fract() * 60.)
+    }
+
+    /// Formats as hours, minutes and seconds, `12h 34m 56.7s`, with as many
+    /// decimal places of seconds as the precision asks for.
+    pub fn display_hms(self) -> Sexagesimal {
+        Sexagesimal {
+            value: self.to_hours().value,
+            units: ["h", "m", "s"],
+            clock: false,
+        }
+    }
+
+    /// Formats as a time of day, `13:10:46`, for sidereal time or an hour
+    /// angle, wrapped into 24 hours after rounding so that `23:59:59.9` shows
+    /// as `00:00:00`.
+    pub fn display_clock(self) -> Sexagesimal {
+        Sexagesimal {
+            value: self.to_hours().value.rem_euclid(24.),
+            units: [":", ":", ""],
+            clock: true,
+        }
+    }
+
+    /// Formats as degrees, minutes and seconds of arc, `-23° 26' 21"`, with
+    /// as many decimal places of seconds as the precision asks for.
+    pub fn display_dms(self) -> Sexagesimal {
+        Sexagesimal {
+            value: self.to_degrees().value,
+            units: ["°", "'", "\""],
+            clock: false,
+        }
+    }
+}
+
+/// An angle written in sixtieths.
+#[derive(Clone, Copy, Debug)]
+pub struct Sexagesimal {
+    value: f64,
+    units: [&'static str; 3],
+    /// Two-digit hours with colons, wrapped into a day.
+    clock: bool,
+}
+
+impl fmt::Display for Sexagesimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Round once, in the last place shown, so 59.99s carries
+        let places = f.precision().unwrap_or(0);
+        let scale = 10_u64.pow(places as u32);
+        let mut units = (self.value.abs() * 3600. * scale as f64).round() as u64;
+        if self.clock {
+            units %= 86_400 * scale;
+        }
+        let seconds = units / scale;
+        let [big, minute, second] = self.units;
+
+        if self.clock {
+            write!(
+                f,
+                "{:02}:{:02}:{:02}",
+                seconds / 3600,
+                seconds % 3600 / 60,
+                seconds % 60
+            )?;
+        } else {
+            write!(
+                f,
+                "{}{}{} {:02}{} {:02}",
+                if self.value < 0. && units > 0 {
+                    "-"
+                } else {
+                    ""
+                },
+                seconds / 3600,
+                big,
+                seconds % 3600 / 60,
+                minute,
+                seconds % 60
+            )?;
+        }
+        if places > 0 {
+            write!(f, ".{:0places$}", units % scale, places = places)?;
+        }
+        write!(f, "{}", second)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseAngleError(String);
+
+impl fmt::Display for ParseAngleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid angle {:?}", self.0)
+    }
+}
+
+impl Error for ParseAngleError {}
+
+impl FromStr for Value<Angle> {
+    type Err = ParseAngleError;
+
+    /// Hours, minutes and seconds as `12h 34m 56.7s`, degrees, minutes and
+    /// seconds of arc as `-23° 26' 21"` (or `-23d 26m 21s`, or with primes),
+    /// radians as `0.41rad`, or bare degrees.  Trailing parts may be left
+    /// out, but not one between two others, and only the last may have a
+    /// fraction.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseAngleError(s.to_string());
+        let trimmed = s.trim();
+        let (sign, rest) = match trimmed.strip_prefix('-') {
+            Some(rest) => (-1., rest),
+            None => (1., trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        if let Ok(value) = rest.parse::<f64>() {
+            return match value.is_finite() {
+                true => Ok(degrees(sign * value)),
+                false => Err(error()),
+            };
+        }
+        if let Some(value) = rest.strip_suffix("rad") {
+            let value: f64 = value.trim().parse().map_err(|_| error())?;
+            return match value.is_finite() {
+                true => Ok(radians(sign * value)),
+                false => Err(error()),
+            };
+        }
+
+        // Numbers each followed by a unit
+        let mut parts = Vec::new();
+        let mut rest = rest.trim_start();
+        while !rest.is_empty() {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .ok_or_else(error)?;
+            let number: f64 = rest[..end].parse().map_err(|_| error())?;
+            let unit = rest[end..].chars().next().ok_or_else(error)?;
+            parts.push((number, unit));
+            rest = rest[end + unit.len_utf8()..].trim_start();
+        }
+
+        let (in_hours, minute_units, second_units): (_, &[char], &[char]) = match parts.first() {
+            Some((_, 'h')) => (true, &['m'], &['s']),
+            Some((_, '°')) | Some((_, 'd')) => (false, &['\'', '′', 'm'], &['"', '″', 's']),
+            _ => return Err(error()),
+        };
+
+        let mut value = 0.;
+        for (i, (number, unit)) in parts.iter().enumerate() {
+            let (units, scale): (&[char], f64) = match i {
+                0 => (&[parts[0].1], 1.),
+                1 => (minute_units, 60.),
+                2 => (second_units, 3600.),
+                _ => return Err(error()),
+            };
+            let last = i + 1 == parts.len();
+            if !units.contains(unit) || (i > 0 && *number >= 60.) || (!last && number.fract() != 0.)
+            {
+                return Err(error());
+            }
+            value += number / scale;
+        }
+
+        Ok(match in_hours {
+            true => hours(sign * value),
+            false => degrees(sign * value),
+        })
+    }
 }
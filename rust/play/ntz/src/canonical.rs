@@ -0,0 +1,226 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Mul},
+};
+
+use crate::Dimension;
+
+/// SI units with names of their own, recognised by dimension when a
+/// compound unit is coherent with them.
+const NAMED: [(&str, Dimension); 5] = [
+    ("N", Dimension::FORCE),
+    ("J", Dimension::ENERGY),
+    ("W", Dimension::POWER),
+    ("Pa", Dimension::PRESSURE),
+    ("Hz", Dimension::FREQUENCY),
+];
+
+/// A rational power, kept in lowest terms with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Exponent {
+    pub numerator: i32,
+    pub denominator: i32,
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    match b {
+        0 => a.abs(),
+        _ => gcd(b, a % b),
+    }
+}
+
+impl Exponent {
+    pub const ZERO: Exponent = Exponent::whole(0);
+    pub const ONE: Exponent = Exponent::whole(1);
+
+    /// A whole power, which is already in lowest terms.
+    pub const fn whole(power: i32) -> Self {
+        Exponent {
+            numerator: power,
+            denominator: 1,
+        }
+    }
+
+    /// Panics if `denominator` is zero.
+    pub fn new(numerator: i32, denominator: i32) -> Self {
+        assert!(
+            denominator != 0,
+            "exponent {}/{} has a zero denominator",
+            numerator,
+            denominator
+        );
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Exponent {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    pub fn to_f64(self) -> f64 {
+        f64::from(self.numerator) / f64::from(self.denominator)
+    }
+}
+
+impl From<f64> for Exponent {
+    /// The simplest fraction within a rounding error of a power, as the
+    /// halves `sqrt()` makes.
+    fn from(power: f64) -> Self {
+        let denominator = (1..1000)
+            .find(|d| {
+                let numerator = power * f64::from(*d);
+                (numerator - numerator.round()).abs() < 1e-9
+            })
+            .unwrap_or(1000);
+        Exponent::new((power * f64::from(denominator)).round() as i32, denominator)
+    }
+}
+
+impl Add for Exponent {
+    type Output = Exponent;
+
+    fn add(self, rhs: Exponent) -> Exponent {
+        Exponent::new(
+            self.numerator * rhs.denominator + rhs.numerator * self.denominator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl Mul for Exponent {
+    type Output = Exponent;
+
+    fn mul(self, rhs: Exponent) -> Exponent {
+        Exponent::new(
+            self.numerator * rhs.numerator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl Default for Exponent {
+    fn default() -> Self {
+        Exponent::ZERO
+    }
+}
+
+impl fmt::Display for Exponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            _ => write!(f, "({}/{})", self.numerator, self.denominator),
+        }
+    }
+}
+
+/// One unit of a compound unit, such as the `s^-2` of `m·s^-2`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Factor {
+    pub symbol: &'static str,
+    /// The size of the unit in SI base units, to the first power.
+    pub scale: f64,
+    /// The dimension of the unit, to the first power.
+    pub dimension: Dimension,
+    pub exponent: Exponent,
+}
+
+impl Factor {
+    fn same_unit(&self, other: &Factor) -> bool {
+        self.symbol == other.symbol
+            && self.dimension == other.dimension
+            && self.scale == other.scale
+    }
+
+    fn order(&self, other: &Factor) -> Ordering {
+        self.dimension
+            .rank()
+            .cmp(&other.dimension.rank())
+            .then(self.symbol.cmp(other.symbol))
+    }
+}
+
+/// The units of a product or quotient, however deeply nested, as a list of
+/// distinct units in order of dimension (length, mass, time and so on) with
+/// their powers.  Units that cancel out are left out, so `m·s/s` is `m`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Canonical(Vec<Factor>);
+
+impl Canonical {
+    pub fn new(mut factors: Vec<Factor>) -> Self {
+        factors.sort_by(Factor::order);
+        let mut merged: Vec<Factor> = Vec::with_capacity(factors.len());
+        for factor in factors {
+            match merged.last_mut() {
+                Some(last) if last.same_unit(&factor) => {
+                    last.exponent = last.exponent + factor.exponent
+                }
+                _ => merged.push(factor),
+            }
+        }
+        merged.retain(|factor| factor.exponent.numerator != 0 && !factor.symbol.is_empty());
+        Canonical(merged)
+    }
+
+    pub fn factors(&self) -> &[Factor] {
+        &self.0
+    }
+
+    /// The size of the whole unit in SI base units.
+    pub fn scale(&self) -> f64 {
+        self.0
+            .iter()
+            .map(|factor| factor.scale.powf(factor.exponent.to_f64()))
+            .product()
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        self.0.iter().fold(Dimension::NONE, |dimension, factor| {
+            dimension * factor.dimension.pow(factor.exponent)
+        })
+    }
+
+    /// The name of the SI unit this is the same as, `N` for `kg·m·s^-2` or
+    /// for `g·km·s^-2`, but not for `g·cm·s^-2`.
+    pub fn named(&self) -> Option<&'static str> {
+        if (self.scale() - 1.).abs() > 1e-12 {
+            return None;
+        }
+        let dimension = self.dimension();
+        NAMED
+            .iter()
+            .find(|(_, named)| *named == dimension)
+            .map(|(symbol, _)| *symbol)
+    }
+}
+
+impl Mul for Canonical {
+    type Output = Canonical;
+
+    fn mul(mut self, rhs: Canonical) -> Canonical {
+        self.0.extend(rhs.0);
+        Canonical::new(self.0)
+    }
+}
+
+impl fmt::Display for Canonical {
+    /// As a named unit if there is one, or the units joined by `·` with any
+    /// powers as `^-2` or `^(1/2)`.  A pure number is `1`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(symbol) = self.named() {
+            return write!(f, "{}", symbol);
+        }
+        if self.0.is_empty() {
+            return write!(f, "1");
+        }
+        for (i, factor) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, "·")?;
+            }
+            write!(f, "{}", factor.symbol)?;
+            if factor.exponent != Exponent::ONE {
+                write!(f, "^{}", factor.exponent)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+use std::{error::Error, fmt, ops::Mul};
+
+use crate::Exponent;
+
+/// Exponents, as exact fractions, of the base quantities a unit measures:
+/// length, mass, time, electric current, temperature, amount of substance
+/// and luminous intensity, plus plane angle so that degrees never pass for
+/// plain numbers.
+///
+/// ```compile_fail
+/// use ntz::{angle::degrees, si::meters};
+///
+/// // Different unit types never add
+/// let _ = meters(1.) + degrees(1.);
+/// ```
+///
+/// ```compile_fail
+/// use ntz::si::meters;
+///
+/// // Nor do products and quotients, whose dimensions are only known at
+/// // runtime; `try_add` is the way to add them
+/// let _ = meters(2.) * meters(3.) + meters(2.) / meters(3.);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Dimension {
+    pub length: Exponent,
+    pub mass: Exponent,
+    pub time: Exponent,
+    pub current: Exponent,
+    pub temperature: Exponent,
+    pub amount: Exponent,
+    pub luminosity: Exponent,
+    pub angle: Exponent,
+}
+
+impl Dimension {
+    pub const NONE: Dimension = Dimension {
+        length: Exponent::whole(0),
+        mass: Exponent::whole(0),
+        time: Exponent::whole(0),
+        current: Exponent::whole(0),
+        temperature: Exponent::whole(0),
+        amount: Exponent::whole(0),
+        luminosity: Exponent::whole(0),
+        angle: Exponent::whole(0),
+    };
+    pub const LENGTH: Dimension = Dimension {
+        length: Exponent::whole(1),
+        ..Dimension::NONE
+    };
+    pub const MASS: Dimension = Dimension {
+        mass: Exponent::whole(1),
+        ..Dimension::NONE
+    };
+    pub const TIME: Dimension = Dimension {
+        time: Exponent::whole(1),
+        ..Dimension::NONE
+    };
+    pub const TEMPERATURE: Dimension = Dimension {
+        temperature: Exponent::whole(1),
+        ..Dimension::NONE
+    };
+    pub const ANGLE: Dimension = Dimension {
+        angle: Exponent::whole(1),
+        ..Dimension::NONE
+    };
+    pub const VOLUME: Dimension = Dimension {
+        length: Exponent::whole(3),
+        ..Dimension::NONE
+    };
+    pub const FREQUENCY: Dimension = Dimension {
+        time: Exponent::whole(-1),
+        ..Dimension::NONE
+    };
+    pub const FORCE: Dimension = Dimension {
+        length: Exponent::whole(1),
+        mass: Exponent::whole(1),
+        time: Exponent::whole(-2),
+        ..Dimension::NONE
+    };
+    pub const ENERGY: Dimension = Dimension {
+        length: Exponent::whole(2),
+        mass: Exponent::whole(1),
+        time: Exponent::whole(-2),
+        ..Dimension::NONE
+    };
+    pub const POWER: Dimension = Dimension {
+        length: Exponent::whole(2),
+        mass: Exponent::whole(1),
+        time: Exponent::whole(-3),
+        ..Dimension::NONE
+    };
+    pub const PRESSURE: Dimension = Dimension {
+        length: Exponent::whole(-1),
+        mass: Exponent::whole(1),
+        time: Exponent::whole(-2),
+        ..Dimension::NONE
+    };
+
+    fn exponents(&self) -> [(&'static str, Exponent); 8] {
+        [
+            ("L", self.length),
+            ("M", self.mass),
+            ("T", self.time),
+            ("I", self.current),
+            ("Θ", self.temperature),
+            ("N", self.amount),
+            ("J", self.luminosity),
+            ("rad", self.angle),
+        ]
+    }
+
+    /// Where the first base quantity comes in the order above, for sorting
+    /// the units of a compound unit.
+    pub(crate) fn rank(&self) -> usize {
+        self.exponents()
+            .iter()
+            .position(|(_, exponent)| *exponent != Exponent::ZERO)
+            .unwrap_or(8)
+    }
+
+    fn map<F: Fn(Exponent) -> Exponent>(self, f: F) -> Dimension {
+        Dimension {
+            length: f(self.length),
+            mass: f(self.mass),
+            time: f(self.time),
+            current: f(self.current),
+            temperature: f(self.temperature),
+            amount: f(self.amount),
+            luminosity: f(self.luminosity),
+            angle: f(self.angle),
+        }
+    }
+
+    /// Raise to a power, as squaring a length makes an area.
+    pub fn pow(self, power: Exponent) -> Dimension {
+        self.map(|exponent| exponent * power)
+    }
+}
+
+impl Mul for Dimension {
+    type Output = Dimension;
+
+    fn mul(self, rhs: Dimension) -> Dimension {
+        Dimension {
+            length: self.length + rhs.length,
+            mass: self.mass + rhs.mass,
+            time: self.time + rhs.time,
+            current: self.current + rhs.current,
+            temperature: self.temperature + rhs.temperature,
+            amount: self.amount + rhs.amount,
+            luminosity: self.luminosity + rhs.luminosity,
+            angle: self.angle + rhs.angle,
+        }
+    }
+}
+
+impl fmt::Display for Dimension {
+    /// As `L M T^-2` or `L^(1/2)`, or `1` for a pure number.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut terms = self
+            .exponents()
+            .iter()
+            .filter(|(_, exponent)| *exponent != Exponent::ZERO)
+            .map(|(symbol, exponent)| match *exponent {
+                Exponent::ONE => symbol.to_string(),
+                e => format!("{}^{}", symbol, e),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        if terms.is_empty() {
+            terms.push('1');
+        }
+        write!(f, "{}", terms)
+    }
+}
+
+/// Two quantities that cannot be added, subtracted or compared.  The
+/// dimensions are boxed to keep the `Result`s that carry this small.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DimensionError {
+    pub left: Box<Dimension>,
+    pub right: Box<Dimension>,
+}
+
+impl fmt::Display for DimensionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "incompatible dimensions {} and {}",
+            self.left, self.right
+        )
+    }
+}
+
+impl Error for DimensionError {}
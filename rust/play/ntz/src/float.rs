@@ -203,7 +203,7 @@ mod tests {
         use crate::prelude::*;
 
         unit! {
-            Qaulity {
+            Qaulity: NONE {
                 Good => (goods, "gds", 1.),
                 Better => (betters, "bts", 2.),
                 Best => (bests, "bsts", 4.)
@@ -5,12 +5,17 @@ use std::{
 };
 
 pub mod angle;
+pub mod canonical;
+pub mod dimension;
 mod float;
 pub mod si;
 pub mod temperature;
 pub mod time;
 pub mod scalar;
 
+pub use canonical::{Canonical, Exponent, Factor};
+pub use dimension::{Dimension, DimensionError};
+
 pub mod prelude {
     pub use super::{unit, Unit, Value};
 }
@@ -32,10 +37,10 @@ impl<U: Unit + std::fmt::Debug> std::fmt::Debug for Value<U> {
 }
 
 impl<U: Unit> Value<U> {
-    pub fn sqrt(mut self) -> Self {
+    pub fn sqrt(mut self) -> Value<Root<U>> {
         Value {
             value: self.value.sqrt(),
-            unit: self.unit.map_power(&|p| p / 2.),
+            unit: Root(self.unit.map_power(&|p| p / 2.)),
         }
     }
 }
@@ -44,6 +49,56 @@ impl<U: Unit> Value<U> {
     pub fn norm(&self) -> f64 {
         self.value * self.unit.factor() + self.unit.offset()
     }
+
+    pub fn dimension(&self) -> Dimension {
+        self.unit.dimension()
+    }
+
+    fn check<R: Unit>(&self, unit: &R) -> std::result::Result<(), DimensionError> {
+        let (left, right) = (self.dimension(), unit.dimension());
+        match left == right {
+            true => Ok(()),
+            false => Err(DimensionError {
+                left: Box::new(left),
+                right: Box::new(right),
+            }),
+        }
+    }
+
+    /// `rhs` in this one's unit, unchecked.
+    fn in_unit<R: Unit>(&self, rhs: Value<R>) -> f64 {
+        rhs.norm() / self.unit.factor()
+    }
+
+    /// Add a quantity in any unit of the same dimension, giving the result
+    /// in this one's unit.  Where the types can't tell, as with `m * m` and
+    /// `m / m`, this is the only way to add.
+    pub fn try_add<R: Unit>(self, rhs: Value<R>) -> std::result::Result<Self, DimensionError> {
+        self.check(&rhs.unit)?;
+        Ok(Value {
+            value: self.value + self.in_unit(rhs),
+            unit: self.unit,
+        })
+    }
+
+    /// Subtract a quantity in any unit of the same dimension.
+    pub fn try_sub<R: Unit>(self, rhs: Value<R>) -> std::result::Result<Self, DimensionError> {
+        self.check(&rhs.unit)?;
+        Ok(Value {
+            value: self.value - self.in_unit(rhs),
+            unit: self.unit,
+        })
+    }
+
+    /// The same quantity in another unit of the same dimension, simple or
+    /// compound, as `inches * grams` in `MulUnit(Meter(1.), Kilogram(1.))`.
+    pub fn convert<R: Unit>(self, unit: R) -> std::result::Result<Value<R>, DimensionError> {
+        self.check(&unit)?;
+        Ok(Value {
+            value: (self.norm() - unit.offset()) / unit.factor(),
+            unit,
+        })
+    }
 }
 
 impl<U: Unit> Display for Value<U> {
@@ -64,37 +119,35 @@ impl<U: Unit> Deref for Value<U> {
 
 impl<U: Unit> PartialEq for Value<U> {
     fn eq(&self, other: &Self) -> bool {
-        self.norm() == other.norm()
+        self.check(&other.unit).is_ok() && self.norm() == other.norm()
     }
 }
 
+/// Quantities of different dimensions are unordered.
 impl<U: Unit> PartialOrd for Value<U> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.check(&other.unit).ok()?;
         self.norm().partial_cmp(&other.norm())
     }
 }
 
-impl<U: Unit> Add<Value<U>> for Value<U> {
+/// Only for units whose type fixes their dimension; see `try_add`.  Panics
+/// should the powers still differ, as with `Meter(2.)` and `Meter(1.)`.
+impl<U: Homogeneous> Add<Value<U>> for Value<U> {
     type Output = Value<U>;
 
     fn add(self, rhs: Value<U>) -> Value<U> {
-        let rval = rhs.norm() / self.unit.factor();
-        Value {
-            value: self.value + rval,
-            unit: self.unit,
-        }
+        self.try_add(rhs).unwrap_or_else(|error| panic!("{}", error))
     }
 }
 
-impl<U: Unit> Sub<Value<U>> for Value<U> {
+/// Only for units whose type fixes their dimension; see `try_sub`.  Panics
+/// should the powers still differ.
+impl<U: Homogeneous> Sub<Value<U>> for Value<U> {
     type Output = Value<U>;
 
     fn sub(self, rhs: Value<U>) -> Value<U> {
-        let rval = rhs.norm() / self.unit.factor();
-        Value {
-            value: self.value - rval,
-            unit: self.unit,
-        }
+        self.try_sub(rhs).unwrap_or_else(|error| panic!("{}", error))
     }
 }
 
@@ -143,6 +196,18 @@ impl<U: Unit> Neg for Value<U> {
 }
 
 pub trait Unit {
+    /// The unit as a list of distinct units with their powers.  Units from
+    /// elsewhere than `unit!` are a single unit named after their type,
+    /// unless they say otherwise.
+    fn canonical(&self) -> Canonical {
+        Canonical::new(vec![Factor {
+            symbol: std::any::type_name::<Self>(),
+            scale: self.factor(),
+            dimension: self.dimension(),
+            exponent: Exponent::ONE,
+        }])
+    }
+    fn dimension(&self) -> Dimension;
     fn factor(&self) -> f64;
     fn map_power<F>(&mut self, f: &F) -> Self
     where
@@ -151,10 +216,23 @@ pub trait Unit {
     fn power(&self) -> Option<f64>;
 }
 
+/// A unit all of whose values have the same dimension, so that the type
+/// alone shows two quantities can be added.  Every `unit!` enum is one, as
+/// long as its powers are left at 1; products, quotients and roots aren't.
+pub trait Homogeneous: Unit {}
+
 #[derive(Clone, Copy, Debug)]
-pub struct MulUnit<L: Unit, R: Unit>(L, R);
+pub struct MulUnit<L: Unit, R: Unit>(pub L, pub R);
 
 impl<L: Unit, R: Unit> Unit for MulUnit<L, R> {
+    fn canonical(&self) -> Canonical {
+        self.0.canonical() * self.1.canonical()
+    }
+
+    fn dimension(&self) -> Dimension {
+        self.0.dimension() * self.1.dimension()
+    }
+
     fn factor(&self) -> f64 {
         self.0.factor() * self.1.factor()
     }
@@ -165,8 +243,13 @@ impl<L: Unit, R: Unit> Unit for MulUnit<L, R> {
         self.0.offset() + self.1.offset()
     }
 
+    /// The power of the one unit left once the product is simplified, as
+    /// `m * m` is `m^2`, or `None` for a mixture of units.
     fn power(&self) -> Option<f64> {
-        None //self.0.power() + self.1.power()
+        match self.canonical().factors() {
+            [factor] => Some(factor.exponent.to_f64()),
+            _ => None,
+        }
     }
 
     fn map_power<F>(&mut self, f: &F) -> Self
@@ -177,59 +260,135 @@ impl<L: Unit, R: Unit> Unit for MulUnit<L, R> {
     }
 }
 
+/// A unit with its power changed, as `sqrt()` leaves it, so that `m^(1/2)`
+/// is never added to `m` as though it were the same.
+#[derive(Clone, Copy, Debug)]
+pub struct Root<U: Unit>(pub U);
+
+impl<U: Unit> Unit for Root<U> {
+    fn canonical(&self) -> Canonical {
+        self.0.canonical()
+    }
+
+    fn dimension(&self) -> Dimension {
+        self.0.dimension()
+    }
+
+    fn factor(&self) -> f64 {
+        self.0.factor()
+    }
+
+    fn offset(&self) -> f64 {
+        self.0.offset()
+    }
+
+    fn power(&self) -> Option<f64> {
+        self.0.power()
+    }
+
+    fn map_power<F>(&mut self, f: &F) -> Self
+    where
+        F: Fn(f64) -> f64,
+    {
+        Root(self.0.map_power(f))
+    }
+}
+
 #[macro_export]
 macro_rules! unit {
-    ( $unit:ty { $( $var:ident => ($new:ident, $abbr:literal, $factor:expr $(, $offset:expr )* ) ),+ } ) => {
-        paste::paste! {
-            #[derive(Clone, Copy, Debug)]
-            pub enum $unit {
-                $( $var(f64), )+
+    ( @unit $unit:ident : $dim:ident { $( $var:ident => ($new:ident, $abbr:literal, $factor:expr $(, $offset:expr )* ) ),+ } ) => {
+        #[derive(Clone, Copy, Debug)]
+        pub enum $unit {
+            $( $var(f64), )+
+        }
+
+        impl Unit for $unit {
+            fn canonical(&self) -> $crate::Canonical {
+                match self {
+                    $(
+                        $unit::$var(p) => $crate::Canonical::new(vec![$crate::Factor {
+                            symbol: $abbr,
+                            scale: $factor,
+                            dimension: $crate::Dimension::$dim,
+                            exponent: $crate::Exponent::from(*p),
+                        }]),
+                    )+
+                }
             }
 
-            impl Unit for $unit {
-                fn factor(&self) -> f64 {
-                    match self {
-                        $( $unit::$var(_) => $factor, )+
-                    }
+            fn dimension(&self) -> $crate::Dimension {
+                match self {
+                    $( $unit::$var(p) => $crate::Dimension::$dim.pow($crate::Exponent::from(*p)), )+
                 }
+            }
 
-                fn power(&self) -> Option<f64> {
-                    match self {
-                        $( $unit::$var(p) => Some(*p), )+
-                    }
+            fn factor(&self) -> f64 {
+                match self {
+                    $( $unit::$var(p) => f64::powf($factor, *p), )+
                 }
+            }
 
-                fn offset(&self) -> f64 {
-                    match self {
-                        $( $unit::$var(_) => 0. $( + $offset )*, )+
-                    }
+            fn power(&self) -> Option<f64> {
+                match self {
+                    $( $unit::$var(p) => Some(*p), )+
                 }
+            }
 
-                fn map_power<F>(&mut self, f: &F) -> $unit
-                where
-                    F: Fn(f64) -> f64,
-                {
-                    match self {
-                        $(
-                            $unit::$var(p) => {
-                                *p = f(*p);
-                                *self
-                            },
-                        )+
-                    }
+            fn offset(&self) -> f64 {
+                match self {
+                    $( $unit::$var(_) => 0. $( + $offset )*, )+
                 }
             }
 
-            $(
-                #[allow(dead_code)]
-                pub fn $new(value: f64) -> Value<$unit> {
-                    Value {
-                        value,
-                        unit: $unit::$var(1.),
-                    }
+            fn map_power<F>(&mut self, f: &F) -> $unit
+            where
+                F: Fn(f64) -> f64,
+            {
+                match self {
+                    $(
+                        $unit::$var(p) => {
+                            *p = f(*p);
+                            *self
+                        },
+                    )+
                 }
-            )+
+            }
+        }
+
+        impl $crate::Homogeneous for $unit {}
+
+        $(
+            #[allow(dead_code)]
+            pub fn $new(value: f64) -> Value<$unit> {
+                Value {
+                    value,
+                    unit: $unit::$var(1.),
+                }
+            }
+        )+
+    };
+
+    // A named unit of a compound dimension, with a checked `to_` method on
+    // any value, so `(kilograms(2.) * meters(3.) / ...).to_newtons()`.
+    ( derived $unit:ident : $dim:ident { $( $var:ident => ($new:ident, $abbr:literal, $factor:expr) ),+ } ) => {
+        $crate::unit!(@unit $unit : $dim { $( $var => ($new, $abbr, $factor) ),+ });
+
+        paste::paste! {
+            impl<U: Unit> Value<U> {
+                $(
+                    #[allow(dead_code)]
+                    pub fn [<to_$new>](self) -> std::result::Result<Value<$unit>, $crate::DimensionError> {
+                        self.convert($unit::$var(1.))
+                    }
+                )+
+            }
+        }
+    };
+
+    ( $unit:ident : $dim:ident { $( $var:ident => ($new:ident, $abbr:literal, $factor:expr $(, $offset:expr )* ) ),+ } ) => {
+        $crate::unit!(@unit $unit : $dim { $( $var => ($new, $abbr, $factor $(, $offset)*) ),+ });
 
+        paste::paste! {
             impl Value<$unit> {
                 $(
                     #[allow(dead_code)]
@@ -241,7 +400,6 @@ macro_rules! unit {
                     }
                 )+
             }
-
         }
     };
 }
@@ -24,7 +24,10 @@ fn main() {
     println!("{:?} / {:?} = {:?}", inch, gram, inch / gram);
     println!(
         "{:?}",
-        ((inch * gram) + (inch * gram) + (meter * kilogram)) * meter * kilogram
+        (inch * gram)
+            .try_add(inch * gram)
+            .and_then(|sum| sum.try_add(meter * kilogram))
+            .map(|sum| sum * meter * kilogram)
     );
 }
 
@@ -32,7 +35,7 @@ fn main() {
 mod tests {
     use float_eq::assert_float_eq;
 
-    use ntz::prelude::*;
+    use ntz::{angle::degrees, prelude::*, Dimension};
 
     use super::*;
 
@@ -43,8 +46,39 @@ mod tests {
 
     #[test]
     fn homogeneity() {
-        let a = grams(99.) * meters(22.) + grams(2323.) * meters(98.);
-        //let b = grams(99.) * meters(22.) + grams(2323.) * degrees(98.);
+        let a = (grams(99.) * meters(22.))
+            .try_add(grams(2323.) * meters(98.))
+            .unwrap();
+        assert_eq!(a.dimension(), Dimension::LENGTH * Dimension::MASS);
+        assert_eq!(a.dimension().to_string(), "L M");
+
+        // `a + grams(2323.) * degrees(98.)` doesn't compile, but the order
+        // of the factors needn't match
+        let b = grams(2323.) * degrees(98.);
+        let error = a.try_add(b).unwrap_err();
+        assert_eq!(error.to_string(), "incompatible dimensions L M and M rad");
+        assert_float_eq!(
+            a.try_add(meters(1.) * kilograms(1.)).unwrap().value,
+            a.value + 1000.,
+            abs <= 1e-9
+        );
+
+        // An area and a ratio are the same type, but not the same thing
+        let area = meters(2.) * meters(3.);
+        let ratio = meters(2.) / meters(3.);
+        assert_eq!(ratio.dimension(), Dimension::NONE);
+        assert!(area.try_sub(ratio).is_err());
+        assert!(area.partial_cmp(&ratio).is_none());
+        assert!(area != ratio);
+        assert_eq!(meters(4.).sqrt().dimension().to_string(), "L^(1/2)");
+    }
+
+    #[test]
+    fn heterogeneity() {
+        let error = (meters(2.) * meters(3.))
+            .try_add(meters(2.) / meters(3.))
+            .unwrap_err();
+        assert_eq!(error.to_string(), "incompatible dimensions L^2 and 1");
     }
 
     #[test]
@@ -104,6 +138,8 @@ mod tests {
         println!("{:?}", n);
         assert_eq!(m.unit.power(), Some(1.));
         assert_eq!(n.unit.power(), None);
+        assert_eq!((m * m).unit.power(), Some(2.));
+        assert_eq!(n.unit.canonical().to_string(), "m^(-1/2)·kg");
     }
 
     #[test]
@@ -130,7 +166,10 @@ mod tests {
         println!("{:?} / {:?} = {:?}", inch, gram, inch / gram);
         println!(
             "{:?}",
-            ((inch * gram) + (inch * gram) + (meter * kilogram)) * meter * kilogram
+            (inch * gram)
+                .try_add(inch * gram)
+                .and_then(|sum| sum.try_add(meter * kilogram))
+                .map(|sum| sum * meter * kilogram)
         );
     }
 }
@@ -1,7 +1,7 @@
 use crate::prelude::*;
 
 unit! {
-    Scalar {
+    Scalar: NONE {
         Unit => (unit, "", 1.)
     }
 }
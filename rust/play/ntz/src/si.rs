@@ -1,7 +1,7 @@
 use crate::prelude::*;
 
 unit! {
-    LengthUnit {
+    LengthUnit: LENGTH {
         AstronomicalUnit => (astronomical_units, "AE", 1.495979e11),
         CentImeter => (centimeters, "cm", 0.01),
         Decimeter => (decimeters, "dm", 0.1),
@@ -19,19 +19,50 @@ unit! {
 }
 
 unit! {
-    MassUnit {
+    MassUnit: MASS {
         Gram => (grams, "g", 0.001),
         Kilogram => (kilograms, "kg", 1.)
     }
 }
 
 unit! {
-    VolumeUnit {
-        Teaspoon => (teaspoons, "tsp", 1.),
-        Tablespoon => (tablespoons, "tbsp", 1.),
-        Cup => (cups, "cup", 1.),
-        Quart => (quarts, "qt", 1.),
-        Pint => (pints, "pint", 1.),
-        Gallon => (gallons, "gal", 1.)
+    // US customary measures
+    VolumeUnit: VOLUME {
+        Teaspoon => (teaspoons, "tsp", 4.92892159375e-6),
+        Tablespoon => (tablespoons, "tbsp", 1.478676478125e-5),
+        Cup => (cups, "cup", 2.365882365e-4),
+        Quart => (quarts, "qt", 9.46352946e-4),
+        Pint => (pints, "pint", 4.73176473e-4),
+        Gallon => (gallons, "gal", 3.785411784e-3)
+    }
+}
+
+unit! {
+    derived Frequency: FREQUENCY {
+        Hertz => (hertz, "Hz", 1.)
+    }
+}
+
+unit! {
+    derived Force: FORCE {
+        Newton => (newtons, "N", 1.)
+    }
+}
+
+unit! {
+    derived Energy: ENERGY {
+        Joule => (joules, "J", 1.)
+    }
+}
+
+unit! {
+    derived Power: POWER {
+        Watt => (watts, "W", 1.)
+    }
+}
+
+unit! {
+    derived Pressure: PRESSURE {
+        Pascal => (pascals, "Pa", 1.)
     }
 }
@@ -1,7 +1,7 @@
 use crate::prelude::*;
 
 unit! {
-    Temperature {
+    Temperature: TEMPERATURE {
         Fahrenheit => (fahrenheit, "°F", 1. / 1.8, -32. / 1.8),
         Celsius => (celsius, "°C", 1.)
     }
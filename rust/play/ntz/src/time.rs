@@ -1,7 +1,7 @@
 use crate::prelude::*;
 
 unit! {
-    Time {
+    Time: TIME {
         Hour => (hours, "h", 60. * 60.),
         Minute => (minutes, "min", 60.),
         Second => (seconds, "s", 1.)
@@ -145,3 +145,259 @@ fn ch1() {
         abs <= celsius(0.01)
     );
 }
+
+#[test]
+fn sexagesimal() {
+    let ra: Value<a::Angle> = "12h 34m 56.7s".parse().unwrap();
+    assert_float_eq!(
+        ra,
+        Value::from_hms_tuple((12., 34., 56.7)),
+        abs <= a::seconds(1e-6)
+    );
+    assert_eq!(format!("{:.1}", ra.display_hms()), "12h 34m 56.7s");
+    assert_eq!(ra.to_hms_tuple().0, 12.);
+
+    let obliquity: Value<a::Angle> = "-23° 26' 21\"".parse().unwrap();
+    assert_float_eq!(obliquity, a::degrees(-23.439_167), abs <= a::degrees(1e-6));
+    assert_eq!(obliquity.display_dms().to_string(), "-23° 26' 21\"");
+    for same in &["-23°26′21″", "-23d 26m 21s", "-23° 26.35'", "-23.439167"] {
+        assert_float_eq!(
+            same.parse::<Value<a::Angle>>().unwrap(),
+            obliquity,
+            abs <= a::seconds(0.01)
+        );
+    }
+
+    // Seconds round up into minutes, and a tiny negative angle is zero
+    assert_eq!(
+        a::hours(1.999_999_9).display_hms().to_string(),
+        "2h 00m 00s"
+    );
+    assert_eq!(a::degrees(-1e-9).display_dms().to_string(), "0° 00' 00\"");
+
+    // As a time of day, wrapped into 24 hours
+    assert_eq!(a::hours(13.5).display_clock().to_string(), "13:30:00");
+    assert_eq!(a::hours(-1.).display_clock().to_string(), "23:00:00");
+    assert_eq!(
+        format!("{:.1}", a::hours(23.999_97).display_clock()),
+        "23:59:59.9"
+    );
+    assert_eq!(a::hours(23.999_97).display_clock().to_string(), "00:00:00");
+    assert_float_eq!(
+        "0.5rad".parse::<Value<a::Angle>>().unwrap(),
+        a::radians(0.5),
+        abs <= a::radians(1e-12)
+    );
+
+    for bad in &[
+        "",
+        "12h 60m",
+        "12.5h 3m",
+        "12h 3\"",
+        "12h 56s",
+        "23° 21\"",
+        "12m",
+        "1h 2m 3s 4",
+        "north",
+        "inf",
+        "-infinity",
+        "NaN",
+        "1e999",
+        "infrad",
+    ] {
+        assert!(bad.parse::<Value<a::Angle>>().is_err(), "{}", bad);
+    }
+}
+
+#[test]
+fn compound() {
+    use ntz::{Exponent, MulUnit};
+
+    // inches * grams in metres * kilograms, and back the other way round
+    let moment = inches(10.) * grams(500.);
+    let si = moment
+        .convert(MulUnit(LengthUnit::Meter(1.), MassUnit::Kilogram(1.)))
+        .unwrap();
+    assert_float_eq!(si.value, 0.127, abs <= 1e-12);
+    let back = si
+        .convert(MulUnit(MassUnit::Gram(1.), LengthUnit::Inch(1.)))
+        .unwrap();
+    assert_float_eq!(back.value, 5000., abs <= 1e-9);
+    assert!(moment.convert(Force::Newton(1.)).is_err());
+
+    // However deeply nested, and whatever cancels
+    let nested = meters(2.) * kilograms(3.) * meters(4.) / kilograms(1.);
+    assert_eq!(nested.unit.canonical().to_string(), "m^2");
+    assert_eq!(nested.unit.power(), Some(2.));
+    assert_eq!((inches(1.) * grams(1.)).unit.power(), None);
+    assert_float_eq!((kilometers(3.) / kilometers(1.)).norm(), 3., abs <= 1e-12);
+    assert_eq!(
+        (grams(1.) * centimeters(1.) / (t::seconds(1.) * t::seconds(1.)))
+            .unit
+            .canonical()
+            .to_string(),
+        "cm·g·s^-2"
+    );
+
+    // Roots
+    let root = kilometers(4.).sqrt();
+    assert_eq!(root.unit.canonical().to_string(), "km^(1/2)");
+    assert_float_eq!(root.norm(), 4000_f64.sqrt(), abs <= 1e-9);
+    assert_eq!(Exponent::from(-0.5), Exponent::new(-1, 2));
+    assert_eq!(
+        Exponent::from(1. / 3.) + Exponent::from(2. / 3.),
+        Exponent::new(1, 1)
+    );
+}
+
+#[test]
+fn named() {
+    use ntz::scalar;
+
+    let force = kilograms(2.) * meters(3.) / (t::seconds(1.) * t::seconds(2.));
+    assert_eq!(force.unit.canonical().to_string(), "N");
+    assert_float_eq!(
+        force.to_newtons().unwrap(),
+        newtons(3.),
+        abs <= newtons(1e-12)
+    );
+    let same = grams(1.) * kilometers(1.) / (t::seconds(1.) * t::seconds(1.));
+    assert_eq!(same.unit.canonical().to_string(), "N");
+    assert!(meters(1.).to_newtons().is_err());
+
+    let kwh = kilometers(1.) * watts(1.) * t::hours(1.) / meters(1.);
+    assert_float_eq!(kwh.to_joules().unwrap(), joules(3.6e6), abs <= joules(1e-6));
+    assert_eq!(
+        (joules(10.) / t::seconds(2.)).unit.canonical().to_string(),
+        "W"
+    );
+    assert_float_eq!(
+        (newtons(10.) / (meters(2.) * meters(1.)))
+            .to_pascals()
+            .unwrap(),
+        pascals(5.),
+        abs <= pascals(1e-12)
+    );
+
+    let rate = scalar::unit(1.) / t::seconds(0.5);
+    assert_eq!(rate.unit.canonical().to_string(), "Hz");
+    assert_float_eq!(rate.to_hertz().unwrap(), hertz(2.), abs <= hertz(1e-12));
+
+    // Angular speed isn't a frequency
+    assert!((a::radians(1.) / t::seconds(1.)).to_hertz().is_err());
+}
+
+#[test]
+fn volume() {
+    assert_float_eq!(gallons(1.).to_quarts(), quarts(4.), abs <= quarts(1e-12));
+    assert_float_eq!(quarts(1.).to_pints(), pints(2.), abs <= pints(1e-12));
+    assert_float_eq!(
+        cups(1.).to_tablespoons(),
+        tablespoons(16.),
+        abs <= tablespoons(1e-9)
+    );
+    assert_float_eq!(
+        tablespoons(1.).to_teaspoons(),
+        teaspoons(3.),
+        abs <= teaspoons(1e-9)
+    );
+
+    // A cubic decimetre is a litre
+    let litre = decimeters(1.) * decimeters(1.) * decimeters(1.);
+    assert_float_eq!(
+        litre.convert(VolumeUnit::Gallon(1.)).unwrap().value,
+        0.264_172_052,
+        abs <= 1e-9
+    );
+    assert!((decimeters(1.) * decimeters(1.))
+        .convert(VolumeUnit::Gallon(1.))
+        .is_err());
+}
+
+#[test]
+fn factor_follows_power() {
+    // A unit's factor is raised to its power; it used to be the factor of
+    // the first power whatever the power, so km^2 was 1000 m^2
+    assert_eq!(LengthUnit::Kilometer(1.).factor(), 1000.);
+    assert_eq!(LengthUnit::Kilometer(2.).factor(), 1e6);
+    assert_eq!(LengthUnit::Kilometer(-1.).factor(), 1e-3);
+    assert_float_eq!(
+        LengthUnit::Kilometer(0.5).factor(),
+        1000_f64.sqrt(),
+        abs <= 1e-12
+    );
+
+    // So a quotient of lengths is a pure number, not 1000 times one
+    assert_float_eq!((meters(500.) / kilometers(1.)).norm(), 0.5, abs <= 1e-12);
+    assert_float_eq!(
+        (kilometers(1.) / t::hours(1.)).norm(),
+        1000. / 3600.,
+        abs <= 1e-12
+    );
+}
+
+#[test]
+#[should_panic(expected = "exponent 1/0 has a zero denominator")]
+fn zero_denominator() {
+    ntz::Exponent::new(1, 0);
+}
+
+#[test]
+#[should_panic(expected = "incompatible dimensions L^2 and L")]
+fn mismatched_powers() {
+    // The type is the same, but square metres aren't metres
+    let square = Value {
+        value: 1.,
+        unit: LengthUnit::Meter(2.),
+    };
+    let _ = square + meters(1.);
+}
+
+/// A unit from outside the crate, neither `Copy` nor with a `canonical()`
+/// of its own.
+#[derive(Clone, Debug)]
+struct Furlong(f64);
+
+impl Unit for Furlong {
+    fn dimension(&self) -> ntz::Dimension {
+        ntz::Dimension::LENGTH.pow(ntz::Exponent::from(self.0))
+    }
+
+    fn factor(&self) -> f64 {
+        201.168_f64.powf(self.0)
+    }
+
+    fn map_power<F>(&mut self, f: &F) -> Self
+    where
+        F: Fn(f64) -> f64,
+    {
+        self.0 = f(self.0);
+        self.clone()
+    }
+
+    fn offset(&self) -> f64 {
+        0.
+    }
+
+    fn power(&self) -> Option<f64> {
+        Some(self.0)
+    }
+}
+
+#[test]
+fn foreign_unit() {
+    let furlongs = Value {
+        value: 2.,
+        unit: Furlong(1.),
+    };
+    assert_eq!(
+        furlongs.unit.canonical().dimension(),
+        ntz::Dimension::LENGTH
+    );
+    assert_float_eq!(
+        furlongs.clone().convert(LengthUnit::Meter(1.)).unwrap(),
+        meters(402.336),
+        abs <= meters(1e-9)
+    );
+    assert_float_eq!((furlongs / t::seconds(1.)).norm(), 402.336, abs <= 1e-9);
+}
@@ -2,10 +2,11 @@
 use std::{env, fs, path::PathBuf};
 
 use chrono::{DateTime, Utc};
-use rusqlite::{named_params, Connection, ToSql};
+use rusqlite::{named_params, params, Connection, ToSql};
 
 use crate::{
     error::{err, Result},
+    forecast::Forecast,
     APP,
 };
 
@@ -23,6 +24,29 @@ pub struct CacheEntry {
     pub content: String,
 }
 
+/// How `ApiClient` requests were served.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CacheStats {
+    /// Served from a fresh cache entry.
+    pub hits: u32,
+    /// Fetched because the cache had no entry.
+    pub misses: u32,
+    /// Fetched again because the cache entry had expired.
+    pub revalidations: u32,
+}
+
+/// One forecast period as it was predicted by a single forecast snapshot.
+#[derive(Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub url: String,
+    pub generated_at: DateTime<Utc>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub temperature: u32,
+    pub temperature_unit: String,
+    pub short_forecast: String,
+}
+
 impl Cache {
     /// Connect to a database and return a handle to perform caching
     /// operations.
@@ -59,10 +83,12 @@ impl Cache {
             None => Connection::open_in_memory()?,
         };
 
-        // Create tables if this is a new database
+        // Create tables if this is a new database, and bring older ones up to
+        // date
         if conn.prepare("select max(id) from version").is_err() {
             init_db(&conn)?;
         }
+        migrate(&conn)?;
 
         Ok(Self { conn })
     }
@@ -100,6 +126,21 @@ impl Cache {
         last_modified: DateTime<Utc>,
         content: &str,
     ) -> Result<()>
+    where
+        T: AsRef<str> + ToSql,
+    {
+        self.insert_at(url, max_age, last_modified, Utc::now(), content)
+    }
+
+    /// Insert an entry as if it was cached at `created_at`.
+    pub fn insert_at<T>(
+        &mut self,
+        url: T,
+        max_age: Option<u32>,
+        last_modified: DateTime<Utc>,
+        created_at: DateTime<Utc>,
+        content: &str,
+    ) -> Result<()>
     where
         T: AsRef<str> + ToSql,
     {
@@ -113,7 +154,7 @@ impl Cache {
             sql,
             named_params! {
                 ":url": url,
-                ":created_at": Utc::now(),
+                ":created_at": created_at,
                 ":max_age": max_age,
                 ":last_modified": last_modified,
                 ":content": content,
@@ -123,15 +164,176 @@ impl Cache {
         Ok(())
     }
 
-    pub fn db_version(&self) -> Result<u32> {
-        let mut stmt = self.conn.prepare("select max(id) from version")?;
-        let mut rows = stmt.query([])?;
-        if let Some(row) = rows.next()? {
-            Ok(row.get(0)?)
-        } else {
-            err("Can't determine database version")
+    /// Record every period of a forecast under its `generated_at` time.
+    /// Returns `false` if this snapshot was already recorded.
+    pub fn insert_forecast<T>(&mut self, url: T, forecast: &Forecast) -> Result<bool>
+    where
+        T: AsRef<str> + ToSql,
+    {
+        let generated_at = forecast.properties.generated_at.with_timezone(&Utc);
+        let sql = "\
+            insert or ignore into forecast_history(url, generated_at, start_time,
+                end_time, temperature, temperature_unit, short_forecast)
+            values(:url, :generated_at, :start_time, :end_time, :temperature,
+                :temperature_unit, :short_forecast)";
+
+        let tx = self.conn.transaction()?;
+        let mut inserted = 0;
+        for period in forecast.properties.periods.iter() {
+            inserted += tx.execute(
+                sql,
+                named_params! {
+                    ":url": url,
+                    ":generated_at": generated_at,
+                    ":start_time": period.start_time.with_timezone(&Utc),
+                    ":end_time": period.end_time.with_timezone(&Utc),
+                    ":temperature": period.temperature,
+                    ":temperature_unit": period.temperature_unit,
+                    ":short_forecast": period.short_forecast,
+                },
+            )?;
         }
+        tx.commit()?;
+
+        Ok(inserted > 0)
+    }
+
+    /// Record an observed temperature at a location, such as the gridpoint
+    /// url of a forecast, for comparison against past forecasts.
+    pub fn insert_observation(
+        &mut self,
+        location: &str,
+        observed_at: DateTime<Utc>,
+        temperature: f64,
+        temperature_unit: &str,
+    ) -> Result<()> {
+        self.conn.execute(
+            "insert or replace into observation_history(location, observed_at,
+                temperature, temperature_unit)
+             values(?, ?, ?, ?)",
+            params![location, observed_at, temperature, temperature_unit],
+        )?;
+
+        Ok(())
+    }
+
+    /// All recorded predictions for periods that contain `time`, by forecast
+    /// url and oldest snapshot first.
+    pub fn forecast_history(&self, time: DateTime<Utc>) -> Result<Vec<HistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "select url, generated_at, start_time, end_time, temperature,
+                temperature_unit, short_forecast
+             from forecast_history
+             where start_time <= :time and end_time > :time
+             order by url, generated_at, start_time",
+        )?;
+        let rows = stmt.query_map(named_params! {":time": time}, history_entry)?;
+
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Every recorded prediction, grouped by forecast url and period and
+    /// ordered by snapshot.
+    pub fn all_forecast_history(&self) -> Result<Vec<HistoryEntry>> {
+        let mut stmt = self.conn.prepare(
+            "select url, generated_at, start_time, end_time, temperature,
+                temperature_unit, short_forecast
+             from forecast_history
+             order by url, start_time, end_time, generated_at",
+        )?;
+        let rows = stmt.query_map([], history_entry)?;
+
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// The earliest temperature observed at a location within `[start, end)`.
+    pub fn observation_between(
+        &self,
+        location: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Option<(f64, String)>> {
+        let mut stmt = self.conn.prepare(
+            "select temperature, temperature_unit from observation_history
+             where location = ? and observed_at >= ? and observed_at < ?
+             order by observed_at limit 1",
+        )?;
+        let mut rows = stmt.query(params![location, start, end])?;
+
+        match rows.next()? {
+            Some(row) => Ok(Some((row.get(0)?, row.get(1)?))),
+            None => Ok(None),
+        }
+    }
+
+    /// Whether a notification rule already fired at a location for a time
+    /// overlapping or adjoining `[start_time, end_time)`.  If it did, the
+    /// recorded event is extended to cover this one.
+    pub fn notification_fired(
+        &mut self,
+        rule: &str,
+        location: &str,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<bool> {
+        let updated = self.conn.execute(
+            "update notifications set end_time = max(end_time, :end_time)
+             where rule = :rule and location = :location
+                and start_time <= :end_time and end_time >= :start_time",
+            named_params! {
+                ":rule": rule,
+                ":location": location,
+                ":start_time": start_time,
+                ":end_time": end_time,
+            },
+        )?;
+
+        Ok(updated > 0)
     }
+
+    /// Record that a notification rule fired at a location for
+    /// `[start_time, end_time)`.
+    pub fn insert_notification(
+        &mut self,
+        rule: &str,
+        location: &str,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "insert into notifications(rule, location, start_time, end_time)
+             values(?, ?, ?, ?)",
+            params![rule, location, start_time, end_time],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn db_version(&self) -> Result<u32> {
+        db_version(&self.conn)
+    }
+}
+
+fn db_version(conn: &Connection) -> Result<u32> {
+    let mut stmt = conn.prepare("select max(id) from version")?;
+    let mut rows = stmt.query([])?;
+    if let Some(row) = rows.next()? {
+        Ok(row.get(0)?)
+    } else {
+        err("Can't determine database version")
+    }
+}
+
+fn history_entry(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
+    Ok(HistoryEntry {
+        url: row.get(0)?,
+        generated_at: row.get(1)?,
+        start_time: row.get(2)?,
+        end_time: row.get(3)?,
+        temperature: row.get(4)?,
+        temperature_unit: row.get(5)?,
+        short_forecast: row.get(6)?,
+    })
 }
 
 fn init_db(conn: &Connection) -> Result<()> {
@@ -151,6 +353,51 @@ fn init_db(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
+/// Schema changes after version 1, in order.  The first brings a database to
+/// version 2.
+const MIGRATIONS: &[&str] = &[
+    // Forecast history, recorded when enabled in the config file
+    "\
+    create table forecast_history(
+        url text,
+        generated_at datetime,
+        start_time datetime,
+        end_time datetime,
+        temperature int,
+        temperature_unit text,
+        short_forecast text,
+        unique(url, generated_at, start_time));
+    create table observation_history(
+        location text,
+        observed_at datetime,
+        temperature real,
+        temperature_unit text,
+        unique(location, observed_at));
+    ",
+    // Notification rules that have fired
+    "\
+    create table notifications(
+        rule text,
+        location text,
+        start_time datetime,
+        end_time datetime);
+    ",
+];
+
+/// The schema version `migrate` brings every database to.
+pub const DB_VERSION: u32 = MIGRATIONS.len() as u32 + 1;
+
+/// Apply the migrations a database hasn't had yet.
+fn migrate(conn: &Connection) -> Result<()> {
+    let version = db_version(conn)?;
+    for (to, sql) in (2..).zip(MIGRATIONS).skip_while(|(to, _)| *to <= version) {
+        conn.execute_batch(sql)?;
+        conn.execute("insert into version values(?)", [to])?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,9 +408,9 @@ mod tests {
     fn tempcache() -> (Cache, TempDir) {
         let temp_dir = tempdir().unwrap();
 
-        // Cache should create new database with version = 1
+        // Cache should create new database with the latest version
         let cache = Cache::with_base_dir(Some(temp_dir.path().to_path_buf())).unwrap();
-        assert_eq!(cache.db_version().unwrap(), 1);
+        assert_eq!(cache.db_version().unwrap(), DB_VERSION);
 
         (cache, temp_dir)
     }
@@ -196,16 +443,70 @@ mod tests {
             Ok(mut rows) => {
                 assert_eq!(
                     rows.next().unwrap().unwrap().get::<usize, u32>(0).unwrap(),
-                    1
+                    DB_VERSION
                 );
             }
             Err(_) => panic!(),
         }
 
         // Create a new instance of `Cache` to make sure it can reuse the database
-        conn.execute("update version set id = ?", [2]).unwrap();
+        conn.execute("update version set id = ?", [DB_VERSION + 1])
+            .unwrap();
+        let cache = Cache::with_base_dir(Some(temp_dir.path().to_path_buf())).unwrap();
+        assert_eq!(cache.db_version().unwrap(), DB_VERSION + 1);
+    }
+
+    #[test]
+    fn db_migrates() {
+        let temp_dir = tempdir().unwrap();
+        let mut db_path = temp_dir.path().join(APP);
+        db_path.set_extension("db");
+
+        // A database from before forecast history
+        let conn = Connection::open(&db_path).unwrap();
+        init_db(&conn).unwrap();
+        drop(conn);
+
+        let mut cache = Cache::with_base_dir(Some(temp_dir.path().to_path_buf())).unwrap();
+        assert_eq!(cache.db_version().unwrap(), DB_VERSION);
+        cache
+            .insert_observation("gridpoint.url", Utc::now(), 20., "C")
+            .unwrap();
+
+        // Opening it again changes nothing
         let cache = Cache::with_base_dir(Some(temp_dir.path().to_path_buf())).unwrap();
-        assert_eq!(cache.db_version().unwrap(), 2);
+        assert_eq!(cache.db_version().unwrap(), DB_VERSION);
+    }
+
+    #[test]
+    fn notifications_fire_once() {
+        let (mut cache, _temp_dir) = tempcache();
+        let start = Utc::now();
+        let hour = chrono::Duration::hours(1);
+        let mut fired = |rule, location, start, end| {
+            let fired = cache
+                .notification_fired(rule, location, start, end)
+                .unwrap();
+            if !fired {
+                cache
+                    .insert_notification(rule, location, start, end)
+                    .unwrap();
+            }
+            fired
+        };
+
+        assert!(!fired("frost", "home", start, start + hour));
+        assert!(fired("frost", "home", start, start + hour));
+
+        // A continuation of the same event doesn't fire again
+        let later = start + hour;
+        assert!(fired("frost", "home", later, later + hour));
+        assert!(!fired("wind", "home", later, later + hour));
+
+        // A separate event does, and so does the same event somewhere else
+        let tomorrow = start + hour * 24;
+        assert!(!fired("frost", "home", tomorrow, tomorrow + hour));
+        assert!(!fired("frost", "cabin", start, start + hour));
     }
 
     #[test]
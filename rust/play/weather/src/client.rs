@@ -1,13 +1,16 @@
 use std::path::PathBuf;
 
-use chrono::{Duration, Utc};
-use reqwest::{header::CACHE_CONTROL, Client};
+use chrono::{DateTime, Duration, Utc};
+use reqwest::{header::CACHE_CONTROL, Client, StatusCode};
 
 use crate::{
-    cache::Cache,
-    error::{err, Error, Result},
-    forecast::Forecast,
+    cache::{Cache, CacheStats},
+    error::{err, Result},
+    fixture::{Fixture, Mode},
+    forecast::{Forecast, GridpointData},
     location::Point,
+    office::{Office, Product, ProductList},
+    radar::RadarStation,
     types::{Position, Url},
     NWS_API,
 };
@@ -22,6 +25,11 @@ pub struct ApiClient {
     cache: Cache,
     client: Client,
     base_url: Url,
+    history: bool,
+    mode: Mode,
+    time_shift: Duration,
+    clock: Duration,
+    stats: CacheStats,
 }
 
 #[derive(Debug, Default)]
@@ -29,6 +37,9 @@ pub struct ApiClientBuilder {
     cache_base_dir: Option<PathBuf>,
     api_key: Option<String>,
     api_base_url: Option<Url>,
+    history: bool,
+    mode: Mode,
+    time_shift: Option<Duration>,
 }
 
 impl ApiClientBuilder {
@@ -39,6 +50,11 @@ impl ApiClientBuilder {
                 .user_agent(self.api_key.unwrap())
                 .build()?,
             base_url: self.api_base_url.unwrap(),
+            history: self.history,
+            mode: self.mode,
+            time_shift: self.time_shift.unwrap_or_else(Duration::zero),
+            clock: Duration::zero(),
+            stats: CacheStats::default(),
         })
     }
 
@@ -56,6 +72,31 @@ impl ApiClientBuilder {
         self.api_base_url = Some(url.into());
         self
     }
+
+    /// Record every distinct forecast snapshot in the cache's history tables.
+    pub fn history(mut self, enabled: bool) -> Self {
+        self.history = enabled;
+        self
+    }
+
+    /// Save every response fetched from the API in a fixture directory.
+    pub fn record(mut self, dir: PathBuf) -> Self {
+        self.mode = Mode::Record(dir);
+        self
+    }
+
+    /// Serve responses from a fixture directory instead of the API.
+    pub fn replay(mut self, dir: PathBuf) -> Self {
+        self.mode = Mode::Replay(dir);
+        self
+    }
+
+    /// Move the timestamps of replayed fixtures, so that a response recorded
+    /// at `recorded_at` is cached as if fetched at `recorded_at + shift`.
+    pub fn time_shift(mut self, shift: Duration) -> Self {
+        self.time_shift = Some(shift);
+        self
+    }
 }
 
 impl ApiClient {
@@ -89,31 +130,144 @@ impl ApiClient {
     /// Fetch a weather forecast from a given url, for different time
     /// resolutions.
     pub async fn get_forecast_from_url(&mut self, url: &str) -> Result<Forecast> {
+        let json = self.fetch_resource(url).await?;
+        let forecast = serde_json::from_str(&json)?;
+        if self.history {
+            self.cache.insert_forecast(url, &forecast)?;
+        }
+
+        Ok(forecast)
+    }
+
+    /// Fetch the raw forecast data for a gridpoint from a given url.
+    pub async fn get_gridpoint_data_from_url(&mut self, url: &str) -> Result<GridpointData> {
+        let json = self.fetch_resource(url).await?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Fetch a weather forecast office, such as the `forecast_office` of a
+    /// `Point`.
+    pub async fn get_office_from_url(&mut self, url: &str) -> Result<Office> {
+        let json = self.fetch_resource(url).await?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Fetch a weather forecast office by its id, such as `BGM`.
+    pub async fn get_office(&mut self, office_id: &str) -> Result<Office> {
+        let url = format!("{}/offices/{}", self.base_url, office_id);
+        self.get_office_from_url(&url).await
+    }
+
+    /// Fetch a radar station by its id, such as the `radar_station` of a
+    /// `Point`.
+    pub async fn get_radar_station(&mut self, station_id: &str) -> Result<RadarStation> {
+        let url = format!("{}/radar/stations/{}", self.base_url, station_id);
+        let json = self.fetch_resource(&url).await?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// List the text products of a type, such as `AFD`, issued for a
+    /// location, such as the `cwa` of a `Point`.
+    pub async fn get_products(
+        &mut self,
+        product_type: &str,
+        location: &str,
+    ) -> Result<ProductList> {
+        let url = format!(
+            "{}/products/types/{}/locations/{}",
+            self.base_url, product_type, location
+        );
+        let json = self.fetch_resource(&url).await?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Fetch a text product from a given url.
+    pub async fn get_product_from_url(&mut self, url: &str) -> Result<Product> {
         let json = self.fetch_resource(url).await?;
         Ok(serde_json::from_str(&json)?)
     }
 
+    /// Fetch the latest Area Forecast Discussion for a forecast office.
+    pub async fn get_forecast_discussion(&mut self, cwa: &str) -> Result<Product> {
+        let products = self.get_products("AFD", cwa).await?;
+        let latest = products
+            .products
+            .iter()
+            .max_by_key(|x| x.issuance_time)
+            .map(|x| x.url.clone());
+
+        match latest {
+            Some(url) => self.get_product_from_url(&url).await,
+            None => err(&format!("No forecast discussion available for {}", cwa)),
+        }
+    }
+
+    /// The cache backing this client, which also holds forecast history.
+    pub fn cache(&self) -> &Cache {
+        &self.cache
+    }
+
+    pub fn cache_mut(&mut self) -> &mut Cache {
+        &mut self.cache
+    }
+
+    /// Cache hits, misses and revalidations since the client was built.
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Run the client's clock ahead of the system clock, e.g. to let cached
+    /// resources expire in tests.
+    pub fn advance_clock(&mut self, by: Duration) {
+        self.clock = self.clock + by;
+    }
+
+    /// The current time as seen by the client.
+    pub fn now(&self) -> DateTime<Utc> {
+        Utc::now() + self.clock
+    }
+
     async fn fetch_resource(&mut self, url: &str) -> Result<String> {
         match self.cache.get(url)? {
             Some(entry) => {
                 let max_age = entry.max_age.unwrap_or(0);
                 let expires_at = entry.created_at + Duration::seconds(max_age as i64);
-                if expires_at <= Utc::now() {
+                if expires_at <= self.now() {
                     // TODO: check last_modified for updated resource
+                    self.stats.revalidations += 1;
                     Ok(self.get_and_cache(url).await?)
                 } else {
+                    self.stats.hits += 1;
                     Ok(entry.content)
                 }
             }
-            None => Ok(self.get_and_cache(url).await?),
+            None => {
+                self.stats.misses += 1;
+                Ok(self.get_and_cache(url).await?)
+            }
         }
     }
 
     async fn get_and_cache(&mut self, url: &str) -> Result<String> {
-        let response = self.client.get(url).send().await?;
+        let (response, fetched_at) = match &self.mode {
+            Mode::Replay(dir) => {
+                let mut fixture = Fixture::load(dir, url)?;
+                fixture.shift(self.time_shift);
+                let recorded_at = fixture.recorded_at;
+                (fixture, recorded_at)
+            }
+            Mode::Live | Mode::Record(_) => {
+                let response = self.client.get(url).send().await?;
+                (Fixture::from_response(url, response).await?, self.now())
+            }
+        };
+        if let Mode::Record(dir) = &self.mode {
+            response.save(dir)?;
+        }
 
         // Bail on error
-        let status = response.status();
+        let status =
+            StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
         if !status.is_success() {
             return err(format!(
                 "Unable to connect to {}: {} {}",
@@ -125,26 +279,24 @@ impl ApiClient {
         }
 
         let mut max_age = None;
-        if let Some(cache_control) = response.headers().get(CACHE_CONTROL) {
-            if let Ok(cache_control) = cache_control.to_str() {
-                for mut part in cache_control.split(',') {
-                    part = part.trim();
-                    if part.starts_with("max-age") {
-                        if let Some((_, mut v)) = part.split_once('=') {
-                            v = v.trim();
-                            if let Ok(v) = v.parse::<u32>() {
-                                max_age = Some(v);
-                                break;
-                            }
+        if let Some(cache_control) = response.header(CACHE_CONTROL.as_str()) {
+            for mut part in cache_control.split(',') {
+                part = part.trim();
+                if part.starts_with("max-age") {
+                    if let Some((_, mut v)) = part.split_once('=') {
+                        v = v.trim();
+                        if let Ok(v) = v.parse::<u32>() {
+                            max_age = Some(v);
+                            break;
                         }
                     }
                 }
             }
         }
 
-        let text = response.text().await?;
-        self.cache.insert(url, max_age, Utc::now(), &text)?;
-        Ok(text)
+        self.cache
+            .insert_at(url, max_age, fetched_at, fetched_at, &response.body)?;
+        Ok(response.body)
     }
 }
 
@@ -4,12 +4,14 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use chrono::{DateTime, FixedOffset};
 use home::home_dir;
 use serde::{Deserialize, Serialize};
 use structopt::StructOpt;
 
 use crate::{
     error::{err, Result},
+    rules::Rule,
     APP,
 };
 
@@ -34,6 +36,50 @@ pub struct Opt {
     pub api_key: Option<String>,
     /// Profile
     pub location: Option<String>,
+    /// Save API responses as fixtures in this directory
+    #[structopt(
+        long,
+        env = "WTHR_RECORD",
+        parse(from_os_str),
+        conflicts_with = "replay"
+    )]
+    pub record: Option<PathBuf>,
+    /// Serve API responses from fixtures in this directory
+    #[structopt(long, env = "WTHR_REPLAY", parse(from_os_str))]
+    pub replay: Option<PathBuf>,
+    /// Treat replayed fixtures as recorded this many hours later
+    #[structopt(long, requires = "replay", allow_hyphen_values = true)]
+    pub time_shift: Option<i64>,
+    #[structopt(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, PartialEq, StructOpt)]
+pub enum Command {
+    /// Show how recorded forecasts evolved and how accurate they were
+    History {
+        /// Show every recorded forecast for the period containing this time
+        /// (RFC 3339)
+        #[structopt(long)]
+        at: Option<DateTime<FixedOffset>>,
+    },
+    /// Evaluate notification rules against the latest forecast
+    Notify,
+    /// Export the daily forecast
+    Export(Export),
+    /// Show the latest forecast discussion from the local forecast office
+    Discuss,
+}
+
+#[derive(Debug, PartialEq, StructOpt)]
+pub enum Export {
+    /// iCalendar (RFC 5545) with forecast periods, sun times and the next
+    /// full moon
+    Ics {
+        /// Write to a file instead of stdout
+        #[structopt(short, long, parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
@@ -42,10 +88,13 @@ pub struct Config {
     path: PathBuf,
     pub resolution: Option<Resolution>,
     pub api_key: Option<String>,
+    pub history: Option<bool>,
     #[serde(flatten)]
     pub location: Option<Location>,
     #[serde(default)]
     pub locations: HashMap<String, Location>,
+    #[serde(default)]
+    pub rules: Vec<Rule>,
 }
 
 #[derive(Debug)]
@@ -54,6 +103,7 @@ pub struct Params {
     pub longitude: f64,
     pub resolution: Resolution,
     pub api_key: String,
+    pub history: bool,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -76,7 +126,9 @@ impl Default for Config {
             resolution: None,
             location: None,
             api_key: None,
+            history: None,
             locations: HashMap::new(),
+            rules: Vec::new(),
         }
     }
 }
@@ -156,6 +208,7 @@ impl Params {
             longitude,
             api_key: api_key.clone(),
             resolution: (*resolution).clone(),
+            history: config.history.unwrap_or(false),
         })
     }
 }
@@ -188,4 +241,62 @@ mod tests {
         let params = Params::from_merge(&config, &opt);
         assert!(params.is_err());
     }
+
+    #[test]
+    fn parse_subcommand() {
+        let opt = Opt::from_iter([APP, "home"]);
+        assert_eq!(opt.location, Some("home".to_string()));
+        assert_eq!(opt.command, None);
+
+        let opt = Opt::from_iter([APP, "history"]);
+        assert_eq!(opt.location, None);
+        assert_eq!(opt.command, Some(Command::History { at: None }));
+
+        let opt = Opt::from_iter([APP, "export", "ics", "-o", "wthr.ics"]);
+        assert_eq!(
+            opt.command,
+            Some(Command::Export(Export::Ics {
+                output: Some(PathBuf::from("wthr.ics"))
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_time_shift() {
+        let opt = Opt::from_iter([APP, "--replay", "fixtures", "--time-shift", "-48"]);
+        assert_eq!(opt.time_shift, Some(-48));
+
+        // Only replayed fixtures can be shifted
+        assert!(Opt::from_iter_safe([APP, "--time-shift", "2"]).is_err());
+    }
+
+    #[test]
+    fn parse_rules() {
+        let config: Config = toml::from_str(
+            r#"
+            latitude = 42.44
+            longitude = -76.48
+
+            [[rules]]
+            name = "frost"
+            field = "temperature"
+            op = "<"
+            value = 33
+
+            [[rules]]
+            name = "commute rain"
+            field = "probability_of_precipitation"
+            op = ">"
+            value = 70
+            between = [7, 9]
+            command = "echo $WTHR_RULE"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.location.unwrap().latitude, 42.44);
+        assert_eq!(config.rules.len(), 2);
+        assert_eq!(config.rules[0].within_hours, 24);
+        assert_eq!(config.rules[1].between, Some((7, 9)));
+    }
 }
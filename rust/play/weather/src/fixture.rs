@@ -0,0 +1,178 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{err, Result};
+
+/// Where `ApiClient` gets its responses from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Mode {
+    /// Fetch every resource from the API.
+    #[default]
+    Live,
+    /// Fetch from the API and save each response in a fixture directory.
+    Record(PathBuf),
+    /// Serve responses from a fixture directory without touching the network.
+    Replay(PathBuf),
+}
+
+/// A recorded API response.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Fixture {
+    pub url: String,
+    pub status: u16,
+    /// Header names are lowercase, and repeated headers are joined by `, `.
+    pub headers: BTreeMap<String, String>,
+    pub recorded_at: DateTime<Utc>,
+    pub body: String,
+}
+
+impl Fixture {
+    /// Read a response from the API.
+    pub async fn from_response(url: &str, response: Response) -> Result<Self> {
+        let status = response.status().as_u16();
+        let mut headers: BTreeMap<String, String> = BTreeMap::new();
+        for (name, value) in response.headers() {
+            if let Ok(value) = value.to_str() {
+                headers
+                    .entry(name.as_str().to_lowercase())
+                    .and_modify(|x| {
+                        x.push_str(", ");
+                        x.push_str(value);
+                    })
+                    .or_insert_with(|| value.to_string());
+            }
+        }
+
+        Ok(Fixture {
+            url: url.to_string(),
+            status,
+            headers,
+            recorded_at: Utc::now(),
+            body: response.text().await?,
+        })
+    }
+
+    /// Load the fixture recorded for `url`.
+    pub fn load(dir: &Path, url: &str) -> Result<Self> {
+        let path = dir.join(file_name(url));
+        match fs::read_to_string(&path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(_) => err(&format!(
+                "No fixture recorded for {} at {}",
+                url,
+                path.display()
+            )),
+        }
+    }
+
+    /// Save the fixture in `dir`, replacing any earlier recording of its url.
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(
+            dir.join(file_name(&self.url)),
+            serde_json::to_string_pretty(self)?,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(|x| x.as_str())
+    }
+
+    /// Move the time of the recording, and the dates in its headers, as if it
+    /// was recorded `by` later.
+    pub fn shift(&mut self, by: Duration) {
+        self.recorded_at = self.recorded_at + by;
+        for name in &["date", "expires", "last-modified"] {
+            if let Some(value) = self.headers.get_mut(*name) {
+                if let Ok(date) = DateTime::parse_from_rfc2822(value) {
+                    *value = (date.with_timezone(&Utc) + by)
+                        .format("%a, %d %b %Y %H:%M:%S GMT")
+                        .to_string();
+                }
+            }
+        }
+    }
+}
+
+/// The fixture file name for a url.  Only the path and query are used, so
+/// fixtures recorded against one server replay against any other.  Slashes
+/// become `_`, and anything else that isn't a letter, digit, `.`, `-` or `,`
+/// is percent-encoded, `_` included, so no two urls share a file.
+pub fn file_name(url: &str) -> String {
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |i| &rest[i..]),
+        None => url,
+    };
+    let mut name = String::new();
+    for byte in path.trim_start_matches('/').bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'.' | b'-' | b',' => name.push(byte as char),
+            b'/' => name.push('_'),
+            _ => name.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+
+    format!("{}.json", name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixture_file_names() {
+        assert_eq!(
+            file_name("https://api.weather.gov/points/42.4465,-76.4807"),
+            "points_42.4465,-76.4807.json"
+        );
+        assert_eq!(
+            file_name("http://127.0.0.1:4000/gridpoints/BGM/44,69/forecast?units=si"),
+            "gridpoints_BGM_44,69_forecast%3Funits%3Dsi.json"
+        );
+
+        // Urls that differ only in punctuation don't collide
+        let names = ["/a/b", "/a_b", "/a?b", "/a%2Fb", "/a b"]
+            .iter()
+            .map(|x| file_name(x))
+            .collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(names.len(), 5);
+    }
+
+    #[test]
+    fn shift_fixture() {
+        let mut fixture = Fixture {
+            url: "https://api.weather.gov/points/42.4465,-76.4807".to_string(),
+            status: 200,
+            headers: BTreeMap::new(),
+            recorded_at: "2021-06-12T01:05:00Z".parse().unwrap(),
+            body: String::new(),
+        };
+        fixture.headers.insert(
+            "date".to_string(),
+            "Sat, 12 Jun 2021 01:05:00 GMT".to_string(),
+        );
+        fixture
+            .headers
+            .insert("content-type".to_string(), "text/plain".to_string());
+
+        fixture.shift(Duration::days(-2) + Duration::hours(1));
+        assert_eq!(
+            fixture.recorded_at,
+            "2021-06-10T02:05:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            fixture.header("Date"),
+            Some("Thu, 10 Jun 2021 02:05:00 GMT")
+        );
+        assert_eq!(fixture.header("Content-Type"), Some("text/plain"));
+    }
+}
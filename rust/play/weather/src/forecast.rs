@@ -135,15 +135,15 @@ pub struct GridpointDataProperties {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TimeSeries<T> {
-    uom: Option<String>,
-    values: Vec<TimeDatum<T>>,
+    pub uom: Option<String>,
+    pub values: Vec<TimeDatum<T>>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TimeDatum<T> {
-    valid_time: Option<TimeInterval>,
-    value: Option<T>,
+    pub valid_time: Option<TimeInterval>,
+    pub value: Option<T>,
 }
 
 #[derive(Debug, Deserialize)]
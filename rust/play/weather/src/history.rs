@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    cache::{Cache, HistoryEntry},
+    error::Result,
+};
+
+/// Mean absolute temperature error for forecasts issued a given number of
+/// whole days before the period they predict.
+#[derive(Debug, PartialEq)]
+pub struct LeadTimeError {
+    pub lead_days: i64,
+    pub samples: usize,
+    pub mean_absolute_error: f64,
+}
+
+/// The gridpoint a forecast url is for, which its daily and hourly forecasts
+/// share, and which observations are recorded against.
+pub fn location(url: &str) -> &str {
+    match url.find("/forecast") {
+        Some(i) => &url[..i],
+        None => url,
+    }
+}
+
+/// Compare every recorded forecast with the temperature that verified for its
+/// period.  A recorded observation is used when one exists.  Otherwise the
+/// most recent forecast issued before the period started stands in for it,
+/// and is itself left out of the statistics.
+pub fn lead_time_errors(cache: &Cache) -> Result<Vec<LeadTimeError>> {
+    let entries = cache.all_forecast_history()?;
+    let mut errors: BTreeMap<i64, (usize, f64)> = BTreeMap::new();
+
+    // Daily and hourly forecasts, and forecasts for other places, are
+    // separate predictions even for the same period
+    let same_period = |a: &HistoryEntry, b: &HistoryEntry| {
+        a.url == b.url && a.start_time == b.start_time && a.end_time == b.end_time
+    };
+
+    for period in entries.chunk_by(same_period) {
+        let first = &period[0];
+
+        // Only forecasts issued before the period started are predictions
+        let issued: Vec<&HistoryEntry> = period
+            .iter()
+            .filter(|x| x.generated_at <= x.start_time)
+            .collect();
+
+        let observed =
+            cache.observation_between(location(&first.url), first.start_time, first.end_time)?;
+        let (verified, predictions) = match observed {
+            Some((temp, unit)) => {
+                let temp = convert(temp, &unit, &first.temperature_unit);
+                (temp, &issued[..])
+            }
+            None => match issued.split_last() {
+                Some((last, rest)) if !rest.is_empty() => (last.temperature as f64, rest),
+                _ => continue,
+            },
+        };
+
+        for entry in predictions {
+            let lead_days = (entry.start_time - entry.generated_at).num_days();
+            let error = errors.entry(lead_days).or_insert((0, 0.));
+            error.0 += 1;
+            error.1 += (entry.temperature as f64 - verified).abs();
+        }
+    }
+
+    Ok(errors
+        .into_iter()
+        .map(|(lead_days, (samples, total))| LeadTimeError {
+            lead_days,
+            samples,
+            mean_absolute_error: total / samples as f64,
+        })
+        .collect())
+}
+
+/// Convert a temperature between the NWS unit codes `F` and `C`.
+fn convert(temperature: f64, from: &str, to: &str) -> f64 {
+    match (from, to) {
+        ("C", "F") => temperature * 9. / 5. + 32.,
+        ("F", "C") => (temperature - 32.) * 5. / 9.,
+        _ => temperature,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forecast_location() {
+        let gridpoint = "https://api.weather.gov/gridpoints/BGM/44,69";
+        assert_eq!(location(&format!("{}/forecast", gridpoint)), gridpoint);
+        assert_eq!(
+            location(&format!("{}/forecast/hourly", gridpoint)),
+            gridpoint
+        );
+        assert_eq!(location(gridpoint), gridpoint);
+    }
+
+    #[test]
+    fn convert_units() {
+        assert_eq!(convert(100., "C", "F"), 212.);
+        assert_eq!(convert(212., "F", "C"), 100.);
+        assert_eq!(convert(50., "F", "F"), 50.);
+    }
+}
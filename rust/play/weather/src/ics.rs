@@ -0,0 +1,336 @@
+use std::fmt::{self, Display};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use esbat::PrincipalPhase;
+use sunrise::sunrise_sunset;
+
+use crate::{forecast::Period, APP};
+
+/// An RFC 5545 calendar whose timed events are all in one time zone.
+#[derive(Debug)]
+pub struct Calendar {
+    tz: Tz,
+    stamp: DateTime<Utc>,
+    events: Vec<Event>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Event {
+    pub uid: String,
+    pub start: EventTime,
+    pub end: Option<EventTime>,
+    pub summary: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EventTime {
+    /// An all-day event date.
+    Date(NaiveDate),
+    /// A moment in the calendar's time zone.
+    DateTime(DateTime<Utc>),
+}
+
+impl Calendar {
+    /// Create an empty calendar, stamped with the time it was generated.
+    pub fn new(tz: Tz, stamp: DateTime<Utc>) -> Self {
+        Calendar {
+            tz,
+            stamp,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Add each forecast period as an all-day event on its local start date.
+    pub fn add_periods(&mut self, periods: &[Period]) {
+        for period in periods {
+            let date = period
+                .start_time
+                .with_timezone(&self.tz)
+                .date()
+                .naive_local();
+            self.push(Event {
+                uid: format!("{}-period-{}@{}", date.format("%Y%m%d"), period.number, APP),
+                start: EventTime::Date(date),
+                end: Some(EventTime::Date(date.succ())),
+                summary: format!(
+                    "{}: {}, {}°{}",
+                    period.name, period.short_forecast, period.temperature, period.temperature_unit
+                ),
+                description: Some(period.detailed_forecast.clone()),
+            });
+        }
+    }
+
+    /// Add sunrise and sunset at a location for every date in `dates`.
+    pub fn add_sun_times(&mut self, latitude: f64, longitude: f64, dates: &[NaiveDate]) {
+        for date in dates {
+            let (rise, set) =
+                sunrise_sunset(latitude, longitude, date.year(), date.month(), date.day());
+            for (name, time) in [("Sunrise", rise), ("Sunset", set)] {
+                let time = Utc.timestamp(time, 0);
+                self.push(Event {
+                    uid: format!("{}-{}@{}", time.format("%Y%m%dT%H%M%SZ"), name, APP),
+                    start: EventTime::DateTime(time),
+                    end: None,
+                    summary: name.to_string(),
+                    description: None,
+                });
+            }
+        }
+    }
+
+    /// Add the first full moon after `after`.
+    pub fn add_next_full_moon(&mut self, after: DateTime<Utc>) {
+        let next_full = esbat::lunar_phase_iter(after..)
+            .find(|x| x.0 == PrincipalPhase::FullMoon)
+            .map(|x| x.1);
+
+        if let Some(time) = next_full {
+            self.push(Event {
+                uid: format!("{}-full-moon@{}", time.format("%Y%m%dT%H%M%SZ"), APP),
+                start: EventTime::DateTime(time),
+                end: None,
+                summary: "Full Moon \u{1F315}".to_string(),
+                description: None,
+            });
+        }
+    }
+
+    /// Local dates with all-day events, for pairing with `add_sun_times`.
+    pub fn dates(&self) -> Vec<NaiveDate> {
+        let mut dates: Vec<NaiveDate> = self
+            .events
+            .iter()
+            .filter_map(|x| match x.start {
+                EventTime::Date(date) => Some(date),
+                EventTime::DateTime(_) => None,
+            })
+            .collect();
+        dates.dedup();
+        dates
+    }
+
+    /// Format a time as local time in the calendar's time zone.
+    fn local(&self, time: &DateTime<Utc>) -> String {
+        time.with_timezone(&self.tz)
+            .format("%Y%m%dT%H%M%S")
+            .to_string()
+    }
+
+    /// Write a VTIMEZONE describing every UTC offset in effect between the
+    /// first and last timed events.
+    fn write_timezone(&self, lines: &mut Vec<String>) {
+        let times: Vec<&DateTime<Utc>> = self
+            .events
+            .iter()
+            .filter_map(|x| match &x.start {
+                EventTime::DateTime(time) => Some(time),
+                EventTime::Date(_) => None,
+            })
+            .collect();
+        let (first, last) = match (times.iter().min(), times.iter().max()) {
+            (Some(first), Some(last)) => (**first - Duration::days(1), **last),
+            _ => return,
+        };
+
+        lines.push("BEGIN:VTIMEZONE".to_string());
+        lines.push(format!("TZID:{}", self.tz.name()));
+
+        // The offset half a year away tells whether the first is daylight time
+        let mut offset = self.offset(&first);
+        let daylight = offset > self.offset(&(first + Duration::days(182)));
+        let local = first.naive_utc() + Duration::seconds(offset as i64);
+        write_observance(lines, &local, daylight, offset, offset);
+
+        for transition in self.transitions(first, last) {
+            let next = self.offset(&transition);
+            let local = transition.naive_utc() + Duration::seconds(offset as i64);
+            write_observance(lines, &local, next > offset, offset, next);
+            offset = next;
+        }
+
+        lines.push("END:VTIMEZONE".to_string());
+    }
+
+    /// The calendar time zone's UTC offset in seconds at a moment.
+    fn offset(&self, time: &DateTime<Utc>) -> i32 {
+        self.tz
+            .offset_from_utc_datetime(&time.naive_utc())
+            .fix()
+            .local_minus_utc()
+    }
+
+    /// Moments when the UTC offset changes, to the minute, found by checking
+    /// each day from midnight UTC and bisecting the days where it changed.
+    fn transitions(&self, first: DateTime<Utc>, last: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        let mut transitions = Vec::new();
+        let mut day = first.date().and_hms(0, 0, 0);
+
+        while day < last {
+            let next = day + Duration::days(1);
+            if self.offset(&day) != self.offset(&next) {
+                let (mut before, mut after) = (0, 24 * 60);
+                while after - before > 1 {
+                    let middle = (before + after) / 2;
+                    match self.offset(&(day + Duration::minutes(middle))) == self.offset(&day) {
+                        true => before = middle,
+                        false => after = middle,
+                    }
+                }
+                transitions.push(day + Duration::minutes(after));
+            }
+            day = next;
+        }
+
+        transitions
+    }
+}
+
+impl Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            format!(
+                "PRODID:-//{}//{} {}//EN",
+                APP,
+                APP,
+                env!("CARGO_PKG_VERSION")
+            ),
+            "CALSCALE:GREGORIAN".to_string(),
+        ];
+        self.write_timezone(&mut lines);
+
+        let tzid = self.tz.name();
+        let stamp = self.stamp.format("%Y%m%dT%H%M%SZ");
+        for event in self.events.iter() {
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(format!("UID:{}", event.uid));
+            lines.push(format!("DTSTAMP:{}", stamp));
+            for (name, time) in [
+                ("DTSTART", Some(&event.start)),
+                ("DTEND", event.end.as_ref()),
+            ] {
+                match time {
+                    Some(EventTime::Date(date)) => {
+                        lines.push(format!("{};VALUE=DATE:{}", name, date.format("%Y%m%d")))
+                    }
+                    Some(EventTime::DateTime(time)) => {
+                        lines.push(format!("{};TZID={}:{}", name, tzid, self.local(time)))
+                    }
+                    None => {}
+                }
+            }
+            lines.push(format!("SUMMARY:{}", escape(&event.summary)));
+            if let Some(description) = &event.description {
+                lines.push(format!("DESCRIPTION:{}", escape(description)));
+            }
+            lines.push("END:VEVENT".to_string());
+        }
+        lines.push("END:VCALENDAR".to_string());
+
+        for line in lines {
+            f.write_str(&fold(&line))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_observance(
+    lines: &mut Vec<String>,
+    local_start: &NaiveDateTime,
+    daylight: bool,
+    from: i32,
+    to: i32,
+) {
+    let kind = match daylight {
+        true => "DAYLIGHT",
+        false => "STANDARD",
+    };
+    lines.push(format!("BEGIN:{}", kind));
+    lines.push(format!("DTSTART:{}", local_start.format("%Y%m%dT%H%M%S")));
+    lines.push(format!("TZOFFSETFROM:{}", utc_offset(from)));
+    lines.push(format!("TZOFFSETTO:{}", utc_offset(to)));
+    lines.push(format!("END:{}", kind));
+}
+
+/// Format a UTC offset in seconds as `+hhmm`.
+fn utc_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("{}{:02}{:02}", sign, minutes / 60, minutes % 60)
+}
+
+/// Escape TEXT property values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Fold a content line into CRLF-terminated lines of at most 75 octets,
+/// without splitting a UTF-8 character.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        width += c.len_utf8();
+        folded.push(c);
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_text() {
+        assert_eq!(
+            escape("Rain; wind, 5\\6\nmph"),
+            "Rain\\; wind\\, 5\\\\6\\nmph"
+        );
+    }
+
+    #[test]
+    fn fold_lines() {
+        let line = "DESCRIPTION:".to_string() + &"°".repeat(40);
+        let folded = fold(&line);
+        for part in folded.split("\r\n") {
+            assert!(part.len() <= 75);
+        }
+        assert_eq!(folded.replace("\r\n ", ""), line + "\r\n");
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(utc_offset(-4 * 3600), "-0400");
+        assert_eq!(utc_offset(5 * 3600 + 30 * 60), "+0530");
+    }
+}
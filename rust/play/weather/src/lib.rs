@@ -2,8 +2,14 @@ pub mod cache;
 pub mod client;
 pub mod config;
 pub mod error;
+pub mod fixture;
 pub mod forecast;
+pub mod history;
+pub mod ics;
 pub mod location;
+pub mod office;
+pub mod radar;
+pub mod rules;
 pub mod types;
 
 pub const APP: &str = "wthr";
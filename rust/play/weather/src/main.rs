@@ -1,18 +1,25 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fs, path::Path};
 
-use chrono::{Date, DateTime, Datelike, Local, TimeZone, Timelike, Utc};
+use chrono::{Date, DateTime, Datelike, Duration, FixedOffset, Local, TimeZone, Timelike, Utc};
 use chrono_tz::Tz;
 use colorgrad::{Color, CustomGradient};
-use crossterm::style::{self, Stylize};
+use crossterm::{
+    style::{self, Stylize},
+    terminal,
+};
 use esbat::{Phase::*, PrincipalPhase};
 use structopt::StructOpt;
 use sunrise::sunrise_sunset;
 use url::Url;
 
 use wthr::{
+    cache::Cache,
     client::ApiClient,
-    config::{Base, Config, Opt, Params, Resolution},
-    error::Result,
+    config::{Base, Command, Config, Export, Opt, Params, Resolution},
+    error::{err, Result},
+    history::lead_time_errors,
+    ics::Calendar,
+    rules::{notify, samples_from_gridpoint},
     APP, NWS_API,
 };
 
@@ -20,12 +27,183 @@ use wthr::{
 async fn main() -> Result<()> {
     let config = Config::load(Base::FromEnv).unwrap_or_default();
     let opt = Opt::from_args();
-    let params = Params::from_merge(&config, &opt)?;
 
-    let mut client = ApiClient::builder()
+    match opt.command {
+        Some(Command::History { at }) => history(at),
+        Some(Command::Notify) => notify_rules(&config, &opt).await,
+        Some(Command::Discuss) => discuss(&config, &opt).await,
+        Some(Command::Export(Export::Ics { ref output })) => {
+            export_ics(&config, &opt, output.as_deref()).await
+        }
+        None => forecast(&config, &opt).await,
+    }
+}
+
+/// Build a client for the NWS API, recording or replaying fixtures if asked.
+fn api_client(opt: &Opt, params: &Params) -> Result<ApiClient> {
+    let mut builder = ApiClient::builder()
         .base_url(NWS_API)
         .api_key(APP, &params.api_key)
-        .build()?;
+        .history(params.history);
+
+    if let Some(dir) = &opt.record {
+        builder = builder.record(dir.clone());
+    } else if let Some(dir) = &opt.replay {
+        builder = builder.replay(dir.clone());
+        if let Some(hours) = opt.time_shift {
+            builder = builder.time_shift(Duration::hours(hours));
+        }
+    }
+
+    builder.build()
+}
+
+/// Report recorded forecasts for one period and the accuracy of all
+/// recorded forecasts by lead time.
+fn history(at: Option<DateTime<FixedOffset>>) -> Result<()> {
+    let cache = Cache::new()?;
+
+    if let Some(at) = at {
+        println!(
+            "{}",
+            format!("Forecasts for {}", at.format("%A, %B %-e, %-l:%M %P, %Y")).blue()
+        );
+        for entry in cache.forecast_history(at.with_timezone(&Utc))? {
+            let lead = entry.start_time - entry.generated_at;
+            println!(
+                "{} {: >4}h {: >3}°{} {}",
+                Local
+                    .from_utc_datetime(&entry.generated_at.naive_utc())
+                    .format("%a %b %e %l:%M %P"),
+                lead.num_hours(),
+                entry.temperature,
+                entry.temperature_unit,
+                entry.short_forecast
+            );
+        }
+    }
+
+    let errors = lead_time_errors(&cache)?;
+    if errors.is_empty() {
+        println!(
+            "No forecast history recorded yet (set `history = true` in {}.toml)",
+            APP
+        );
+        return Ok(());
+    }
+
+    println!("{}", "Mean absolute temperature error:".blue());
+    for error in errors {
+        println!(
+            "Day {: >2} {: >5.1}° ({} forecasts)",
+            error.lead_days, error.mean_absolute_error, error.samples
+        );
+    }
+
+    Ok(())
+}
+
+/// Fire notifications for the rules in the config file that match the
+/// gridpoint forecast.
+async fn notify_rules(config: &Config, opt: &Opt) -> Result<()> {
+    let params = Params::from_merge(config, opt)?;
+
+    let mut client = api_client(opt, &params)?;
+
+    let point = client
+        .get_point(vec![params.latitude, params.longitude])
+        .await?;
+    let data = client
+        .get_gridpoint_data_from_url(&point.properties.forecast_grid_data)
+        .await?;
+    let samples = samples_from_gridpoint(&data)?;
+
+    let (_, errors) = notify(
+        client.cache_mut(),
+        &config.rules,
+        &point.properties.forecast_grid_data,
+        &samples,
+        Utc::now(),
+        &point.properties.time_zone,
+    );
+    for error in errors.iter() {
+        eprintln!("{:?}", error);
+    }
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => err(&format!("{} notification(s) failed", errors.len())),
+    }
+}
+
+/// Print the local office's latest Area Forecast Discussion, wrapped to the
+/// terminal width.
+async fn discuss(config: &Config, opt: &Opt) -> Result<()> {
+    let params = Params::from_merge(config, opt)?;
+
+    let mut client = api_client(opt, &params)?;
+
+    let point = client
+        .get_point(vec![params.latitude, params.longitude])
+        .await?;
+    let office = client
+        .get_office_from_url(&point.properties.forecast_office)
+        .await?;
+    let discussion = client
+        .get_forecast_discussion(&point.properties.cwa)
+        .await?;
+
+    let width = terminal::size().map(|x| x.0 as usize).unwrap_or(80);
+    println!(
+        "{}",
+        format!("{} from {}", discussion.product_name, office.name).blue()
+    );
+    println!(
+        "{}",
+        Local
+            .from_utc_datetime(&discussion.issuance_time.naive_utc())
+            .format("%A, %B %-e, %-l:%M %P, %Y")
+            .to_string()
+            .blue()
+    );
+    println!();
+    print!("{}", discussion.wrapped_text(width));
+
+    Ok(())
+}
+
+/// Write the daily forecast and astronomy events as an iCalendar file.
+async fn export_ics(config: &Config, opt: &Opt, output: Option<&Path>) -> Result<()> {
+    let params = Params::from_merge(config, opt)?;
+
+    let mut client = api_client(opt, &params)?;
+
+    let point = client
+        .get_point(vec![params.latitude, params.longitude])
+        .await?;
+    let forecast = client
+        .get_forecast_from_url(&point.properties.forecast)
+        .await?;
+
+    let mut calendar = Calendar::new(point.properties.time_zone, Utc::now());
+    calendar.add_periods(&forecast.properties.periods);
+    let dates = calendar.dates();
+    calendar.add_sun_times(params.latitude, params.longitude, &dates);
+    calendar.add_next_full_moon(Utc::now());
+
+    match output {
+        Some(path) => fs::write(path, calendar.to_string())?,
+        None => print!("{}", calendar),
+    }
+
+    Ok(())
+}
+
+/// Display the hourly or daily forecast with sun and moon times.
+async fn forecast(config: &Config, opt: &Opt) -> Result<()> {
+    let params = Params::from_merge(config, opt)?;
+
+    let mut client = api_client(opt, &params)?;
 
     // Find the weather station gridpoint for the location
     let point = client
@@ -0,0 +1,158 @@
+use chrono::{offset::FixedOffset, DateTime};
+use serde::Deserialize;
+
+use crate::types::Url;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Office {
+    #[serde(rename = "@id")]
+    pub url: Url,
+    pub id: String,
+    pub name: String,
+    pub address: Address,
+    pub telephone: String,
+    pub fax_number: Option<String>,
+    pub email: Option<String>,
+    pub same_as: Option<Url>,
+    pub nws_region: String,
+    pub parent_organization: Option<Url>,
+    #[serde(default)]
+    pub responsible_counties: Vec<Url>,
+    #[serde(default)]
+    pub responsible_forecast_zones: Vec<Url>,
+    #[serde(default)]
+    pub responsible_fire_zones: Vec<Url>,
+    #[serde(default)]
+    pub approved_observation_stations: Vec<Url>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Address {
+    pub street_address: String,
+    pub address_locality: String,
+    pub address_region: String,
+    pub postal_code: String,
+}
+
+/// A list of text products, newest first.
+#[derive(Debug, Deserialize)]
+pub struct ProductList {
+    #[serde(rename = "@graph")]
+    pub products: Vec<ProductSummary>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductSummary {
+    #[serde(rename = "@id")]
+    pub url: Url,
+    pub id: String,
+    pub wmo_collective_id: String,
+    pub issuing_office: String,
+    pub issuance_time: DateTime<FixedOffset>,
+    pub product_code: String,
+    pub product_name: String,
+}
+
+/// A text product, such as an Area Forecast Discussion.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Product {
+    #[serde(rename = "@id")]
+    pub url: Url,
+    pub id: String,
+    pub wmo_collective_id: String,
+    pub issuing_office: String,
+    pub issuance_time: DateTime<FixedOffset>,
+    pub product_code: String,
+    pub product_name: String,
+    pub product_text: String,
+}
+
+impl Product {
+    /// The product text reflowed into paragraphs no wider than `width`.
+    pub fn wrapped_text(&self, width: usize) -> String {
+        wrap(&self.product_text, width)
+    }
+}
+
+/// Reflow text that was hard-wrapped at a fixed width.  Blank lines,
+/// separators (`&&`, `$$`) and lines much shorter than the original width,
+/// such as headers and the ends of paragraphs, keep their line breaks.
+pub fn wrap(text: &str, width: usize) -> String {
+    let width = width.max(1);
+    let original_width = text.lines().map(|x| x.trim_end().len()).max().unwrap_or(0);
+    let mut wrapped = String::new();
+    let mut line_len = 0;
+
+    for line in text.trim().lines() {
+        let line = line.trim_end();
+        if line.is_empty() || line == "&&" || line == "$$" {
+            if line_len > 0 {
+                wrapped.push('\n');
+                line_len = 0;
+            }
+            wrapped.push_str(line);
+            wrapped.push('\n');
+            continue;
+        }
+
+        for word in line.split_whitespace() {
+            let word_len = word.chars().count();
+            if line_len > 0 && line_len + 1 + word_len > width {
+                wrapped.push('\n');
+                line_len = 0;
+            }
+            if line_len > 0 {
+                wrapped.push(' ');
+                line_len += 1;
+            }
+            wrapped.push_str(word);
+            line_len += word_len;
+        }
+
+        if line.len() < original_width / 2 {
+            wrapped.push('\n');
+            line_len = 0;
+        }
+    }
+    if line_len > 0 {
+        wrapped.push('\n');
+    }
+
+    wrapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reflow_paragraphs() {
+        let text = "\
+AFDBGM
+
+.SYNOPSIS...
+A weak cold front will bring scattered
+showers tonight, then high pressure
+returns.
+
+&&
+";
+        assert_eq!(
+            wrap(text, 30),
+            "\
+AFDBGM
+
+.SYNOPSIS...
+A weak cold front will bring
+scattered showers tonight,
+then high pressure returns.
+
+&&
+"
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use chrono_tz::Tz;
+use geojson::Geometry;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::types::{Quantity, Url};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RadarStation {
+    pub id: Url,
+    pub geometry: Geometry,
+    pub properties: RadarStationProperties,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RadarStationProperties {
+    #[serde(rename = "@id")]
+    pub url: Url,
+    pub id: String,
+    pub name: String,
+    pub station_type: String,
+    pub elevation: Quantity,
+    pub time_zone: Tz,
+    pub latency: Option<Value>,
+    pub rda: Option<Value>,
+    pub performance: Option<Value>,
+    pub adaptation: Option<Value>,
+}
+
+impl RadarStation {
+    /// Latitude and longitude of the radar site.
+    pub fn position(&self) -> Option<(f64, f64)> {
+        match &self.geometry.value {
+            geojson::Value::Point(point) if point.len() >= 2 => Some((point[1], point[0])),
+            _ => None,
+        }
+    }
+}
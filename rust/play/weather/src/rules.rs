@@ -0,0 +1,397 @@
+use std::process;
+
+use chrono::{DateTime, Duration, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cache::Cache,
+    error::{err, Error, Result},
+    forecast::{GridpointData, Period, TimeSeries},
+    types::parse_time_interval,
+};
+
+/// A forecast quantity that rules can test.  Values are compared in US
+/// customary units: °F, mph, percent and inches.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Field {
+    Temperature,
+    WindSpeed,
+    WindGust,
+    ProbabilityOfPrecipitation,
+    QuantitativePrecipitation,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Comparison {
+    #[serde(rename = "<")]
+    Below,
+    #[serde(rename = "<=")]
+    AtMost,
+    #[serde(rename = ">")]
+    Above,
+    #[serde(rename = ">=")]
+    AtLeast,
+}
+
+/// A notification rule from a `[[rules]]` table in the config file, e.g.
+///
+/// ```toml
+/// [[rules]]
+/// name = "frost"
+/// field = "temperature"
+/// op = "<"
+/// value = 33
+/// within_hours = 24
+/// command = "notify-send 'Frost tonight'"
+/// ```
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Rule {
+    pub name: String,
+    pub field: Field,
+    pub op: Comparison,
+    pub value: f64,
+    /// How far ahead of now to look.
+    #[serde(default = "default_within_hours")]
+    pub within_hours: u32,
+    /// Only consider local hours in `[start, end)`, e.g. `[7, 9]`.
+    pub between: Option<(u32, u32)>,
+    /// Shell command to run when the rule fires, instead of printing to
+    /// stdout.  `WTHR_RULE`, `WTHR_TIME` and `WTHR_VALUE` describe the event.
+    pub command: Option<String>,
+}
+
+fn default_within_hours() -> u32 {
+    24
+}
+
+/// One forecast value for a field, valid over `[start_time, end_time)`.
+#[derive(Debug, PartialEq)]
+pub struct Sample {
+    pub field: Field,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub value: f64,
+}
+
+/// A run of consecutive samples that satisfy a rule.
+#[derive(Debug, PartialEq)]
+pub struct Event<'a> {
+    pub rule: &'a Rule,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// The most extreme value in the run.
+    pub value: f64,
+}
+
+impl Comparison {
+    pub fn test(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Below => value < threshold,
+            Comparison::AtMost => value <= threshold,
+            Comparison::Above => value > threshold,
+            Comparison::AtLeast => value >= threshold,
+        }
+    }
+
+    fn most_extreme(&self, a: f64, b: f64) -> f64 {
+        match self {
+            Comparison::Below | Comparison::AtMost => a.min(b),
+            Comparison::Above | Comparison::AtLeast => a.max(b),
+        }
+    }
+}
+
+impl Rule {
+    /// Find the first run of samples, starting within the rule's time window,
+    /// that satisfy the rule.  Samples must be sorted by start time.
+    pub fn evaluate<'a, Tz: TimeZone>(
+        &'a self,
+        samples: &[Sample],
+        now: DateTime<Utc>,
+        tz: &Tz,
+    ) -> Option<Event<'a>> {
+        let horizon = now + Duration::hours(self.within_hours as i64);
+        let mut event: Option<Event> = None;
+
+        for sample in samples.iter().filter(|x| x.field == self.field) {
+            let in_window = sample.start_time < horizon
+                && sample.end_time > now
+                && self.in_hours(sample, now, horizon, tz);
+            let matched = in_window && self.op.test(sample.value, self.value);
+
+            match (event.as_mut(), matched) {
+                (None, true) => {
+                    event = Some(Event {
+                        rule: self,
+                        start_time: sample.start_time,
+                        end_time: sample.end_time,
+                        value: sample.value,
+                    })
+                }
+                (Some(e), true) if sample.start_time <= e.end_time => {
+                    e.end_time = e.end_time.max(sample.end_time);
+                    e.value = self.op.most_extreme(e.value, sample.value);
+                }
+                (Some(_), _) => break,
+                (None, false) => {}
+            }
+        }
+
+        event
+    }
+
+    /// Whether any hour of the sample within `[now, horizon)` falls in the
+    /// rule's local `between` hours.
+    fn in_hours<Tz: TimeZone>(
+        &self,
+        sample: &Sample,
+        now: DateTime<Utc>,
+        horizon: DateTime<Utc>,
+        tz: &Tz,
+    ) -> bool {
+        let (first, last) = match self.between {
+            Some(hours) => hours,
+            None => return true,
+        };
+
+        let mut time = sample.start_time.max(now);
+        let end = sample.end_time.min(horizon);
+        while time < end {
+            let hour = time.with_timezone(tz).hour();
+            if hour >= first && hour < last {
+                return true;
+            }
+            time = time + Duration::hours(1);
+        }
+
+        false
+    }
+}
+
+impl Event<'_> {
+    /// Run the rule's command, or print the event if it has none.
+    pub fn fire(&self) -> Result<()> {
+        let time = self.start_time.to_rfc3339();
+        let value = format!("{}", self.value);
+
+        match &self.rule.command {
+            Some(command) => {
+                let status = process::Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .env("WTHR_RULE", &self.rule.name)
+                    .env("WTHR_TIME", &time)
+                    .env("WTHR_VALUE", &value)
+                    .status()?;
+                if !status.success() {
+                    return err(&format!("Command for rule '{}' failed", self.rule.name));
+                }
+            }
+            None => println!("{}: {} at {}", self.rule.name, value, time),
+        }
+
+        Ok(())
+    }
+}
+
+/// Evaluate every rule for a location, such as a gridpoint url, and fire the
+/// events that haven't fired there before.  An event is only recorded once it
+/// has fired, so one whose command fails fires again next time.  Returns the
+/// events that fired and the errors of any that couldn't, having tried every
+/// rule.
+pub fn notify<'a, Tz: TimeZone>(
+    cache: &mut Cache,
+    rules: &'a [Rule],
+    location: &str,
+    samples: &[Sample],
+    now: DateTime<Utc>,
+    tz: &Tz,
+) -> (Vec<Event<'a>>, Vec<Error>) {
+    let mut fired = Vec::new();
+    let mut errors = Vec::new();
+
+    for rule in rules {
+        if let Some(event) = rule.evaluate(samples, now, tz) {
+            let (name, start, end) = (&rule.name, event.start_time, event.end_time);
+            match cache.notification_fired(name, location, start, end) {
+                Ok(true) => {}
+                Ok(false) => match event
+                    .fire()
+                    .and_then(|_| cache.insert_notification(name, location, start, end))
+                {
+                    Ok(()) => fired.push(event),
+                    Err(error) => errors.push(error),
+                },
+                Err(error) => errors.push(error),
+            }
+        }
+    }
+
+    (fired, errors)
+}
+
+/// Samples from the periods of a daily or hourly forecast.
+pub fn samples_from_periods(periods: &[Period]) -> Vec<Sample> {
+    let mut samples = Vec::new();
+
+    for period in periods {
+        let start_time = period.start_time.with_timezone(&Utc);
+        let end_time = period.end_time.with_timezone(&Utc);
+        let temperature = match period.temperature_unit.as_str() {
+            "C" => period.temperature as f64 * 9. / 5. + 32.,
+            _ => period.temperature as f64,
+        };
+        samples.push(Sample {
+            field: Field::Temperature,
+            start_time,
+            end_time,
+            value: temperature,
+        });
+
+        // Wind speed is text such as "10 mph" or "2 to 6 mph"
+        let speed = period
+            .wind_speed
+            .split_whitespace()
+            .filter_map(|x| x.parse::<f64>().ok())
+            .fold(None, |max: Option<f64>, x| {
+                Some(max.map_or(x, |m| m.max(x)))
+            });
+        if let Some(speed) = speed {
+            let speed = match period.wind_speed.ends_with("km/h") {
+                true => speed / KM_PER_MILE,
+                false => speed,
+            };
+            samples.push(Sample {
+                field: Field::WindSpeed,
+                start_time,
+                end_time,
+                value: speed,
+            });
+        }
+    }
+
+    samples.sort_by_key(|x| x.start_time);
+    samples
+}
+
+/// Samples from raw gridpoint data, converted from the units in each series.
+pub fn samples_from_gridpoint(data: &GridpointData) -> Result<Vec<Sample>> {
+    let properties = &data.properties;
+    let mut samples = Vec::new();
+
+    push_series(&mut samples, Field::Temperature, &properties.temperature)?;
+    push_series(&mut samples, Field::WindSpeed, &properties.wind_speed)?;
+    push_series(&mut samples, Field::WindGust, &properties.wind_gust)?;
+    push_series(
+        &mut samples,
+        Field::QuantitativePrecipitation,
+        &properties.quantitative_precipitation,
+    )?;
+
+    let pop = &properties.probability_of_precipitation;
+    for datum in pop.values.iter() {
+        if let (Some(interval), Some(value)) = (&datum.valid_time, datum.value) {
+            let (start, duration) = parse_time_interval(interval)?;
+            let start_time = start.with_timezone(&Utc);
+            samples.push(Sample {
+                field: Field::ProbabilityOfPrecipitation,
+                start_time,
+                end_time: start_time + duration,
+                value: value as f64,
+            });
+        }
+    }
+
+    samples.sort_by_key(|x| x.start_time);
+    Ok(samples)
+}
+
+const KM_PER_MILE: f64 = 1.609344;
+
+fn push_series(samples: &mut Vec<Sample>, field: Field, series: &TimeSeries<f64>) -> Result<()> {
+    let convert = match series.uom.as_deref() {
+        Some("wmoUnit:degC") => |x: f64| x * 9. / 5. + 32.,
+        Some("wmoUnit:km_h-1") => |x: f64| x / KM_PER_MILE,
+        Some("wmoUnit:m_s-1") => |x: f64| x * 3600. / 1000. / KM_PER_MILE,
+        Some("wmoUnit:mm") => |x: f64| x / 25.4,
+        _ => |x: f64| x,
+    };
+
+    for datum in series.values.iter() {
+        if let (Some(interval), Some(value)) = (&datum.valid_time, datum.value) {
+            let (start, duration) = parse_time_interval(interval)?;
+            let start_time = start.with_timezone(&Utc);
+            samples.push(Sample {
+                field,
+                start_time,
+                end_time: start_time + duration,
+                value: convert(value),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(hour: u32, hours: i64, value: f64) -> Sample {
+        let start_time = Utc.ymd(2021, 6, 12).and_hms(hour, 0, 0);
+        Sample {
+            field: Field::Temperature,
+            start_time,
+            end_time: start_time + Duration::hours(hours),
+            value,
+        }
+    }
+
+    fn frost() -> Rule {
+        Rule {
+            name: "frost".to_string(),
+            field: Field::Temperature,
+            op: Comparison::Below,
+            value: 33.,
+            within_hours: 12,
+            between: None,
+            command: None,
+        }
+    }
+
+    #[test]
+    fn consecutive_samples_make_one_event() {
+        let now = Utc.ymd(2021, 6, 12).and_hms(0, 30, 0);
+        let samples = vec![
+            sample(0, 1, 35.),
+            sample(1, 1, 32.),
+            sample(2, 2, 30.),
+            sample(4, 1, 34.),
+            sample(5, 1, 31.),
+        ];
+        let rule = frost();
+        let event = rule.evaluate(&samples, now, &Utc).unwrap();
+
+        assert_eq!(event.start_time, samples[1].start_time);
+        assert_eq!(event.end_time, samples[2].end_time);
+        assert_eq!(event.value, 30.);
+    }
+
+    #[test]
+    fn window_limits_matches() {
+        let now = Utc.ymd(2021, 6, 12).and_hms(0, 0, 0);
+        let samples = vec![sample(0, 6, 40.), sample(6, 6, 20.)];
+        let mut rule = frost();
+
+        rule.within_hours = 6;
+        assert_eq!(rule.evaluate(&samples, now, &Utc), None);
+
+        rule.within_hours = 12;
+        rule.between = Some((7, 9));
+        assert!(rule.evaluate(&samples, now, &Utc).is_some());
+
+        rule.between = Some((13, 15));
+        assert_eq!(rule.evaluate(&samples, now, &Utc), None);
+    }
+}
@@ -1,5 +1,8 @@
+use chrono::{DateTime, Duration, FixedOffset};
 use serde::Deserialize;
 
+use crate::error::{err, Result};
+
 pub type Angle = u32;
 pub type TimeInterval = String;
 pub type Percent = u8;
@@ -12,3 +15,85 @@ pub struct Quantity {
     pub value: Option<f64>,
     pub unit_code: String,
 }
+
+/// Split an ISO 8601 `TimeInterval` such as
+/// `2021-06-10T14:00:00+00:00/PT2H` into its start time and duration.
+pub fn parse_time_interval(interval: &str) -> Result<(DateTime<FixedOffset>, Duration)> {
+    let (start, duration) = match interval.split_once('/') {
+        Some(parts) => parts,
+        None => return err(&format!("Invalid time interval: {}", interval)),
+    };
+    let start = match DateTime::parse_from_rfc3339(start) {
+        Ok(start) => start,
+        Err(_) => return err(&format!("Invalid time interval: {}", interval)),
+    };
+
+    Ok((start, parse_duration(duration)?))
+}
+
+/// Parse an ISO 8601 duration made of days, hours, minutes and seconds,
+/// such as `P1DT6H`.
+pub fn parse_duration(duration: &str) -> Result<Duration> {
+    let invalid = || err(&format!("Invalid duration: {}", duration));
+    let mut chars = match duration.strip_prefix('P') {
+        Some(rest) if !rest.is_empty() => rest.chars(),
+        _ => return invalid(),
+    };
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in &mut chars {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' if !in_time && number.is_empty() => in_time = true,
+            _ => {
+                let n: i64 = match number.parse() {
+                    Ok(n) => n,
+                    Err(_) => return invalid(),
+                };
+                total = total
+                    + match (c, in_time) {
+                        ('W', false) => Duration::weeks(n),
+                        ('D', false) => Duration::days(n),
+                        ('H', true) => Duration::hours(n),
+                        ('M', true) => Duration::minutes(n),
+                        ('S', true) => Duration::seconds(n),
+                        _ => return invalid(),
+                    };
+                number.clear();
+            }
+        }
+    }
+
+    match number.is_empty() {
+        true => Ok(total),
+        false => invalid(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("PT1H").unwrap(), Duration::hours(1));
+        assert_eq!(parse_duration("P8D").unwrap(), Duration::days(8));
+        assert_eq!(
+            parse_duration("P7DT20H").unwrap(),
+            Duration::days(7) + Duration::hours(20)
+        );
+        assert!(parse_duration("P").is_err());
+        assert!(parse_duration("P1H").is_err());
+        assert!(parse_duration("PT12").is_err());
+    }
+
+    #[test]
+    fn time_intervals() {
+        let (start, duration) = parse_time_interval("2021-06-10T14:00:00+00:00/PT2H").unwrap();
+        assert_eq!(start.to_rfc3339(), "2021-06-10T14:00:00+00:00");
+        assert_eq!(duration, Duration::hours(2));
+        assert!(parse_time_interval("2021-06-10T14:00:00+00:00").is_err());
+    }
+}
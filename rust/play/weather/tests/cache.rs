@@ -1,13 +1,35 @@
-#![allow(unused_imports)]
+use chrono::{DateTime, Duration, Utc};
 use wiremock::{
     matchers::{method, path},
     Mock, MockServer, ResponseTemplate,
 };
 
-use wthr::{cache::Cache, client::{round_fmt, ApiClient}};
+use wthr::{
+    cache::CacheStats,
+    client::ApiClient,
+    fixture::{file_name, Fixture},
+};
 
 mod common;
-use common::{json, tempcache, API, APP, USER};
+use common::{fixtures, json, APP, USER};
+
+const LATITUDE: f64 = 42.44645644561855;
+const LONGITUDE: f64 = -76.4807390759812;
+
+/// How far to shift the fixtures for them to have been recorded `ago`.
+fn recorded(ago: Duration) -> Duration {
+    let url = "https://unreachable.invalid/points/42.4465,-76.4807";
+    let recorded_at: DateTime<Utc> = Fixture::load(&fixtures(), url).unwrap().recorded_at;
+    Utc::now() - ago - recorded_at
+}
+
+fn stats(hits: u32, misses: u32, revalidations: u32) -> CacheStats {
+    CacheStats {
+        hits,
+        misses,
+        revalidations,
+    }
+}
 
 #[tokio::test]
 async fn fetch_from_cache() {
@@ -15,13 +37,120 @@ async fn fetch_from_cache() {
 
     let response = ResponseTemplate::new(200)
         .set_body_string(json("get_point"))
-        .insert_header("content-type", "application/geo+json");
+        .insert_header("content-type", "application/geo+json")
+        .insert_header("cache-control", "public, max-age=600");
 
     Mock::given(method("GET"))
         .and(path("/points/42.4465,-76.4807"))
         .respond_with(response)
+        .expect(2)
         .mount(&server)
         .await;
 
-    let (cache, tempdir) = tempcache();
+    let cache_dir = tempfile::tempdir().unwrap();
+    let fixture_dir = tempfile::tempdir().unwrap();
+    let mut client = ApiClient::builder()
+        .base_url(&server.uri())
+        .api_key(APP, USER)
+        .cache_base_dir(cache_dir.path().to_path_buf())
+        .record(fixture_dir.path().to_path_buf())
+        .build()
+        .unwrap();
+
+    client.get_point(vec![LATITUDE, LONGITUDE]).await.unwrap();
+    assert_eq!(client.stats(), stats(0, 1, 0));
+
+    // Fresh for ten minutes
+    client.get_point(vec![LATITUDE, LONGITUDE]).await.unwrap();
+    client.advance_clock(Duration::minutes(9));
+    client.get_point(vec![LATITUDE, LONGITUDE]).await.unwrap();
+    assert_eq!(client.stats(), stats(2, 1, 0));
+
+    client.advance_clock(Duration::minutes(1));
+    client.get_point(vec![LATITUDE, LONGITUDE]).await.unwrap();
+    assert_eq!(client.stats(), stats(2, 1, 1));
+
+    // The response was recorded with its headers
+    let url = format!("{}/points/42.4465,-76.4807", server.uri());
+    let fixture = Fixture::load(fixture_dir.path(), &url).unwrap();
+    assert_eq!(fixture.status, 200);
+    assert_eq!(fixture.header("Cache-Control"), Some("public, max-age=600"));
+    assert_eq!(fixture.body, json("get_point"));
+    assert!(fixture_dir.path().join(file_name(&url)).exists());
+}
+
+#[tokio::test]
+async fn replay_fixtures() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let mut client = ApiClient::builder()
+        .base_url("https://unreachable.invalid")
+        .api_key(APP, USER)
+        .cache_base_dir(cache_dir.path().to_path_buf())
+        .replay(fixtures())
+        .time_shift(recorded(Duration::zero()))
+        .build()
+        .unwrap();
+
+    let point = client.get_point(vec![LATITUDE, LONGITUDE]).await.unwrap();
+    let forecast = client
+        .get_forecast_from_url(&point.properties.forecast)
+        .await
+        .unwrap();
+    assert_eq!(forecast.properties.periods[0].name, "Tonight");
+    assert_eq!(client.stats(), stats(0, 2, 0));
+
+    // The forecast expires after an hour, the point after a day
+    client.advance_clock(Duration::hours(2));
+    client.get_point(vec![LATITUDE, LONGITUDE]).await.unwrap();
+    client
+        .get_forecast_from_url(&point.properties.forecast)
+        .await
+        .unwrap();
+    assert_eq!(client.stats(), stats(1, 2, 1));
+
+    // Unrecorded resources are an error, not a network request
+    assert!(client.get_office("BGM").await.is_err());
+    assert_eq!(client.stats(), stats(1, 3, 1));
+}
+
+#[tokio::test]
+async fn replay_with_time_shift() {
+    // Unshifted, fixtures are cached as of when they were recorded, long ago
+    let cache_dir = tempfile::tempdir().unwrap();
+    let mut client = ApiClient::builder()
+        .base_url("https://unreachable.invalid")
+        .api_key(APP, USER)
+        .cache_base_dir(cache_dir.path().to_path_buf())
+        .replay(fixtures())
+        .build()
+        .unwrap();
+
+    client.get_point(vec![LATITUDE, LONGITUDE]).await.unwrap();
+    client.get_point(vec![LATITUDE, LONGITUDE]).await.unwrap();
+    assert_eq!(client.stats(), stats(0, 1, 1));
+
+    // Recorded twelve hours ago, the point is still fresh but not the forecast
+    let cache_dir = tempfile::tempdir().unwrap();
+    let mut client = ApiClient::builder()
+        .base_url("https://unreachable.invalid")
+        .api_key(APP, USER)
+        .cache_base_dir(cache_dir.path().to_path_buf())
+        .replay(fixtures())
+        .time_shift(recorded(Duration::hours(12)))
+        .build()
+        .unwrap();
+
+    let point = client.get_point(vec![LATITUDE, LONGITUDE]).await.unwrap();
+    client.get_point(vec![LATITUDE, LONGITUDE]).await.unwrap();
+    assert_eq!(client.stats(), stats(1, 1, 0));
+
+    client
+        .get_forecast_from_url(&point.properties.forecast)
+        .await
+        .unwrap();
+    client
+        .get_forecast_from_url(&point.properties.forecast)
+        .await
+        .unwrap();
+    assert_eq!(client.stats(), stats(1, 2, 1));
 }
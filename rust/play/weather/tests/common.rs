@@ -1,6 +1,8 @@
+use std::path::PathBuf;
+
 use tempfile::{tempdir, TempDir};
 
-use wthr::cache::Cache;
+use wthr::cache::{Cache, DB_VERSION};
 
 #[allow(dead_code)]
 pub const API: &str = "https://mock.api";
@@ -13,13 +15,21 @@ pub const USER: &str = "user@test.app";
 pub fn tempcache() -> (Cache, TempDir) {
     let temp_dir = tempdir().unwrap();
 
-    // Cache should create new database with version = 1
+    // Cache should create new database with the latest version
     let cache = Cache::with_base_dir(Some(temp_dir.path().to_path_buf())).unwrap();
-    assert_eq!(cache.db_version().unwrap(), 1);
+    assert_eq!(cache.db_version().unwrap(), DB_VERSION);
 
     (cache, temp_dir)
 }
 
+/// The directory of recorded API responses for replay.
+#[allow(dead_code)]
+pub fn fixtures() -> PathBuf {
+    PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("tests")
+        .join("fixtures")
+}
+
 pub fn json(file: &str) -> String {
     let path = format!(
         "{}/tests/json/{}.json",
@@ -0,0 +1,110 @@
+use chrono::{Duration, TimeZone, Utc};
+
+use wthr::{forecast::Forecast, history::lead_time_errors};
+
+mod common;
+use common::{json, tempcache};
+
+const FORECAST: &str = "https://api.weather.gov/gridpoints/BGM/44,69/forecast";
+
+#[test]
+fn record_distinct_snapshots() {
+    let (mut cache, _tempdir) = tempcache();
+    let mut forecast: Forecast = serde_json::from_str(&json("forecast")).unwrap();
+
+    assert!(cache.insert_forecast(FORECAST, &forecast).unwrap());
+    assert!(!cache.insert_forecast(FORECAST, &forecast).unwrap());
+
+    // A later snapshot predicts the same periods a little warmer
+    forecast.properties.generated_at = forecast.properties.generated_at + Duration::hours(6);
+    for period in forecast.properties.periods.iter_mut() {
+        period.temperature += 2;
+    }
+    assert!(cache.insert_forecast(FORECAST, &forecast).unwrap());
+
+    let saturday = Utc.ymd(2021, 6, 12).and_hms(15, 0, 0);
+    let history = cache.forecast_history(saturday).unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].temperature, 78);
+    assert_eq!(history[1].temperature, 80);
+    assert!(history[0].generated_at < history[1].generated_at);
+}
+
+#[test]
+fn errors_by_lead_time() {
+    let (mut cache, _tempdir) = tempcache();
+    let mut forecast: Forecast = serde_json::from_str(&json("forecast")).unwrap();
+    cache.insert_forecast(FORECAST, &forecast).unwrap();
+
+    // Without observations the latest forecast verifies the earlier ones
+    forecast.properties.generated_at = forecast.properties.generated_at + Duration::hours(6);
+    for period in forecast.properties.periods.iter_mut() {
+        period.temperature += 3;
+    }
+    cache.insert_forecast(FORECAST, &forecast).unwrap();
+
+    let errors = lead_time_errors(&cache).unwrap();
+    assert!(!errors.is_empty());
+    for error in errors.iter() {
+        assert_eq!(error.mean_absolute_error, 3.);
+    }
+    let day_zero = errors.iter().find(|x| x.lead_days == 0).unwrap();
+    assert_eq!(day_zero.samples, 2);
+
+    // An observation verifies both forecasts for Saturday (78°F and 81°F)
+    let saturday = Utc.ymd(2021, 6, 12).and_hms(15, 0, 0);
+    let gridpoint = "https://api.weather.gov/gridpoints/BGM/44,69";
+    cache
+        .insert_observation(
+            "https://api.weather.gov/gridpoints/OKX/33,35",
+            saturday,
+            0.,
+            "C",
+        )
+        .unwrap();
+    let errors = lead_time_errors(&cache).unwrap();
+    let day_zero = errors.iter().find(|x| x.lead_days == 0).unwrap();
+    assert_eq!(day_zero.samples, 2);
+    cache
+        .insert_observation(gridpoint, saturday, 25., "C")
+        .unwrap();
+    let errors = lead_time_errors(&cache).unwrap();
+    let day_zero = errors.iter().find(|x| x.lead_days == 0).unwrap();
+    assert_eq!(day_zero.samples, 3);
+    assert_eq!(day_zero.mean_absolute_error, (3. + 1. + 4.) / 3.);
+}
+
+#[test]
+fn separate_forecasts_for_the_same_period() {
+    let (mut cache, _tempdir) = tempcache();
+    let mut forecast: Forecast = serde_json::from_str(&json("forecast")).unwrap();
+    let elsewhere = "https://api.weather.gov/gridpoints/OKX/33,35/forecast";
+
+    // Each place has two snapshots, the later three degrees warmer here and
+    // ten degrees warmer there, recorded in between each other
+    cache.insert_forecast(FORECAST, &forecast).unwrap();
+    for period in forecast.properties.periods.iter_mut() {
+        period.temperature += 20;
+    }
+    cache.insert_forecast(elsewhere, &forecast).unwrap();
+    forecast.properties.generated_at = forecast.properties.generated_at + Duration::hours(6);
+    for period in forecast.properties.periods.iter_mut() {
+        period.temperature -= 17;
+    }
+    cache.insert_forecast(FORECAST, &forecast).unwrap();
+    for period in forecast.properties.periods.iter_mut() {
+        period.temperature += 27;
+    }
+    cache.insert_forecast(elsewhere, &forecast).unwrap();
+
+    let errors = lead_time_errors(&cache).unwrap();
+    let day_zero = errors.iter().find(|x| x.lead_days == 0).unwrap();
+    assert_eq!(day_zero.samples, 4);
+    assert_eq!(day_zero.mean_absolute_error, (3. + 3. + 10. + 10.) / 4.);
+
+    let saturday = Utc.ymd(2021, 6, 12).and_hms(15, 0, 0);
+    let history = cache.forecast_history(saturday).unwrap();
+    assert_eq!(history.len(), 4);
+    assert_eq!(history[0].url, FORECAST);
+    assert_eq!(history[2].url, elsewhere);
+}
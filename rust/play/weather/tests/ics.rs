@@ -0,0 +1,45 @@
+use chrono::{TimeZone, Utc};
+use chrono_tz::America::New_York;
+
+use wthr::{forecast::Forecast, ics::Calendar};
+
+mod common;
+use common::json;
+
+#[test]
+fn export_forecast() {
+    let forecast: Forecast = serde_json::from_str(&json("forecast")).unwrap();
+    let mut calendar = Calendar::new(New_York, Utc.ymd(2021, 6, 12).and_hms(1, 0, 0));
+    calendar.add_periods(&forecast.properties.periods);
+    let dates = calendar.dates();
+    calendar.add_sun_times(42.4465, -76.4807, &dates);
+    calendar.add_next_full_moon(Utc.ymd(2021, 6, 12).and_hms(1, 0, 0));
+
+    let periods = forecast.properties.periods.len();
+    assert_eq!(
+        dates.first(),
+        Some(&New_York.ymd(2021, 6, 11).naive_local())
+    );
+    assert_eq!(calendar.events().len(), periods + 2 * dates.len() + 1);
+
+    let ics = calendar.to_string();
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(ics.matches("BEGIN:VEVENT").count(), calendar.events().len());
+    assert!(ics.contains("DTSTAMP:20210612T010000Z\r\n"));
+
+    // Periods are all-day events
+    assert!(ics.contains("DTSTART;VALUE=DATE:20210611\r\nDTEND;VALUE=DATE:20210612\r\n"));
+    assert!(ics.contains("SUMMARY:Tonight: Chance Rain Showers\\, 59°F\r\n"));
+
+    // Timed events use the location's time zone, which is defined once
+    assert_eq!(ics.matches("BEGIN:VTIMEZONE").count(), 1);
+    assert!(ics.contains("TZID:America/New_York\r\nBEGIN:DAYLIGHT\r\n"));
+    assert!(ics.contains("TZOFFSETTO:-0400\r\n"));
+    assert!(ics.contains("DTSTART;TZID=America/New_York:20210612T0528"));
+    assert!(ics.contains("DTSTART;TZID=America/New_York:20210624T"));
+
+    for line in ics.split("\r\n") {
+        assert!(line.len() <= 75);
+    }
+}
@@ -0,0 +1,82 @@
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+use wthr::client::ApiClient;
+
+mod common;
+use common::{json, API, APP, USER};
+
+/// Serve a fixture, with the urls in it pointed at the mock server.
+async fn mount(server: &MockServer, url_path: &str, file: &str) {
+    let response = ResponseTemplate::new(200)
+        .set_body_string(json(file).replace(API, &server.uri()))
+        .insert_header("content-type", "application/geo+json");
+
+    Mock::given(method("GET"))
+        .and(path(url_path))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn office_and_radar() {
+    let mock_server = MockServer::start().await;
+    mount(&mock_server, "/offices/BGM", "office").await;
+    mount(&mock_server, "/radar/stations/KBGM", "radar_station").await;
+
+    let mut client = ApiClient::builder()
+        .base_url(&mock_server.uri())
+        .api_key(APP, USER)
+        .build()
+        .unwrap();
+
+    let office = client.get_office("BGM").await.unwrap();
+    assert_eq!(office.name, "Binghamton, NY");
+    assert_eq!(office.address.address_locality, "Johnson City");
+    assert_eq!(office.approved_observation_stations.len(), 2);
+
+    let radar = client.get_radar_station("KBGM").await.unwrap();
+    assert_eq!(radar.properties.name, "Binghamton");
+    assert_eq!(radar.properties.station_type, "WSR-88D");
+    let (latitude, longitude) = radar.position().unwrap();
+    assert_eq!((latitude.round(), longitude.round()), (42., -76.));
+}
+
+#[tokio::test]
+async fn forecast_discussion() {
+    let mock_server = MockServer::start().await;
+    mount(
+        &mock_server,
+        "/products/types/AFD/locations/BGM",
+        "products_afd",
+    )
+    .await;
+    mount(
+        &mock_server,
+        "/products/1c2cd5b4-8e0c-4e5b-9d7c-0d2b1b9c6a51",
+        "product_afd",
+    )
+    .await;
+
+    let mut client = ApiClient::builder()
+        .base_url(&mock_server.uri())
+        .api_key(APP, USER)
+        .build()
+        .unwrap();
+
+    let products = client.get_products("AFD", "BGM").await.unwrap();
+    assert_eq!(products.products.len(), 2);
+
+    // The latest of the two
+    let product = client.get_forecast_discussion("BGM").await.unwrap();
+    assert_eq!(product.id, "1c2cd5b4-8e0c-4e5b-9d7c-0d2b1b9c6a51");
+    assert_eq!(product.product_code, "AFD");
+
+    let text = product.wrapped_text(40);
+    assert!(text.lines().all(|x| x.chars().count() <= 40));
+    assert!(text.contains("\n.SYNOPSIS...\n"));
+    assert!(text.contains("\n&&\n"));
+}
@@ -0,0 +1,93 @@
+use chrono::{TimeZone, Utc};
+use chrono_tz::America::New_York;
+
+use wthr::{
+    forecast::{Forecast, GridpointData},
+    rules::{notify, samples_from_gridpoint, samples_from_periods, Comparison, Field, Rule},
+};
+
+mod common;
+use common::{json, tempcache};
+
+const HOME: &str = "https://api.weather.gov/gridpoints/BGM/44,69";
+const CABIN: &str = "https://api.weather.gov/gridpoints/BTV/90,55";
+
+fn rule(name: &str, field: Field, op: Comparison, value: f64) -> Rule {
+    Rule {
+        name: name.to_string(),
+        field,
+        op,
+        value,
+        within_hours: 24,
+        between: None,
+        command: None,
+    }
+}
+
+#[test]
+fn gridpoint_units() {
+    let data: GridpointData = serde_json::from_str(&json("gridpoint_data")).unwrap();
+    let samples = samples_from_gridpoint(&data).unwrap();
+
+    // 20°C at the first valid time
+    let first = samples
+        .iter()
+        .find(|x| x.field == Field::Temperature)
+        .unwrap();
+    assert_eq!(first.start_time, Utc.ymd(2021, 6, 10).and_hms(13, 0, 0));
+    assert_eq!(first.value, 68.);
+    assert!(samples
+        .windows(2)
+        .all(|x| x[0].start_time <= x[1].start_time));
+}
+
+#[test]
+fn rules_fire_once() {
+    let (mut cache, _tempdir) = tempcache();
+    let forecast: Forecast = serde_json::from_str(&json("forecast")).unwrap();
+    let samples = samples_from_periods(&forecast.properties.periods);
+    let now = Utc.ymd(2021, 6, 12).and_hms(1, 0, 0);
+    let rules = vec![
+        rule("warm", Field::Temperature, Comparison::Above, 75.),
+        rule("frost", Field::Temperature, Comparison::Below, 33.),
+    ];
+
+    let (fired, errors) = notify(&mut cache, &rules, HOME, &samples, now, &New_York);
+    assert!(errors.is_empty());
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].rule.name, "warm");
+    assert_eq!(fired[0].value, 78.);
+
+    let (fired, _) = notify(&mut cache, &rules, HOME, &samples, now, &New_York);
+    assert!(fired.is_empty());
+
+    // The same forecast somewhere else is news there
+    let (fired, _) = notify(&mut cache, &rules, CABIN, &samples, now, &New_York);
+    assert_eq!(fired.len(), 1);
+}
+
+#[test]
+fn failed_commands_fire_again() {
+    let (mut cache, _tempdir) = tempcache();
+    let forecast: Forecast = serde_json::from_str(&json("forecast")).unwrap();
+    let samples = samples_from_periods(&forecast.properties.periods);
+    let now = Utc.ymd(2021, 6, 12).and_hms(1, 0, 0);
+    let mut failing = rule("warm", Field::Temperature, Comparison::Above, 75.);
+    failing.command = Some("exit 1".to_string());
+    let mut rules = vec![
+        failing,
+        rule("windy", Field::WindSpeed, Comparison::AtLeast, 0.),
+    ];
+
+    // The failure doesn't stop the other rule
+    let (fired, errors) = notify(&mut cache, &rules, HOME, &samples, now, &New_York);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].rule.name, "windy");
+
+    rules[0].command = Some("true".to_string());
+    let (fired, errors) = notify(&mut cache, &rules, HOME, &samples, now, &New_York);
+    assert!(errors.is_empty());
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].rule.name, "warm");
+}